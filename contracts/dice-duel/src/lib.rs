@@ -2,16 +2,45 @@
 
 //! # Dice Duel
 //!
-//! A two-player dice game where each player rolls two dice.
-//! The player with the highest total wins (ties go to player 1).
+//! A dice game for 2-6 players where each player rolls a handful of dice.
+//! The player with the highest total wins the round.
+//!
+//! **Game Variants:**
+//! A `DiceConfig` given at start picks the number of dice and their sides, how many
+//! rounds are played (best-of-N, stopping once the lead can no longer be caught) and what
+//! happens on a tie: re-roll it, call it a draw shared by the tied seats, or give it to the
+//! earliest tied seat (player 1 on a two-player table). Without a config the classic game
+//! is played: one round of two six-sided dice, where a two-player tie goes to player 1 and
+//! a tie at a larger table is shared.
+//!
+//! **Pig:**
+//! The contract also hosts Pig, a turn-based push-your-luck game played at the same
+//! tables and through the same Game Hub sessions; see the `pig` module.
+//!
+//! **Seating and Turns:**
+//! Players sit at a table in the order they were given to `start_game`/`start_table`
+//! and commit their rolls in seat order.
+//!
+//! **Randomness:**
+//! Without a beacon, the dice are seeded from the session ID and the player addresses, so
+//! every roll is known before the game even starts. When the admin configures a randomness
+//! beacon, the game instead requests a seed from the beacon once the last seat has rolled
+//! and `reveal_winner` waits for that seed to be finalized. Pig always needs the beacon:
+//! every Pig roll is drawn from a beacon round of its own.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
+//! Two-seat games with a single winner use the Game Hub's two-player `start_game`/`end_game`
+//! session, which is all the Game Hub interface defines. Tables of 3-6 seats and shared
+//! wins (`start_game_multi`/`end_game_multi`) are extensions only some hubs implement, such
+//! as `mock-game-hub`. They are off until the admin confirms the configured hub supports
+//! them with `set_hub_extensions`; until then only two-seat games whose ties go to player 1
+//! can be started.
 
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl,
-    contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, Vec, contract, contractclient, contracterror,
+    contractimpl, contracttype, vec
 };
 
 // Import GameHub contract interface
@@ -28,11 +57,31 @@ pub trait GameHub {
         player2_points: i128,
     );
 
+    // Extensions, only called when `set_hub_extensions` is on
+    fn start_game_multi(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    );
+
     fn end_game(
         env: Env,
         session_id: u32,
         player1_won: bool
     );
+
+    fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>);
+}
+
+// Import RandomnessBeacon contract interface
+// Seeds are requested and read per (this contract, session ID)
+#[contractclient(name = "BeaconClient")]
+pub trait RandomnessBeacon {
+    fn request_seed(env: Env, requester: Address, request_id: u32);
+
+    fn get_seed(env: Env, requester: Address, request_id: u32) -> Option<BytesN<32>>;
 }
 
 // ============================================================================
@@ -48,33 +97,85 @@ pub enum Error {
     AlreadyRolled = 3,
     BothPlayersNotRolled = 4,
     GameAlreadyEnded = 5,
+    SelfPlay = 6,
+    InvalidTableSize = 7,
+    NotYourTurn = 8,
+    InvalidConfig = 9,
+    NothingToHold = 10,
+    TurnNotExpired = 11,
+    CannotClaimOwnTimeout = 12,
+    SeedNotReady = 13,
+    ChannelNotFound = 14,
+    ChannelExists = 15,
+    ChannelOpen = 16,
+    InvalidChannelState = 17,
+    StaleChannelState = 18,
+    ChallengeClosed = 19,
+    ChallengeOpen = 20,
+    NoChannelState = 21,
+    HubExtensionsRequired = 22,
+    BeaconRequired = 23,
+    RollPending = 24,
+    NoPendingRoll = 25,
 }
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TiePolicy {
+    Reroll,      // Tied rounds are rolled again
+    Draw,        // Every tied seat is credited with the round
+    Player1Wins, // The earliest tied seat takes the round
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiceConfig {
+    pub dice_count: u32, // Dice rolled by each seat per round
+    pub dice_sides: u32,
+    pub rounds: u32, // Best-of-N rounds
+    pub tie_policy: TiePolicy,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundResult {
+    pub dice: Vec<Vec<u32>>, // Dice rolled by each seat, in seat order
+    pub totals: Vec<u32>,    // Total of each seat's dice, in seat order
+    pub winners: Vec<Address>, // Empty if the round was re-rolled
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Seat {
+    pub player: Address,
+    pub points: i128,
+    pub rolled: bool,
+    pub round_wins: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
-    pub player1: Address,
-    pub player2: Address,
-    pub player1_points: i128,
-    pub player2_points: i128,
-    pub player1_rolled: bool,
-    pub player2_rolled: bool,
-    pub player1_die1: Option<u32>,
-    pub player1_die2: Option<u32>,
-    pub player2_die1: Option<u32>,
-    pub player2_die2: Option<u32>,
-    pub winner: Option<Address>,
+    pub seats: Vec<Seat>,
+    pub config: DiceConfig,
+    pub rounds: Vec<RoundResult>, // Empty until the winner is revealed
+    pub winners: Vec<Address>,    // Empty while the game is running
+    pub beacon: Option<Address>,  // Randomness beacon in force when the game started
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Game(u32),
+    PigGame(u32),
+    PigRolls,
     GameHubAddress,
+    BeaconAddress,
+    HubExtensions,
     Admin,
 }
 
@@ -88,14 +189,179 @@ pub enum DataKey {
 /// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
 const GAME_TTL_LEDGERS: u32 = 518_400;
 
+/// Smallest and largest table a game can be started with
+const MIN_SEATS: u32 = 2;
+const MAX_SEATS: u32 = 6;
+
+/// Limits on a game's dice configuration
+const MAX_DICE: u32 = 10;
+const MIN_SIDES: u32 = 2;
+const MAX_SIDES: u32 = 100;
+const MAX_ROUNDS: u32 = 9;
+
+/// Maximum number of tied rounds re-rolled under `TiePolicy::Reroll` before the tie stands as a draw
+const MAX_REROLLS: u32 = 10;
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Roll a single die (1-6) using deterministic PRNG
-fn roll_die(env: &Env, seed: BytesN<32>) -> u32 {
+/// Roll a single die (1-sides) using deterministic PRNG
+fn roll_die(env: &Env, seed: BytesN<32>, sides: u32) -> u32 {
     env.prng().seed(seed.into());
-    env.prng().gen_range::<u64>(1..=6) as u32
+    env.prng().gen_range::<u64>(1..=sides as u64) as u32
+}
+
+/// Roll one die for a seat; the seed is the game seed plus the seat and die numbers.
+/// `roll` counts every round rolled so far (re-rolls included) and is only mixed in
+/// after the first, so the classic game keeps its original dice.
+fn roll_seat_die(env: &Env, base_seed: &BytesN<32>, roll: u32, seat: u32, die: u32, sides: u32) -> u32 {
+    let mut roll_seed_bytes = Bytes::new(env);
+    roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
+    roll_seed_bytes.append(&Bytes::from_array(env, &[(seat + 1) as u8, (die + 1) as u8]));
+    if roll > 0 {
+        roll_seed_bytes.append(&Bytes::from_array(env, &roll.to_be_bytes()));
+    }
+    roll_die(env, env.crypto().keccak256(&roll_seed_bytes).into(), sides)
+}
+
+/// The classic game: one round of two six-sided dice.
+/// Two-player ties go to player 1; ties at larger tables are shared.
+fn classic_config(seat_count: u32) -> DiceConfig {
+    DiceConfig {
+        dice_count: 2,
+        dice_sides: 6,
+        rounds: 1,
+        tie_policy: if seat_count == 2 {
+            TiePolicy::Player1Wins
+        } else {
+            TiePolicy::Draw
+        },
+    }
+}
+
+/// Check a dice configuration is within the supported limits
+fn validate_config(config: &DiceConfig) -> Result<(), Error> {
+    if config.dice_count == 0
+        || config.dice_count > MAX_DICE
+        || config.dice_sides < MIN_SIDES
+        || config.dice_sides > MAX_SIDES
+        || config.rounds == 0
+        || config.rounds > MAX_ROUNDS
+    {
+        return Err(Error::InvalidConfig);
+    }
+    Ok(())
+}
+
+/// Indices of the seats holding the highest value
+fn leaders(env: &Env, values: &Vec<u32>) -> Vec<u32> {
+    let best = values.iter().max().unwrap_or(0);
+    let mut leaders = Vec::new(env);
+    for i in 0..values.len() {
+        if values.get_unchecked(i) == best {
+            leaders.push_back(i);
+        }
+    }
+    leaders
+}
+
+/// True once no seat can catch the leader in the rounds left to play
+fn lead_is_safe(wins: &Vec<u32>, rounds_left: u32) -> bool {
+    let mut best = 0;
+    let mut second = 0;
+    for count in wins.iter() {
+        if count > best {
+            second = best;
+            best = count;
+        } else if count > second {
+            second = count;
+        }
+    }
+    best > second + rounds_left
+}
+
+/// Roll every round of a game and credit round wins to the seats.
+/// Returns the round results and the indices of the winning seats.
+/// A match tied on round wins is settled like a tied round: extra rounds under
+/// `Reroll`, the earliest seat under `Player1Wins`, or shared under `Draw`.
+fn play_rounds(
+    env: &Env,
+    base_seed: &BytesN<32>,
+    seats: &mut Vec<Seat>,
+    config: &DiceConfig,
+) -> (Vec<RoundResult>, Vec<u32>) {
+    let mut results = Vec::new(env);
+    let mut wins = Vec::new(env);
+    for _ in 0..seats.len() {
+        wins.push_back(0u32);
+    }
+    let mut played = 0u32;
+    let mut rerolls = 0u32;
+    let mut roll = 0u32;
+
+    loop {
+        let can_reroll = config.tie_policy == TiePolicy::Reroll && rerolls < MAX_REROLLS;
+        let extra_round = played >= config.rounds;
+        if extra_round || lead_is_safe(&wins, config.rounds - played) {
+            // Extra rounds are only rolled to break a tied match under the re-roll policy
+            if leaders(env, &wins).len() == 1 || !can_reroll {
+                break;
+            }
+        }
+
+        // Roll every seat's dice
+        let mut dice = Vec::new(env);
+        let mut totals = Vec::new(env);
+        for seat in 0..seats.len() {
+            let mut seat_dice = Vec::new(env);
+            let mut total = 0u32;
+            for die in 0..config.dice_count {
+                let value = roll_seat_die(env, base_seed, roll, seat, die, config.dice_sides);
+                seat_dice.push_back(value);
+                total += value;
+            }
+            dice.push_back(seat_dice);
+            totals.push_back(total);
+        }
+        roll += 1;
+
+        // Pick the round winners according to the tie policy
+        let mut round_leaders = leaders(env, &totals);
+        let mut winners = Vec::new(env);
+        if extra_round || (round_leaders.len() > 1 && can_reroll) {
+            // Tie-break rounds count against the re-roll limit too
+            rerolls += 1;
+        }
+        if round_leaders.len() == 1 || !can_reroll {
+            if round_leaders.len() > 1 && config.tie_policy == TiePolicy::Player1Wins {
+                round_leaders = vec![env, round_leaders.get_unchecked(0)];
+            }
+            for i in round_leaders.iter() {
+                wins.set(i, wins.get_unchecked(i) + 1);
+                winners.push_back(seats.get_unchecked(i).player);
+            }
+            played += 1;
+        }
+
+        results.push_back(RoundResult {
+            dice,
+            totals,
+            winners,
+        });
+    }
+
+    for i in 0..seats.len() {
+        let mut seat = seats.get_unchecked(i);
+        seat.round_wins = wins.get_unchecked(i);
+        seats.set(i, seat);
+    }
+
+    let mut match_leaders = leaders(env, &wins);
+    if match_leaders.len() > 1 && config.tie_policy == TiePolicy::Player1Wins {
+        match_leaders = vec![env, match_leaders.get_unchecked(0)];
+    }
+    (results, match_leaders)
 }
 
 // ============================================================================
@@ -132,6 +398,7 @@ impl DiceDuelContract {
     /// * `player2` - Address of second player
     /// * `player1_points` - Points amount committed by player 1
     /// * `player2_points` - Points amount committed by player 2
+    /// * `config` - Dice, rounds and tie policy, or `None` for the classic game
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -139,50 +406,57 @@ impl DiceDuelContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
+        config: Option<DiceConfig>,
     ) -> Result<(), Error> {
-        // Prevent self-play: Player 1 and Player 2 must be different
-        if player1 == player2 {
-            panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
-        }
-
-        // Require authentication from both players (they consent to committing points)
-        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
-        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
-
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        let players = vec![&env, player1, player2];
+        let points = vec![&env, player1_points, player2_points];
+        Self::start_table(env, session_id, players, points, config)
+    }
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+    /// Start a new game at a table of 2-6 players with per-seat points.
+    /// Players are seated, and roll, in the order given.
+    /// This creates a session in the Game Hub and locks points before starting the game.
+    ///
+    /// **CRITICAL:** This method requires authorization from THIS contract (not players).
+    /// The Game Hub will call `game_id.require_auth()` which checks this contract's address.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `players` - Addresses of the players, in seat order
+    /// * `points` - Points amount committed by each player, in seat order
+    /// * `config` - Dice, rounds and tie policy, or `None` for the classic game
+    pub fn start_table(
+        env: Env,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+        config: Option<DiceConfig>,
+    ) -> Result<(), Error> {
+        let config = config.unwrap_or_else(|| classic_config(players.len()));
+        validate_config(&config)?;
+        // Any other tie policy can end in a shared win
+        if config.tie_policy != TiePolicy::Player1Wins && !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
 
-        // Call the Game Hub to start the session and lock points
-        // This requires THIS contract's authorization (env.current_contract_address())
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+        Self::open_session(&env, session_id, &players, &points)?;
 
         // Create game (dice not rolled yet - will be generated in reveal_winner)
+        let mut seats = Vec::new(&env);
+        for i in 0..players.len() {
+            seats.push_back(Seat {
+                player: players.get_unchecked(i),
+                points: points.get_unchecked(i),
+                rolled: false,
+                round_wins: 0,
+            });
+        }
         let game = Game {
-            player1: player1.clone(),
-            player2: player2.clone(),
-            player1_points,
-            player2_points,
-            player1_rolled: false,
-            player2_rolled: false,
-            player1_die1: None,
-            player1_die2: None,
-            player2_die1: None,
-            player2_die2: None,
-            winner: None,
+            seats,
+            config,
+            rounds: Vec::new(&env),
+            winners: Vec::new(&env),
+            beacon: Self::get_beacon(env.clone()),
         };
 
         // Store game in temporary storage with 30-day TTL
@@ -194,13 +468,12 @@ impl DiceDuelContract {
             .temporary()
             .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
 
-        // Event emitted by GameHub contract (GameStarted)
-
         Ok(())
     }
 
     /// Commit a roll for the current game.
-    /// Both players must roll before the winner can be revealed.
+    /// Players roll in seat order, and every player must roll before the winner can be revealed.
+    /// When the game uses a randomness beacon, the last seat's roll requests the seed.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
@@ -216,42 +489,57 @@ impl DiceDuelContract {
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
-        // Check game is still active (no winner yet)
-        if game.winner.is_some() {
+        // Check game is still active (no winners yet)
+        if !game.winners.is_empty() {
             return Err(Error::GameAlreadyEnded);
         }
+        channel::ensure_no_channel(&env, session_id)?;
+
+        let seat_index = game
+            .seats
+            .iter()
+            .position(|seat| seat.player == player)
+            .ok_or(Error::NotPlayer)? as u32;
+        let mut seat = game.seats.get_unchecked(seat_index);
+        if seat.rolled {
+            return Err(Error::AlreadyRolled);
+        }
 
-        // Update roll commitment for the appropriate player
-        if player == game.player1 {
-            if game.player1_rolled {
-                return Err(Error::AlreadyRolled);
-            }
-            game.player1_rolled = true;
-        } else if player == game.player2 {
-            if game.player2_rolled {
-                return Err(Error::AlreadyRolled);
-            }
-            game.player2_rolled = true;
-        } else {
-            return Err(Error::NotPlayer);
+        // Seats roll in order: every earlier seat must have rolled already
+        if seat_index > 0 && !game.seats.get_unchecked(seat_index - 1).rolled {
+            return Err(Error::NotYourTurn);
         }
 
+        // Update roll commitment for the seat
+        seat.rolled = true;
+        game.seats.set(seat_index, seat);
+
         // Store updated game in temporary storage
         env.storage().temporary().set(&key, &game);
 
+        // Every seat has rolled, ask the beacon for the seed the dice are rolled with
+        if let Some(beacon) = &game.beacon {
+            if seat_index == game.seats.len() - 1 {
+                BeaconClient::new(&env, beacon)
+                    .request_seed(&env.current_contract_address(), &session_id);
+            }
+        }
+
         Ok(())
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
-    /// Can only be called after both players have rolled.
-    /// This generates dice rolls for both players, determines the winner, and ends the session.
+    /// Reveal the winners of the game and submit outcome to GameHub.
+    /// Can only be called after every player has rolled.
+    /// This rolls every round for every seat, determines the winners, and ends the session.
+    /// Every round's dice, totals and winners are kept in the game for inspection.
+    /// Games using a randomness beacon can only be revealed once the beacon seed is finalized.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `Vec<Address>` - Addresses of the winning players
+    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Vec<Address>, Error> {
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -260,85 +548,57 @@ impl DiceDuelContract {
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
-        // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+        // Check if game already ended (has winners)
+        if !game.winners.is_empty() {
+            return Ok(game.winners);
         }
+        // Channel games are settled with settle_channel
+        channel::ensure_no_channel(&env, session_id)?;
 
-        // Check both players have rolled
-        if !game.player1_rolled || !game.player2_rolled {
+        // Check every player has rolled
+        if game.seats.iter().any(|seat| !seat.rolled) {
             return Err(Error::BothPlayersNotRolled);
         }
 
-        // Generate deterministic dice rolls (1-6)
+        // Generate deterministic dice rolls
         // Seed components (all deterministic and identical between sim/submit):
         // 1. Session ID - unique per game
-        // 2. Player addresses - both players contribute
+        // 2. Player addresses - every seat contributes
         //
         // Note: We do NOT include ledger sequence or timestamp because those differ
         // between simulation and submission, which would cause different winners.
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-        seed_bytes.append(&game.player1.to_string().to_bytes());
-        seed_bytes.append(&game.player2.to_string().to_bytes());
-        let base_seed = env.crypto().keccak256(&seed_bytes);
-
-        // Roll dice for both players using unique seeds
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[1, 1]));
-        let player1_die1 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[1, 2]));
-        let player1_die2 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[2, 1]));
-        let player2_die1 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[2, 2]));
-        let player2_die2 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        game.player1_die1 = Some(player1_die1);
-        game.player1_die2 = Some(player1_die2);
-        game.player2_die1 = Some(player2_die1);
-        game.player2_die2 = Some(player2_die2);
-
-        // Determine winner (if tie, player1 wins)
-        let player1_total = player1_die1 + player1_die2;
-        let player2_total = player2_die1 + player2_die2;
-        let winner = if player1_total >= player2_total {
-            game.player1.clone()
-        } else {
-            game.player2.clone()
+        //
+        // With a randomness beacon the beacon's seed is the base seed.
+        let base_seed: BytesN<32> = match &game.beacon {
+            Some(beacon) => BeaconClient::new(&env, beacon)
+                .get_seed(&env.current_contract_address(), &session_id)
+                .ok_or(Error::SeedNotReady)?,
+            None => {
+                let mut seed_bytes = Bytes::new(&env);
+                seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
+                for seat in game.seats.iter() {
+                    seed_bytes.append(&seat.player.to_string().to_bytes());
+                }
+                env.crypto().keccak256(&seed_bytes).into()
+            }
         };
 
-        // Update game with winner (this marks the game as ended)
-        game.winner = Some(winner.clone());
-        env.storage().temporary().set(&key, &game);
-
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        // Roll every round and pick the winners according to the game's config
+        let (rounds, winner_seats) = play_rounds(&env, &base_seed, &mut game.seats, &game.config);
+        let mut winners = Vec::new(&env);
+        for i in winner_seats.iter() {
+            winners.push_back(game.seats.get_unchecked(i).player);
+        }
+        game.rounds = rounds;
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        // Update game with winners (this marks the game as ended)
+        game.winners = winners.clone();
+        env.storage().temporary().set(&key, &game);
 
-        // Call GameHub to end the session
-        // This unlocks points and updates standings
-        // Event emitted by the Game Hub contract (GameEnded)
-        let player1_won = winner == game.player1; // true if player1 won, false if player2 won
-        game_hub.end_game(&session_id, &player1_won);
+        let player1 = game.seats.get_unchecked(0).player;
+        Self::close_session(&env, session_id, game.seats.len(), &player1, &winners);
 
-        Ok(winner)
+        Ok(winners)
     }
 
     /// Get game information.
@@ -347,7 +607,7 @@ impl DiceDuelContract {
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Game` - The game state (includes dice after game ends)
+    /// * `Game` - The game state (includes every round's dice after game ends)
     pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
         let key = DataKey::Game(session_id);
         env.storage()
@@ -356,6 +616,97 @@ impl DiceDuelContract {
             .ok_or(Error::GameNotFound)
     }
 
+    // ========================================================================
+    // Internal Helper Functions
+    // ========================================================================
+
+    /// Get a GameHub client for the configured hub
+    fn game_hub(env: &Env) -> GameHubClient<'_> {
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        GameHubClient::new(env, &game_hub_addr)
+    }
+
+    /// Validate a table, collect every player's consent and open the Game Hub session.
+    /// Two-seat tables use the two-player session, larger tables the N-player session,
+    /// which needs the hub extensions.
+    fn open_session(
+        env: &Env,
+        session_id: u32,
+        players: &Vec<Address>,
+        points: &Vec<i128>,
+    ) -> Result<(), Error> {
+        if players.len() < MIN_SEATS || players.len() > MAX_SEATS || points.len() != players.len() {
+            return Err(Error::InvalidTableSize);
+        }
+        if players.len() > 2 && !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
+
+        // Prevent self-play: every seat must be taken by a different player
+        for i in 0..players.len() {
+            for j in (i + 1)..players.len() {
+                if players.get_unchecked(i) == players.get_unchecked(j) {
+                    return Err(Error::SelfPlay);
+                }
+            }
+        }
+
+        // Require authentication from every player (they consent to committing points)
+        for i in 0..players.len() {
+            players.get_unchecked(i).require_auth_for_args(vec![
+                env,
+                session_id.into_val(env),
+                points.get_unchecked(i).into_val(env),
+            ]);
+        }
+
+        // Call the Game Hub to start the session and lock points
+        // This requires THIS contract's authorization (env.current_contract_address())
+        let game_hub = Self::game_hub(env);
+        if players.len() == 2 {
+            game_hub.start_game(
+                &env.current_contract_address(),
+                &session_id,
+                &players.get_unchecked(0),
+                &players.get_unchecked(1),
+                &points.get_unchecked(0),
+                &points.get_unchecked(1),
+            );
+        } else {
+            game_hub.start_game_multi(&env.current_contract_address(), &session_id, players, points);
+        }
+
+        // Event emitted by GameHub contract (GameStarted)
+
+        Ok(())
+    }
+
+    /// End a Game Hub session with the given winners.
+    /// A single winner at a two-seat table settles through the two-player session;
+    /// everything else (including a two-player draw) shares the payout through the N-player call.
+    fn close_session(
+        env: &Env,
+        session_id: u32,
+        seat_count: u32,
+        player1: &Address,
+        winners: &Vec<Address>,
+    ) {
+        // Call GameHub to end the session
+        // This unlocks points and updates standings
+        // Event emitted by the Game Hub contract (GameEnded)
+        let game_hub = Self::game_hub(env);
+        if seat_count == 2 && winners.len() == 1 {
+            let player1_won = winners.get_unchecked(0) == *player1;
+            game_hub.end_game(&session_id, &player1_won);
+        } else {
+            game_hub.end_game_multi(&session_id, winners);
+        }
+    }
+
     // ========================================================================
     // Admin Functions
     // ========================================================================
@@ -414,6 +765,63 @@ impl DiceDuelContract {
             .set(&DataKey::GameHubAddress, &new_hub);
     }
 
+    /// Get the randomness beacon used by newly started games
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The beacon contract address, or `None` if games seed themselves
+    pub fn get_beacon(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BeaconAddress)
+    }
+
+    /// Set the randomness beacon used by newly started dice games.
+    /// Games already in progress keep the beacon (or lack of one) they started with.
+    ///
+    /// # Arguments
+    /// * `beacon` - The beacon contract address, or `None` to go back to self-seeded games
+    pub fn set_beacon(env: Env, beacon: Option<Address>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        match beacon {
+            Some(beacon) => env.storage().instance().set(&DataKey::BeaconAddress, &beacon),
+            None => env.storage().instance().remove(&DataKey::BeaconAddress),
+        }
+    }
+
+    /// Whether the configured Game Hub supports the multi-seat extensions
+    ///
+    /// # Returns
+    /// * `bool` - True once the admin has turned the extensions on (off by default)
+    pub fn get_hub_extensions(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::HubExtensions)
+            .unwrap_or(false)
+    }
+
+    /// Turn the Game Hub extensions on or off.
+    /// Only turn them on for a hub that implements `start_game_multi` and `end_game_multi`;
+    /// the standard Game Hub only has `start_game` and `end_game`.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether tables of 3+ seats and shared wins are allowed
+    pub fn set_hub_extensions(env: Env, enabled: bool) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::HubExtensions, &enabled);
+    }
+
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
@@ -430,6 +838,16 @@ impl DiceDuelContract {
     }
 }
 
+// ============================================================================
+// Game Modes
+// ============================================================================
+
+mod channel;
+mod pig;
+
+pub use pig::{PigGame, PigSeat};
+pub use state_channel::{Channel, ChannelState};
+
 // ============================================================================
 // Tests
 // ============================================================================
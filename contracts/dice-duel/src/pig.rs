@@ -0,0 +1,328 @@
+//! # Pig
+//!
+//! A push-your-luck dice game played at the same 2-6 player tables as the duel.
+//! On their turn a player keeps rolling one die with `roll_turn`, adding each roll to a
+//! turn total, until they either `hold` (banking the turn total into their score) or roll
+//! a 1 (losing the turn total). Either way play passes to the next seat. The first player
+//! to bank the target score wins.
+//!
+//! **Randomness:**
+//! Every roll comes from its own randomness beacon round, so Pig needs the admin to have
+//! configured a beacon. `roll_turn` only commits the seat to the roll and requests the
+//! seed; nothing about the roll is known in that transaction, so a player cannot look at
+//! the result and revert. Once the beacon round is finalized anyone calls `resolve_roll`,
+//! which rolls the die from the seed and applies it. The seat cannot hold while a roll is
+//! pending.
+//!
+//! **Turn Deadlines:**
+//! The seat on turn must roll or hold within `PIG_TURN_TIMEOUT_LEDGERS` of its last move.
+//! After that any other seated player can `claim_pig_timeout`, which wipes the slow seat's
+//! turn total and passes play on. No seat is on the clock while a roll waits on the beacon.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+// The contract's client and args types are needed by `#[contractimpl]` in this module
+use crate::{
+    roll_die, BeaconClient, DataKey, DiceDuelContract, DiceDuelContractArgs,
+    DiceDuelContractClient, Error, GAME_TTL_LEDGERS,
+};
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PigSeat {
+    pub player: Address,
+    pub points: i128,
+    pub score: u32, // Points banked so far
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PigGame {
+    pub seats: Vec<PigSeat>,
+    pub target: u32,               // Score that wins the game
+    pub turn: u32,                 // Index of the seat on turn
+    pub turn_total: u32,           // Points rolled this turn, lost on a 1
+    pub turn_rolls: Vec<u32>,      // Rolls made so far this turn
+    pub last_roll: u32,            // Most recent roll at the table, 0 before the first
+    pub turn_deadline: u32,        // Ledger sequence after which the seat on turn can be timed out
+    pub winners: Vec<Address>,     // Empty while the game is running
+    pub beacon: Address,           // Randomness beacon in force when the game started
+    pub pending_roll: Option<u32>, // Beacon request ID of the roll waiting on its seed
+}
+
+/// Time a seat has to make each move (1 hour, ~5 seconds per ledger)
+const PIG_TURN_TIMEOUT_LEDGERS: u32 = 720;
+
+/// Highest target score a game can be started with
+const MAX_PIG_TARGET: u32 = 1_000;
+
+/// Pig rolls take beacon request IDs from the top half of the u32 range. Duels request
+/// their seed under the session ID, so the two never meet while session IDs stay below it.
+const PIG_ROLL_REQUEST_BASE: u32 = 1 << 31;
+
+/// Ledger sequence after which the seat now on turn can be timed out
+fn next_deadline(env: &Env) -> u32 {
+    env.ledger()
+        .sequence()
+        .saturating_add(PIG_TURN_TIMEOUT_LEDGERS)
+}
+
+/// Pass play to the next seat with a fresh turn
+fn pass_turn(env: &Env, game: &mut PigGame) {
+    game.turn = (game.turn + 1) % game.seats.len();
+    game.turn_total = 0;
+    game.turn_rolls = Vec::new(env);
+    game.turn_deadline = next_deadline(env);
+}
+
+#[contractimpl]
+impl DiceDuelContract {
+    /// Start a new game of Pig at a table of 2-6 players with per-seat points.
+    /// Players take turns in the order given. Needs a randomness beacon for the rolls.
+    /// This creates a session in the Game Hub and locks points before starting the game.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `players` - Addresses of the players, in seat order
+    /// * `points` - Points amount committed by each player, in seat order
+    /// * `target` - Score that wins the game (1-1000)
+    pub fn start_pig(
+        env: Env,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+        target: u32,
+    ) -> Result<(), Error> {
+        if target == 0 || target > MAX_PIG_TARGET {
+            return Err(Error::InvalidConfig);
+        }
+        let beacon = Self::get_beacon(env.clone()).ok_or(Error::BeaconRequired)?;
+
+        Self::open_session(&env, session_id, &players, &points)?;
+
+        let mut seats = Vec::new(&env);
+        for i in 0..players.len() {
+            seats.push_back(PigSeat {
+                player: players.get_unchecked(i),
+                points: points.get_unchecked(i),
+                score: 0,
+            });
+        }
+        let game = PigGame {
+            seats,
+            target,
+            turn: 0,
+            turn_total: 0,
+            turn_rolls: Vec::new(&env),
+            last_roll: 0,
+            turn_deadline: next_deadline(&env),
+            winners: Vec::new(&env),
+            beacon,
+            pending_roll: None,
+        };
+
+        // Store game in temporary storage with 30-day TTL
+        let game_key = DataKey::PigGame(session_id);
+        env.storage().temporary().set(&game_key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Roll the die on your turn.
+    /// The roll is committed now and requested from the randomness beacon; it is applied
+    /// by `resolve_roll` once the beacon has finalized the seed.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player on turn
+    ///
+    /// # Returns
+    /// * `u32` - The beacon request ID the roll waits on
+    pub fn roll_turn(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
+        player.require_auth();
+
+        let mut game = Self::load_pig_turn(&env, session_id, &player)?;
+        if game.pending_roll.is_some() {
+            return Err(Error::RollPending);
+        }
+
+        // Every roll gets its own beacon round
+        let rolls: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PigRolls)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::PigRolls, &((rolls + 1) % PIG_ROLL_REQUEST_BASE));
+        let request_id = PIG_ROLL_REQUEST_BASE | rolls;
+        BeaconClient::new(&env, &game.beacon)
+            .request_seed(&env.current_contract_address(), &request_id);
+        game.pending_roll = Some(request_id);
+
+        env.storage()
+            .temporary()
+            .set(&DataKey::PigGame(session_id), &game);
+
+        Ok(request_id)
+    }
+
+    /// Apply the pending roll once the beacon has finalized its seed. Anyone can call this.
+    /// Anything but a 1 is added to the turn total; a 1 wipes the turn total and ends the turn.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `u32` - The value rolled (1-6)
+    pub fn resolve_roll(env: Env, session_id: u32) -> Result<u32, Error> {
+        let mut game = Self::get_pig_game(env.clone(), session_id)?;
+        if !game.winners.is_empty() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        let request_id = game.pending_roll.ok_or(Error::NoPendingRoll)?;
+        let seed = BeaconClient::new(&env, &game.beacon)
+            .get_seed(&env.current_contract_address(), &request_id)
+            .ok_or(Error::SeedNotReady)?;
+
+        let roll = roll_die(&env, seed, 6);
+        game.pending_roll = None;
+        game.last_roll = roll;
+        if roll == 1 {
+            pass_turn(&env, &mut game);
+        } else {
+            game.turn_total += roll;
+            game.turn_rolls.push_back(roll);
+            game.turn_deadline = next_deadline(&env);
+        }
+
+        env.storage()
+            .temporary()
+            .set(&DataKey::PigGame(session_id), &game);
+
+        Ok(roll)
+    }
+
+    /// Bank the turn total into your score and end your turn.
+    /// Reaching the target score wins the game and ends the session.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player on turn
+    pub fn hold(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let mut game = Self::load_pig_turn(&env, session_id, &player)?;
+        if game.pending_roll.is_some() {
+            return Err(Error::RollPending);
+        }
+        if game.turn_total == 0 {
+            return Err(Error::NothingToHold);
+        }
+
+        let mut seat = game.seats.get_unchecked(game.turn);
+        seat.score += game.turn_total;
+        let won = seat.score >= game.target;
+        game.seats.set(game.turn, seat);
+
+        if won {
+            let winners = Vec::from_array(&env, [player]);
+            let player1 = game.seats.get_unchecked(0).player;
+            Self::close_session(&env, session_id, game.seats.len(), &player1, &winners);
+            game.winners = winners;
+            game.turn_total = 0;
+        } else {
+            pass_turn(&env, &mut game);
+        }
+
+        env.storage()
+            .temporary()
+            .set(&DataKey::PigGame(session_id), &game);
+
+        Ok(())
+    }
+
+    /// Claim that the seat on turn let its deadline pass.
+    /// Any other seated player can call this once the ledger sequence is past the turn
+    /// deadline. The slow seat loses its turn total and play passes to the next seat.
+    /// A seat waiting on the beacon for its roll cannot be timed out.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `claimer` - Address of the seated player making the claim
+    pub fn claim_pig_timeout(env: Env, session_id: u32, claimer: Address) -> Result<(), Error> {
+        claimer.require_auth();
+
+        let mut game = Self::get_pig_game(env.clone(), session_id)?;
+        if !game.winners.is_empty() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        let claimer_index = Self::pig_seat_of(&game, &claimer)?;
+        if claimer_index == game.turn {
+            return Err(Error::CannotClaimOwnTimeout);
+        }
+        if game.pending_roll.is_some() {
+            return Err(Error::RollPending);
+        }
+        if env.ledger().sequence() <= game.turn_deadline {
+            return Err(Error::TurnNotExpired);
+        }
+
+        pass_turn(&env, &mut game);
+        env.storage()
+            .temporary()
+            .set(&DataKey::PigGame(session_id), &game);
+
+        Ok(())
+    }
+
+    /// Get Pig game information.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `PigGame` - The game state
+    pub fn get_pig_game(env: Env, session_id: u32) -> Result<PigGame, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::PigGame(session_id))
+            .ok_or(Error::GameNotFound)
+    }
+
+    // ========================================================================
+    // Internal Helper Functions
+    // ========================================================================
+
+    /// Resolve which seat a player occupies
+    fn pig_seat_of(game: &PigGame, player: &Address) -> Result<u32, Error> {
+        game.seats
+            .iter()
+            .position(|seat| seat.player == *player)
+            .map(|index| index as u32)
+            .ok_or(Error::NotPlayer)
+    }
+
+    /// Load a game in which `player` is on turn right now
+    fn load_pig_turn(env: &Env, session_id: u32, player: &Address) -> Result<PigGame, Error> {
+        let game = Self::get_pig_game(env.clone(), session_id)?;
+
+        // Check game is still active (no winners yet)
+        if !game.winners.is_empty() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if Self::pig_seat_of(&game, player)? != game.turn {
+            return Err(Error::NotYourTurn);
+        }
+
+        Ok(game)
+    }
+}
@@ -3,14 +3,24 @@
 // Unit tests for the dice-duel contract using a simple mock GameHub.
 // These tests verify game logic independently of the full GameHub system.
 
-use crate::{DiceDuelContract, DiceDuelContractClient, Error};
+use crate::{
+    ChannelState, DiceConfig, DiceDuelContract, DiceDuelContractClient, Error, Game, PigGame, Seat,
+    TiePolicy,
+};
+use ed25519_dalek::{Signer as _, SigningKey};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, BytesN, Env, Vec};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
 // ============================================================================
 
+#[contracttype]
+pub enum HubDataKey {
+    Player1Won(u32),
+    Winners(u32),
+}
+
 #[contract]
 pub struct MockGameHub;
 
@@ -28,15 +38,119 @@ impl MockGameHub {
         // Mock implementation - does nothing
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+    pub fn start_game_multi(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _players: Vec<Address>,
+        _points: Vec<i128>,
+    ) {
         // Mock implementation - does nothing
     }
 
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        // Track the result so tests can check which session call was used
+        env.storage()
+            .instance()
+            .set(&HubDataKey::Player1Won(session_id), &player1_won);
+    }
+
+    pub fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>) {
+        // Track winners so tests can check multi-winner payouts
+        env.storage()
+            .instance()
+            .set(&HubDataKey::Winners(session_id), &winners);
+    }
+
+    pub fn get_player1_won(env: Env, session_id: u32) -> Option<bool> {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::Player1Won(session_id))
+    }
+
+    pub fn get_winners(env: Env, session_id: u32) -> Option<Vec<Address>> {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::Winners(session_id))
+    }
+
     pub fn add_game(_env: Env, _game_address: Address) {
         // Mock implementation - does nothing
     }
 }
 
+// ============================================================================
+// Standard GameHub for Unit Testing
+// ============================================================================
+
+// Only the two-player Game Hub interface, without any of the extensions
+#[contract]
+pub struct StandardGameHub;
+
+#[contractimpl]
+impl StandardGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        env.storage().instance().set(&session_id, &player1_won);
+    }
+
+    pub fn get_player1_won(env: Env, session_id: u32) -> Option<bool> {
+        env.storage().instance().get(&session_id)
+    }
+}
+
+// ============================================================================
+// Mock RandomnessBeacon for Unit Testing
+// ============================================================================
+
+#[contracttype]
+pub enum BeaconDataKey {
+    Requested(Address, u32),
+    Seed(Address, u32),
+}
+
+#[contract]
+pub struct MockBeacon;
+
+#[contractimpl]
+impl MockBeacon {
+    pub fn request_seed(env: Env, requester: Address, request_id: u32) {
+        // Track requests so tests can check when the seed was asked for
+        env.storage()
+            .instance()
+            .set(&BeaconDataKey::Requested(requester, request_id), &true);
+    }
+
+    pub fn get_seed(env: Env, requester: Address, request_id: u32) -> Option<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&BeaconDataKey::Seed(requester, request_id))
+    }
+
+    pub fn was_requested(env: Env, requester: Address, request_id: u32) -> bool {
+        env.storage()
+            .instance()
+            .has(&BeaconDataKey::Requested(requester, request_id))
+    }
+
+    /// Stand-in for a finalized commit-reveal round
+    pub fn finalize(env: Env, requester: Address, request_id: u32, seed: BytesN<32>) {
+        env.storage()
+            .instance()
+            .set(&BeaconDataKey::Seed(requester, request_id), &seed);
+    }
+}
+
 // ============================================================================
 // Test Helpers
 // ============================================================================
@@ -77,12 +191,64 @@ fn setup_test() -> (
     // Register dice-duel as a whitelisted game (mock does nothing)
     game_hub.add_game(&contract_id);
 
+    // The mock implements the multi-seat extensions
+    client.set_hub_extensions(&true);
+
     let player1 = Address::generate(&env);
     let player2 = Address::generate(&env);
 
     (env, client, game_hub, player1, player2)
 }
 
+/// Get a seat of a game by index
+fn seat(game: &Game, index: u32) -> Seat {
+    game.seats.get(index).unwrap()
+}
+
+/// Total of a seat's dice in the last round rolled
+fn seat_total(game: &Game, index: u32) -> u32 {
+    let round = game.rounds.last().unwrap();
+    round.totals.get(index).unwrap()
+}
+
+fn three_players(env: &Env) -> Vec<Address> {
+    vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ]
+}
+
+fn dice_config(dice_count: u32, dice_sides: u32, rounds: u32, tie_policy: TiePolicy) -> DiceConfig {
+    DiceConfig {
+        dice_count,
+        dice_sides,
+        rounds,
+        tie_policy,
+    }
+}
+
+/// Start a table with the given config, have everyone roll and reveal
+fn play_table(
+    env: &Env,
+    client: &DiceDuelContractClient,
+    session_id: u32,
+    players: &Vec<Address>,
+    config: &DiceConfig,
+) -> Game {
+    let mut points = Vec::new(env);
+    for _ in players.iter() {
+        points.push_back(100i128);
+    }
+    client.start_table(&session_id, players, &points, &Some(config.clone()));
+    for player in players.iter() {
+        client.roll(&session_id, &player);
+    }
+    client.reveal_winner(&session_id);
+    client.get_game(&session_id)
+}
+
 /// Assert that a Result contains a specific dice_duel error
 fn assert_dice_duel_error<T, E>(
     result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
@@ -123,55 +289,61 @@ fn assert_dice_duel_error<T, E>(
 
 #[test]
 fn test_complete_game() {
-    let (_env, client, _hub, player1, player2) = setup_test();
+    let (_env, client, hub, player1, player2) = setup_test();
 
     let session_id = 1u32;
     let points = 100_0000000;
 
     // Start game
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None);
 
     // Verify initial state
     let game = client.get_game(&session_id);
-    assert!(game.winner.is_none());
-    assert_eq!(game.player1, player1);
-    assert_eq!(game.player2, player2);
-    assert_eq!(game.player1_points, points);
-    assert_eq!(game.player2_points, points);
-    assert_eq!(game.player1_rolled, false);
-    assert_eq!(game.player2_rolled, false);
-    assert!(game.player1_die1.is_none());
-    assert!(game.player1_die2.is_none());
-    assert!(game.player2_die1.is_none());
-    assert!(game.player2_die2.is_none());
+    assert!(game.winners.is_empty());
+    assert_eq!(game.seats.len(), 2);
+    assert_eq!(seat(&game, 0).player, player1);
+    assert_eq!(seat(&game, 1).player, player2);
+    assert_eq!(seat(&game, 0).points, points);
+    assert_eq!(seat(&game, 1).points, points);
+    assert!(!seat(&game, 0).rolled);
+    assert!(!seat(&game, 1).rolled);
+    assert!(game.rounds.is_empty());
+    assert_eq!(game.config.dice_count, 2);
+    assert_eq!(game.config.dice_sides, 6);
+    assert_eq!(game.config.rounds, 1);
+    assert_eq!(game.config.tie_policy, TiePolicy::Player1Wins);
 
     // Players roll
     client.roll(&session_id, &player1);
     client.roll(&session_id, &player2);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    let winners = client.reveal_winner(&session_id);
+    assert_eq!(winners.len(), 1);
+    let winner = winners.get(0).unwrap();
     assert!(winner == player1 || winner == player2);
 
     // Verify dice values and winner stored
     let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some());
-    assert_eq!(final_game.winner.unwrap(), winner);
+    assert_eq!(final_game.winners, winners);
 
-    let p1d1 = final_game.player1_die1.unwrap();
-    let p1d2 = final_game.player1_die2.unwrap();
-    let p2d1 = final_game.player2_die1.unwrap();
-    let p2d2 = final_game.player2_die2.unwrap();
-
-    assert!((1..=6).contains(&p1d1));
-    assert!((1..=6).contains(&p1d2));
-    assert!((1..=6).contains(&p2d1));
-    assert!((1..=6).contains(&p2d2));
+    assert_eq!(final_game.rounds.len(), 1);
+    for i in 0..2 {
+        let dice = final_game.rounds.get(0).unwrap().dice.get(i).unwrap();
+        assert_eq!(dice.len(), 2);
+        for die in dice.iter() {
+            assert!((1..=6).contains(&die));
+        }
+    }
 
-    let total1 = p1d1 + p1d2;
-    let total2 = p2d1 + p2d2;
+    // Two-player tables settle through the two-player hub call; ties go to player 1
+    let total1 = seat_total(&final_game, 0);
+    let total2 = seat_total(&final_game, 1);
     assert!((2..=12).contains(&total1));
     assert!((2..=12).contains(&total2));
+    assert_eq!(winner == player1, total1 >= total2);
+    assert_eq!(hub.get_player1_won(&session_id), Some(winner == player1));
+    assert_eq!(hub.get_winners(&session_id), None);
 }
 
 #[test]
@@ -181,7 +353,7 @@ fn test_cannot_roll_twice() {
     let session_id = 2u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None);
 
     client.roll(&session_id, &player1);
     let result = client.try_roll(&session_id, &player1);
@@ -195,7 +367,7 @@ fn test_cannot_reveal_before_both_roll() {
     let session_id = 3u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None);
     client.roll(&session_id, &player1);
 
     let result = client.try_reveal_winner(&session_id);
@@ -209,7 +381,7 @@ fn test_non_player_cannot_roll() {
     let session_id = 4u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None);
 
     let non_player = Address::generate(&_env);
     let result = client.try_roll(&session_id, &non_player);
@@ -223,7 +395,7 @@ fn test_cannot_roll_after_game_ended() {
     let session_id = 5u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None);
     client.roll(&session_id, &player1);
     client.roll(&session_id, &player2);
     client.reveal_winner(&session_id);
@@ -232,6 +404,848 @@ fn test_cannot_roll_after_game_ended() {
     assert_dice_duel_error(&result, Error::GameAlreadyEnded);
 }
 
+// ============================================================================
+// Multi-Player Table Tests
+// ============================================================================
+
+#[test]
+fn test_start_table_seats_players_in_order() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+    let points = vec![&env, 100_0000000i128, 50_0000000, 25_0000000];
+
+    let session_id = 10u32;
+    client.start_table(&session_id, &players, &points, &None);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.seats.len(), 3);
+    for i in 0..3 {
+        assert_eq!(seat(&game, i).player, players.get(i).unwrap());
+        assert_eq!(seat(&game, i).points, points.get(i).unwrap());
+        assert!(!seat(&game, i).rolled);
+    }
+}
+
+#[test]
+fn test_start_table_rejects_bad_sizes() {
+    let (env, client, _hub, player1, _player2) = setup_test();
+
+    // Too few players
+    let result = client.try_start_table(&11, &vec![&env, player1], &vec![&env, 100i128], &None);
+    assert_dice_duel_error(&result, Error::InvalidTableSize);
+
+    // Too many players
+    let mut players = Vec::new(&env);
+    let mut points = Vec::new(&env);
+    for _ in 0..7 {
+        players.push_back(Address::generate(&env));
+        points.push_back(100i128);
+    }
+    let result = client.try_start_table(&12, &players, &points, &None);
+    assert_dice_duel_error(&result, Error::InvalidTableSize);
+
+    // Points must be given for every seat
+    let players = three_players(&env);
+    let result = client.try_start_table(&13, &players, &vec![&env, 100i128, 100], &None);
+    assert_dice_duel_error(&result, Error::InvalidTableSize);
+}
+
+#[test]
+fn test_cannot_play_against_yourself() {
+    let (_env, client, _hub, player1, _player2) = setup_test();
+
+    let result = client.try_start_game(&14, &player1, &player1, &100, &100, &None);
+    assert_dice_duel_error(&result, Error::SelfPlay);
+}
+
+#[test]
+fn test_seats_roll_in_order() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+
+    let session_id = 15u32;
+    client.start_table(&session_id, &players, &vec![&env, 100i128, 100, 100], &None);
+
+    let result = client.try_roll(&session_id, &players.get(1).unwrap());
+    assert_dice_duel_error(&result, Error::NotYourTurn);
+
+    client.roll(&session_id, &players.get(0).unwrap());
+    let result = client.try_roll(&session_id, &players.get(2).unwrap());
+    assert_dice_duel_error(&result, Error::NotYourTurn);
+
+    client.roll(&session_id, &players.get(1).unwrap());
+    client.roll(&session_id, &players.get(2).unwrap());
+    assert!(client
+        .get_game(&session_id)
+        .seats
+        .iter()
+        .all(|seat| seat.rolled));
+}
+
+#[test]
+fn test_table_winners_share_best_total() {
+    let (env, client, hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+
+    // Find a table where two seats share the best total, and check one without a tie on the way
+    let mut session_id = 100u32;
+    let mut seen_single = false;
+    loop {
+        client.start_table(&session_id, &players, &vec![&env, 100i128, 100, 100], &None);
+        for player in players.iter() {
+            client.roll(&session_id, &player);
+        }
+        let winners = client.reveal_winner(&session_id);
+
+        let game = client.get_game(&session_id);
+        let best = (0..3).map(|i| seat_total(&game, i)).max().unwrap();
+        let mut expected = Vec::new(&env);
+        for i in 0..3 {
+            if seat_total(&game, i) == best {
+                expected.push_back(players.get(i).unwrap());
+            }
+        }
+        assert_eq!(winners, expected);
+        assert_eq!(game.winners, expected);
+        assert_eq!(hub.get_winners(&session_id), Some(expected));
+        assert_eq!(hub.get_player1_won(&session_id), None);
+
+        seen_single |= winners.len() == 1;
+        if winners.len() > 1 && seen_single {
+            break;
+        }
+        session_id += 1;
+    }
+}
+
+// ============================================================================
+// Game Variant Tests
+// ============================================================================
+
+#[test]
+fn test_custom_dice_are_rolled() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let players = vec![&env, player1, player2];
+
+    let game = play_table(
+        &env,
+        &client,
+        20,
+        &players,
+        &dice_config(3, 20, 1, TiePolicy::Draw),
+    );
+
+    assert_eq!(game.rounds.len(), 1);
+    let round = game.rounds.get(0).unwrap();
+    for i in 0..2 {
+        let dice = round.dice.get(i).unwrap();
+        assert_eq!(dice.len(), 3);
+        assert!(dice.iter().all(|die| (1..=20).contains(&die)));
+        assert_eq!(round.totals.get(i).unwrap(), dice.iter().sum::<u32>());
+    }
+}
+
+#[test]
+fn test_invalid_config_rejected() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    for config in [
+        dice_config(0, 6, 1, TiePolicy::Draw),
+        dice_config(11, 6, 1, TiePolicy::Draw),
+        dice_config(2, 1, 1, TiePolicy::Draw),
+        dice_config(2, 101, 1, TiePolicy::Draw),
+        dice_config(2, 6, 0, TiePolicy::Draw),
+        dice_config(2, 6, 10, TiePolicy::Draw),
+    ] {
+        let result = client.try_start_game(&21, &player1, &player2, &100, &100, &Some(config));
+        assert_dice_duel_error(&result, Error::InvalidConfig);
+    }
+}
+
+#[test]
+fn test_best_of_rounds_stops_at_majority() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let players = vec![&env, player1.clone(), player2.clone()];
+
+    for session_id in 30..40u32 {
+        let game = play_table(
+            &env,
+            &client,
+            session_id,
+            &players,
+            &dice_config(2, 6, 5, TiePolicy::Player1Wins),
+        );
+
+        // Every round has a single winner, and play stops once one seat has three
+        assert!((3..=5).contains(&game.rounds.len()));
+        let mut wins = [0u32; 2];
+        for round in game.rounds.iter() {
+            assert_eq!(round.winners.len(), 1);
+            let winner = round.winners.get(0).unwrap();
+            wins[if winner == player1 { 0 } else { 1 }] += 1;
+        }
+        assert_eq!(seat(&game, 0).round_wins, wins[0]);
+        assert_eq!(seat(&game, 1).round_wins, wins[1]);
+        assert_eq!(wins[0].max(wins[1]), 3);
+
+        let player1_won = wins[0] == 3;
+        assert_eq!(
+            game.winners,
+            vec![
+                &env,
+                if player1_won {
+                    player1.clone()
+                } else {
+                    player2.clone()
+                }
+            ]
+        );
+        assert_eq!(hub.get_player1_won(&session_id), Some(player1_won));
+    }
+}
+
+#[test]
+fn test_reroll_policy_rolls_ties_again() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let players = vec![&env, player1, player2];
+
+    // Few sides make ties common
+    let mut session_id = 200u32;
+    loop {
+        let game = play_table(
+            &env,
+            &client,
+            session_id,
+            &players,
+            &dice_config(1, 2, 1, TiePolicy::Reroll),
+        );
+
+        // Rerolled rounds are tied and credit nobody; the last round decides the game
+        for round in game.rounds.iter() {
+            let tied = round.totals.get(0) == round.totals.get(1);
+            assert_eq!(round.winners.is_empty(), tied);
+        }
+        let last = game.rounds.last().unwrap();
+        assert_eq!(game.winners, last.winners);
+        assert_eq!(game.winners.len(), 1);
+
+        if game.rounds.len() > 1 {
+            break;
+        }
+        session_id += 1;
+    }
+}
+
+#[test]
+fn test_draw_policy_shares_tied_game() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let players = vec![&env, player1, player2];
+
+    let mut session_id = 300u32;
+    let game = loop {
+        let game = play_table(
+            &env,
+            &client,
+            session_id,
+            &players,
+            &dice_config(1, 2, 1, TiePolicy::Draw),
+        );
+        if game.winners.len() > 1 {
+            break game;
+        }
+        session_id += 1;
+    };
+
+    // A two-player draw is settled through the N-player hub call
+    assert_eq!(game.winners, players);
+    assert_eq!(game.rounds.get(0).unwrap().winners, players);
+    assert_eq!(hub.get_winners(&session_id), Some(players));
+    assert_eq!(hub.get_player1_won(&session_id), None);
+}
+
+#[test]
+fn test_player1_wins_policy_at_table_favours_earliest_seat() {
+    let (env, client, hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+
+    let mut session_id = 400u32;
+    let game = loop {
+        let game = play_table(
+            &env,
+            &client,
+            session_id,
+            &players,
+            &dice_config(1, 2, 1, TiePolicy::Player1Wins),
+        );
+        let round = game.rounds.get(0).unwrap();
+        let best = round.totals.iter().max().unwrap();
+        if round.totals.iter().filter(|total| *total == best).count() > 1 {
+            break game;
+        }
+        session_id += 1;
+    };
+
+    let round = game.rounds.get(0).unwrap();
+    let best = round.totals.iter().max().unwrap();
+    let earliest = round.totals.iter().position(|total| total == best).unwrap() as u32;
+    assert_eq!(game.winners, vec![&env, players.get(earliest).unwrap()]);
+    assert_eq!(hub.get_winners(&session_id), Some(game.winners));
+}
+
+// ============================================================================
+// Pig Tests
+// ============================================================================
+
+/// Move the ledger forward by `ledgers`
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+/// Seat on turn in a Pig game
+fn pig_player_on_turn(game: &PigGame) -> Address {
+    game.seats.get(game.turn).unwrap().player
+}
+
+/// Roll for `player`, finalize the roll's beacon round and apply it
+fn pig_roll(
+    env: &Env,
+    client: &DiceDuelContractClient,
+    beacon: &MockBeaconClient,
+    session_id: u32,
+    player: &Address,
+) -> u32 {
+    let request_id = client.roll_turn(&session_id, player);
+    let seed = BytesN::from_array(env, &[request_id as u8; 32]);
+    beacon.finalize(&client.address, &request_id, &seed);
+    client.resolve_roll(&session_id)
+}
+
+#[test]
+fn test_start_pig() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+    let players = vec![&env, player1.clone(), player2.clone()];
+
+    let session_id = 500u32;
+    client.start_pig(&session_id, &players, &vec![&env, 100i128, 50], &50);
+
+    let game = client.get_pig_game(&session_id);
+    assert_eq!(game.seats.len(), 2);
+    assert_eq!(game.seats.get(0).unwrap().player, player1);
+    assert_eq!(game.seats.get(1).unwrap().points, 50);
+    assert!(game.seats.iter().all(|seat| seat.score == 0));
+    assert_eq!(game.target, 50);
+    assert_eq!(game.turn, 0);
+    assert_eq!(game.turn_total, 0);
+    assert_eq!(game.last_roll, 0);
+    assert_eq!(game.turn_deadline, 100 + 720);
+    assert!(game.winners.is_empty());
+    assert_eq!(game.beacon, beacon.address);
+    assert_eq!(game.pending_roll, None);
+
+    // Pig games are kept apart from duels in the same contract
+    let result = client.try_get_game(&session_id);
+    assert_dice_duel_error(&result, Error::GameNotFound);
+}
+
+#[test]
+fn test_start_pig_rejects_bad_setup() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let players = vec![&env, player1.clone(), player2];
+    let points = vec![&env, 100i128, 100];
+
+    // Rolls are drawn from the beacon, so there is no Pig without one
+    let result = client.try_start_pig(&500, &players, &points, &50);
+    assert_dice_duel_error(&result, Error::BeaconRequired);
+    use_beacon(&env, &client);
+
+    let result = client.try_start_pig(&501, &players, &points, &0);
+    assert_dice_duel_error(&result, Error::InvalidConfig);
+    let result = client.try_start_pig(&502, &players, &points, &1001);
+    assert_dice_duel_error(&result, Error::InvalidConfig);
+
+    let result = client.try_start_pig(&503, &vec![&env, player1.clone(), player1], &points, &50);
+    assert_dice_duel_error(&result, Error::SelfPlay);
+}
+
+#[test]
+fn test_pig_rolls_build_turn_total() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+    let players = vec![&env, player1.clone(), player2.clone()];
+
+    let session_id = 504u32;
+    client.start_pig(&session_id, &players, &vec![&env, 100i128, 100], &100);
+
+    let result = client.try_roll_turn(&session_id, &player2);
+    assert_dice_duel_error(&result, Error::NotYourTurn);
+    let result = client.try_hold(&session_id, &player1);
+    assert_dice_duel_error(&result, Error::NothingToHold);
+
+    // Player1 keeps rolling until they roll a 1
+    loop {
+        let roll = pig_roll(&env, &client, &beacon, session_id, &player1);
+        assert!((1..=6).contains(&roll));
+        let game = client.get_pig_game(&session_id);
+        assert_eq!(game.last_roll, roll);
+        if roll == 1 {
+            // The turn total is lost and play passes on
+            assert_eq!(game.turn, 1);
+            assert_eq!(game.turn_total, 0);
+            assert!(game.turn_rolls.is_empty());
+            assert_eq!(game.seats.get(0).unwrap().score, 0);
+            break;
+        }
+        assert_eq!(game.turn, 0);
+        assert_eq!(game.turn_total, game.turn_rolls.iter().sum::<u32>());
+    }
+
+    let result = client.try_roll_turn(&session_id, &player1);
+    assert_dice_duel_error(&result, Error::NotYourTurn);
+}
+
+#[test]
+fn test_pig_hold_banks_turn_total() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+    let players = vec![&env, player1.clone(), player2.clone()];
+
+    // Play until someone banks points
+    let session_id = 505u32;
+    client.start_pig(&session_id, &players, &vec![&env, 100i128, 100], &100);
+    loop {
+        let game = client.get_pig_game(&session_id);
+        let player = pig_player_on_turn(&game);
+        if pig_roll(&env, &client, &beacon, session_id, &player) == 1 {
+            continue;
+        }
+
+        let turn_total = client.get_pig_game(&session_id).turn_total;
+        client.hold(&session_id, &player);
+
+        let after = client.get_pig_game(&session_id);
+        assert_eq!(after.seats.get(game.turn).unwrap().score, turn_total);
+        assert_eq!(after.turn, (game.turn + 1) % 2);
+        assert_eq!(after.turn_total, 0);
+        break;
+    }
+}
+
+#[test]
+fn test_pig_game_to_target() {
+    let (env, client, hub, _player1, _player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+    let players = three_players(&env);
+
+    // Every player rolls twice per turn before holding
+    let session_id = 506u32;
+    client.start_pig(&session_id, &players, &vec![&env, 100i128, 100, 100], &20);
+    let mut turns = 0;
+    while client.get_pig_game(&session_id).winners.is_empty() {
+        let game = client.get_pig_game(&session_id);
+        let player = pig_player_on_turn(&game);
+        if pig_roll(&env, &client, &beacon, session_id, &player) != 1
+            && pig_roll(&env, &client, &beacon, session_id, &player) != 1
+        {
+            client.hold(&session_id, &player);
+        }
+        turns += 1;
+        assert!(turns < 200);
+    }
+
+    let game = client.get_pig_game(&session_id);
+    assert_eq!(game.winners.len(), 1);
+    let winner = game.winners.get(0).unwrap();
+    for seat in game.seats.iter() {
+        assert_eq!(seat.score >= 20, seat.player == winner);
+    }
+    assert_eq!(hub.get_winners(&session_id), Some(game.winners));
+
+    let result = client.try_roll_turn(&session_id, &winner);
+    assert_dice_duel_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_pig_claim_timeout() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    use_beacon(&env, &client);
+    let players = three_players(&env);
+    let seat1 = players.get(0).unwrap();
+    let seat3 = players.get(2).unwrap();
+
+    let session_id = 507u32;
+    client.start_pig(&session_id, &players, &vec![&env, 100i128, 100, 100], &50);
+
+    let result = client.try_claim_pig_timeout(&session_id, &seat3);
+    assert_dice_duel_error(&result, Error::TurnNotExpired);
+    let result = client.try_claim_pig_timeout(&session_id, &seat1);
+    assert_dice_duel_error(&result, Error::CannotClaimOwnTimeout);
+    let result = client.try_claim_pig_timeout(&session_id, &Address::generate(&env));
+    assert_dice_duel_error(&result, Error::NotPlayer);
+
+    // The seat on turn stalls; any other seat can pass play on
+    advance_ledgers(&env, 721);
+    client.claim_pig_timeout(&session_id, &seat3);
+    let game = client.get_pig_game(&session_id);
+    assert_eq!(game.turn, 1);
+    assert_eq!(game.turn_total, 0);
+    assert_eq!(game.turn_deadline, 821 + 720);
+
+    // Play wraps around the table
+    advance_ledgers(&env, 721);
+    client.claim_pig_timeout(&session_id, &seat1);
+    advance_ledgers(&env, 721);
+    client.claim_pig_timeout(&session_id, &seat1);
+    assert_eq!(client.get_pig_game(&session_id).turn, 0);
+}
+
+#[test]
+fn test_pig_roll_waits_for_beacon() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+    let players = vec![&env, player1.clone(), player2.clone()];
+
+    let session_id = 508u32;
+    client.start_pig(&session_id, &players, &vec![&env, 100i128, 100], &50);
+
+    // Rolling only requests the seed; nothing about the roll is known yet
+    let request_id = client.roll_turn(&session_id, &player1);
+    assert!(beacon.was_requested(&client.address, &request_id));
+    let game = client.get_pig_game(&session_id);
+    assert_eq!(game.pending_roll, Some(request_id));
+    assert_eq!(game.last_roll, 0);
+    assert_eq!(game.turn_total, 0);
+
+    // The roll can be neither taken back nor rolled over, and the seat is off the clock
+    assert_dice_duel_error(
+        &client.try_roll_turn(&session_id, &player1),
+        Error::RollPending,
+    );
+    assert_dice_duel_error(&client.try_hold(&session_id, &player1), Error::RollPending);
+    advance_ledgers(&env, 721);
+    let result = client.try_claim_pig_timeout(&session_id, &player2);
+    assert_dice_duel_error(&result, Error::RollPending);
+    let result = client.try_resolve_roll(&session_id);
+    assert_dice_duel_error(&result, Error::SeedNotReady);
+
+    // Once the round is finalized anyone applies the roll
+    beacon.finalize(
+        &client.address,
+        &request_id,
+        &BytesN::from_array(&env, &[7; 32]),
+    );
+    let roll = client.resolve_roll(&session_id);
+    let game = client.get_pig_game(&session_id);
+    assert_eq!(game.pending_roll, None);
+    assert_eq!(game.last_roll, roll);
+    let result = client.try_resolve_roll(&session_id);
+    assert_dice_duel_error(&result, Error::NoPendingRoll);
+
+    // The next roll gets a round of its own
+    let player = pig_player_on_turn(&game);
+    assert_ne!(client.roll_turn(&session_id, &player), request_id);
+}
+
+// ============================================================================
+// Game Hub Extension Tests
+// ============================================================================
+
+/// Point the game at a hub with only `start_game`/`end_game` and turn the extensions off
+fn use_standard_hub(env: &Env, client: &DiceDuelContractClient) -> StandardGameHubClient<'static> {
+    let hub_addr = env.register(StandardGameHub, ());
+    client.set_hub(&hub_addr);
+    client.set_hub_extensions(&false);
+    StandardGameHubClient::new(env, &hub_addr)
+}
+
+#[test]
+fn test_hub_extensions_off_by_default() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(MockGameHub, ());
+    let contract_id = env.register(DiceDuelContract, (&admin, &hub_addr));
+    let client = DiceDuelContractClient::new(&env, &contract_id);
+    assert!(!client.get_hub_extensions());
+}
+
+#[test]
+fn test_standard_hub_plays_classic_two_seat_games() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub = use_standard_hub(&env, &client);
+
+    let session_id = 1u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+    let winners = client.reveal_winner(&session_id);
+    assert_eq!(winners.len(), 1);
+    assert_eq!(
+        hub.get_player1_won(&session_id),
+        Some(winners.get(0).unwrap() == player1)
+    );
+
+    // Pig always has a single winner, so it runs on the standard hub too
+    use_beacon(&env, &client);
+    client.start_pig(&2, &vec![&env, player1, player2], &vec![&env, 10, 10], &50);
+}
+
+#[test]
+fn test_standard_hub_refuses_extensions() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    use_standard_hub(&env, &client);
+    use_beacon(&env, &client);
+
+    let result = client.try_start_table(
+        &1,
+        &three_players(&env),
+        &vec![&env, 100_0000000, 100_0000000, 100_0000000],
+        &None,
+    );
+    assert_dice_duel_error(&result, Error::HubExtensionsRequired);
+    let result = client.try_start_pig(&2, &three_players(&env), &vec![&env, 10, 10, 10], &50);
+    assert_dice_duel_error(&result, Error::HubExtensionsRequired);
+
+    // Two-seat games whose ties could be shared need the extensions as well
+    for tie_policy in [TiePolicy::Draw, TiePolicy::Reroll] {
+        let config = DiceConfig {
+            dice_count: 2,
+            dice_sides: 6,
+            rounds: 1,
+            tie_policy,
+        };
+        let result = client.try_start_game(&3, &player1, &player2, &10, &10, &Some(config));
+        assert_dice_duel_error(&result, Error::HubExtensionsRequired);
+    }
+}
+
+// ============================================================================
+// Channel Tests
+// ============================================================================
+
+fn channel_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn channel_public_keys(env: &Env, keys: &[SigningKey]) -> Vec<BytesN<32>> {
+    let mut public_keys = Vec::new(env);
+    for key in keys {
+        public_keys.push_back(BytesN::from_array(env, &key.verifying_key().to_bytes()));
+    }
+    public_keys
+}
+
+fn channel_state(
+    env: &Env,
+    session_id: u32,
+    nonce: u64,
+    winners: Option<Vec<Address>>,
+) -> ChannelState {
+    ChannelState {
+        session_id,
+        nonce,
+        state_hash: BytesN::from_array(env, &[nonce as u8; 32]),
+        finished: winners.is_some(),
+        winners: winners.unwrap_or(Vec::new(env)),
+    }
+}
+
+fn co_sign(
+    env: &Env,
+    client: &DiceDuelContractClient,
+    keys: &[SigningKey],
+    state: &ChannelState,
+) -> Vec<BytesN<64>> {
+    let digest = client.channel_state_digest(state).to_array();
+    let mut signatures = Vec::new(env);
+    for key in keys {
+        signatures.push_back(BytesN::from_array(env, &key.sign(&digest).to_bytes()));
+    }
+    signatures
+}
+
+#[test]
+fn test_channel_settles_final_state() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 40u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+    assert_dice_duel_error(&client.try_roll(&session_id, &player1), Error::ChannelOpen);
+
+    let state = channel_state(&env, session_id, 7, Some(vec![&env, player1.clone()]));
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    assert_eq!(
+        client.settle_channel(&session_id),
+        vec![&env, player1.clone()]
+    );
+    assert_eq!(hub.get_player1_won(&session_id), Some(true));
+    assert_eq!(client.get_game(&session_id).winners, vec![&env, player1]);
+    assert_dice_duel_error(
+        &client.try_submit_channel_state(
+            &session_id,
+            &state,
+            &co_sign(&env, &client, &keys, &state),
+        ),
+        Error::GameAlreadyEnded,
+    );
+}
+
+#[test]
+fn test_channel_loser_stops_signing() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 41u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+
+    // The last co-signed state is unfinished and nothing higher arrives in the window
+    let state = channel_state(&env, session_id, 5, None);
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    assert_dice_duel_error(
+        &client.try_settle_channel(&session_id),
+        Error::ChallengeOpen,
+    );
+    env.ledger()
+        .with_mut(|li| li.sequence_number += state_channel::CHALLENGE_LEDGERS + 1);
+
+    // No forced draw: the dice are rolled on chain again
+    assert_eq!(client.settle_channel(&session_id), Vec::new(&env));
+    assert_eq!(hub.get_winners(&session_id), None);
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+    let winners = client.reveal_winner(&session_id);
+    assert_eq!(
+        hub.get_player1_won(&session_id),
+        Some(winners.get(0).unwrap() == player1)
+    );
+}
+
+#[test]
+fn test_channel_draw_on_standard_hub_follows_tie_policy() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub = use_standard_hub(&env, &client);
+    let session_id = 42u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+
+    // The standard hub has no shared wins, so a co-signed draw goes to player 1 like an on-chain tie
+    let state = channel_state(
+        &env,
+        session_id,
+        2,
+        Some(vec![&env, player2.clone(), player1.clone()]),
+    );
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    assert_eq!(client.settle_channel(&session_id), vec![&env, player1]);
+    assert_eq!(hub.get_player1_won(&session_id), Some(true));
+}
+
+// ============================================================================
+// Randomness Beacon Tests
+// ============================================================================
+
+/// Point the game at a fresh mock beacon
+fn use_beacon(env: &Env, client: &DiceDuelContractClient) -> MockBeaconClient<'static> {
+    let beacon_addr = env.register(MockBeacon, ());
+    client.set_beacon(&Some(beacon_addr.clone()));
+    MockBeaconClient::new(env, &beacon_addr)
+}
+
+#[test]
+fn test_beacon_seed_requested_after_last_roll() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+
+    client.start_game(&60, &player1, &player2, &100, &100, &None);
+    assert_eq!(client.get_game(&60).beacon, Some(beacon.address.clone()));
+
+    client.roll(&60, &player1);
+    assert!(!beacon.was_requested(&client.address, &60));
+    client.roll(&60, &player2);
+    assert!(beacon.was_requested(&client.address, &60));
+
+    let result = client.try_reveal_winner(&60);
+    assert_dice_duel_error(&result, Error::SeedNotReady);
+}
+
+#[test]
+fn test_dice_rolled_from_beacon_seed() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+    let seed = BytesN::from_array(&env, &[7u8; 32]);
+
+    // Two sessions finalized with the same beacon seed roll the same dice
+    for session_id in [61u32, 62u32] {
+        client.start_game(&session_id, &player1, &player2, &100, &100, &None);
+        client.roll(&session_id, &player1);
+        client.roll(&session_id, &player2);
+        beacon.finalize(&client.address, &session_id, &seed);
+        let winners = client.reveal_winner(&session_id);
+        assert_eq!(
+            hub.get_player1_won(&session_id),
+            Some(winners.get(0).unwrap() == player1)
+        );
+    }
+    assert_eq!(client.get_game(&61).rounds, client.get_game(&62).rounds);
+
+    // A different seed gives different dice
+    client.start_game(&63, &player1, &player2, &100, &100, &None);
+    client.roll(&63, &player1);
+    client.roll(&63, &player2);
+    beacon.finalize(&client.address, &63, &BytesN::from_array(&env, &[8u8; 32]));
+    client.reveal_winner(&63);
+    assert_ne!(client.get_game(&61).rounds, client.get_game(&63).rounds);
+}
+
+#[test]
+fn test_games_keep_beacon_they_started_with() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // Started without a beacon, so it seeds itself even after one is configured
+    client.start_game(&64, &player1, &player2, &100, &100, &None);
+    let beacon = use_beacon(&env, &client);
+    assert_eq!(client.get_beacon(), Some(beacon.address.clone()));
+
+    client.roll(&64, &player1);
+    client.roll(&64, &player2);
+    assert!(!beacon.was_requested(&client.address, &64));
+    client.reveal_winner(&64);
+
+    // Clearing the beacon goes back to self-seeded games
+    client.set_beacon(&None);
+    assert_eq!(client.get_beacon(), None);
+}
+
 #[test]
 fn test_upgrade_function_exists() {
     let (_env, client, _hub, _player1, _player2) = setup_test();
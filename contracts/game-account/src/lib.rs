@@ -0,0 +1,405 @@
+#![no_std]
+
+//! # Game Account
+//!
+//! A smart account for playing the workspace's games. The player's main key signs for the
+//! account, and `__check_auth` only lets that signature through for calls the account's
+//! policy allows, so a leaked approval cannot be replayed against anything else.
+//!
+//! **Signers:**
+//! - `Ed25519` - a plain ed25519 key signing the authorization payload
+//! - `Secp256r1` - a passkey; the signature is a WebAuthn assertion whose challenge is the
+//!   base64url-encoded authorization payload
+//!
+//! **Policy:**
+//! - Only the listed game contracts (and the account itself) can be authorized
+//! - Every game entry point that locks points authorizes `(session_id, points, ..)`; the
+//!   points are capped per call and per UTC day
+//! - A bond token can be listed too; its `transfer`s count against the same caps, and
+//!   allowances on it are never authorized
+//!
+//! The account can also register a session key in `my-game`, so in-game moves need no
+//! signature from the main key at all.
+
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contract, contracterror, contractimpl, contracttype,
+    crypto::Hash,
+    panic_with_error, Address, Bytes, BytesN, Env, Symbol, TryFromVal, Val, Vec,
+};
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    SignatureMismatch = 1,
+    InvalidAuthenticatorData = 2,
+    InvalidClientData = 3,
+    ContextNotAllowed = 4,
+    GameNotAllowed = 5,
+    InvalidStakeArgs = 6,
+    StakeAboveLimit = 7,
+    DailyLimitExceeded = 8,
+    InvalidPolicy = 9,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Signer {
+    Ed25519(BytesN<32>),
+    Secp256r1(BytesN<65>), // SEC1 uncompressed public key
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WebAuthnSignature {
+    pub authenticator_data: Bytes,
+    pub client_data_json: Bytes,
+    pub signature: BytesN<64>, // r || s, low-S
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccountSignature {
+    Ed25519(BytesN<64>),
+    Secp256r1(WebAuthnSignature),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Policy {
+    pub games: Vec<Address>, // Game contracts the main key may authorize calls to
+    pub max_stake: i128,     // Most points per staking call or token transfer
+    pub daily_limit: i128,   // Most points and tokens across all of them in a UTC day
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailySpend {
+    pub day: u64, // Ledger timestamp / 86400
+    pub spent: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Signer,
+    Policy,
+    Spend,
+}
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// Everything lives in instance storage, bumped on every authorization, so an account
+// in use never expires.
+
+/// TTL for instance storage (30 days in ledgers, ~5 seconds per ledger)
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+const ACCOUNT_TTL_LEDGERS: u32 = 518_400;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Game entry points that lock points with the Game Hub
+const STAKING_FUNCTIONS: [&str; 8] = [
+    "start_game",
+    "start_table",
+    "start_pig",
+    "start_duel",
+    "start_game_with_session_keys",
+    "double_down",
+    "split",
+    "request_hint",
+];
+
+/// Token calls that hand the account's funds to someone else without a `transfer`
+const TOKEN_ALLOWANCE_FUNCTIONS: [&str; 4] = ["approve", "transfer_from", "burn", "burn_from"];
+
+/// Longest WebAuthn client data JSON accepted
+const MAX_CLIENT_DATA_LEN: usize = 1024;
+
+/// WebAuthn authenticator data: rpIdHash (32) || flags (1) || signCount (4)
+const AUTHENTICATOR_DATA_MIN_LEN: u32 = 37;
+const FLAG_USER_PRESENT: u8 = 0x01;
+
+const CHALLENGE_PREFIX: &[u8] = b"\"challenge\":\"";
+const CHALLENGE_LEN: usize = 43; // base64url of 32 bytes, unpadded
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct GameAccountContract;
+
+#[contractimpl]
+impl GameAccountContract {
+    /// Initialize the account with its main key and policy.
+    ///
+    /// # Arguments
+    /// * `signer` - The player's main key
+    /// * `policy` - Games, per-session stake cap and daily limit
+    pub fn __constructor(env: Env, signer: Signer, policy: Policy) {
+        if !Self::is_valid_policy(&policy) {
+            panic_with_error!(&env, Error::InvalidPolicy);
+        }
+        env.storage().instance().set(&DataKey::Signer, &signer);
+        env.storage().instance().set(&DataKey::Policy, &policy);
+    }
+
+    /// Replace the policy. Signed by the main key through `__check_auth`.
+    pub fn set_policy(env: Env, policy: Policy) -> Result<(), Error> {
+        env.current_contract_address().require_auth();
+        if !Self::is_valid_policy(&policy) {
+            return Err(Error::InvalidPolicy);
+        }
+        env.storage().instance().set(&DataKey::Policy, &policy);
+        Ok(())
+    }
+
+    /// Rotate the main key. Signed by the current key through `__check_auth`.
+    pub fn set_signer(env: Env, signer: Signer) {
+        env.current_contract_address().require_auth();
+        env.storage().instance().set(&DataKey::Signer, &signer);
+    }
+
+    pub fn get_signer(env: Env) -> Signer {
+        env.storage().instance().get(&DataKey::Signer).unwrap()
+    }
+
+    pub fn get_policy(env: Env) -> Policy {
+        env.storage().instance().get(&DataKey::Policy).unwrap()
+    }
+
+    /// Points staked so far in the current UTC day.
+    pub fn get_daily_spend(env: Env) -> i128 {
+        Self::load_spend(&env).spent
+    }
+
+    // ========================================================================
+    // Internal Helpers
+    // ========================================================================
+
+    fn is_valid_policy(policy: &Policy) -> bool {
+        policy.max_stake >= 0 && policy.daily_limit >= 0
+    }
+
+    fn load_spend(env: &Env) -> DailySpend {
+        let day = env.ledger().timestamp() / SECONDS_PER_DAY;
+        env.storage()
+            .instance()
+            .get(&DataKey::Spend)
+            .filter(|spend: &DailySpend| spend.day == day)
+            .unwrap_or(DailySpend { day, spent: 0 })
+    }
+
+    fn verify_signature(
+        env: &Env,
+        payload: &Hash<32>,
+        signature: AccountSignature,
+    ) -> Result<(), Error> {
+        match (Self::get_signer(env.clone()), signature) {
+            (Signer::Ed25519(public_key), AccountSignature::Ed25519(signature)) => {
+                let message = Bytes::from_array(env, &payload.to_array());
+                env.crypto()
+                    .ed25519_verify(&public_key, &message, &signature);
+                Ok(())
+            }
+            (Signer::Secp256r1(public_key), AccountSignature::Secp256r1(assertion)) => {
+                Self::verify_webauthn(env, payload, &public_key, assertion)
+            }
+            _ => Err(Error::SignatureMismatch),
+        }
+    }
+
+    // The authenticator signs sha256(authenticator_data || sha256(client_data_json))
+    fn verify_webauthn(
+        env: &Env,
+        payload: &Hash<32>,
+        public_key: &BytesN<65>,
+        assertion: WebAuthnSignature,
+    ) -> Result<(), Error> {
+        let auth_data = &assertion.authenticator_data;
+        if auth_data.len() < AUTHENTICATOR_DATA_MIN_LEN
+            || auth_data.get_unchecked(32) & FLAG_USER_PRESENT == 0
+        {
+            return Err(Error::InvalidAuthenticatorData);
+        }
+        Self::check_challenge(&assertion.client_data_json, &payload.to_array())?;
+
+        let mut message = auth_data.clone();
+        message.append(&Bytes::from(
+            env.crypto().sha256(&assertion.client_data_json).to_bytes(),
+        ));
+        let digest = env.crypto().sha256(&message);
+        env.crypto()
+            .secp256r1_verify(public_key, &digest, &assertion.signature);
+        Ok(())
+    }
+
+    fn check_challenge(client_data_json: &Bytes, payload: &[u8; 32]) -> Result<(), Error> {
+        let len = client_data_json.len() as usize;
+        if len > MAX_CLIENT_DATA_LEN {
+            return Err(Error::InvalidClientData);
+        }
+        let mut json = [0u8; MAX_CLIENT_DATA_LEN];
+        client_data_json.copy_into_slice(&mut json[..len]);
+
+        let mut needle = [0u8; CHALLENGE_PREFIX.len() + CHALLENGE_LEN + 1];
+        needle[..CHALLENGE_PREFIX.len()].copy_from_slice(CHALLENGE_PREFIX);
+        needle[CHALLENGE_PREFIX.len()..CHALLENGE_PREFIX.len() + CHALLENGE_LEN]
+            .copy_from_slice(&base64url_32(payload));
+        needle[CHALLENGE_PREFIX.len() + CHALLENGE_LEN] = b'"';
+
+        if json[..len]
+            .windows(needle.len())
+            .any(|window| window == needle)
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidClientData)
+        }
+    }
+
+    // Calls on the account itself are always allowed; anything else must be a listed game
+    fn check_policy(env: &Env, auth_contexts: &Vec<Context>) -> Result<(), Error> {
+        let policy = Self::get_policy(env.clone());
+        let account = env.current_contract_address();
+
+        let mut staked: i128 = 0;
+        for context in auth_contexts.iter() {
+            let call = match context {
+                Context::Contract(call) => call,
+                _ => return Err(Error::ContextNotAllowed),
+            };
+            if call.contract == account {
+                continue;
+            }
+            if !policy.games.contains(&call.contract) {
+                return Err(Error::GameNotAllowed);
+            }
+            let stake = if Self::is_staking(env, &call.fn_name) {
+                Self::stake_of(env, &call.args)?
+            } else if call.fn_name == Symbol::new(env, "transfer") {
+                // A bond posted to a game from a listed token
+                Self::transfer_amount(env, &call.args)?
+            } else if Self::is_token_allowance(env, &call.fn_name) {
+                return Err(Error::ContextNotAllowed);
+            } else {
+                continue;
+            };
+            if stake > policy.max_stake {
+                return Err(Error::StakeAboveLimit);
+            }
+            staked = staked.checked_add(stake).ok_or(Error::DailyLimitExceeded)?;
+        }
+
+        if staked > 0 {
+            let mut spend = Self::load_spend(env);
+            spend.spent = spend
+                .spent
+                .checked_add(staked)
+                .filter(|spent| *spent <= policy.daily_limit)
+                .ok_or(Error::DailyLimitExceeded)?;
+            env.storage().instance().set(&DataKey::Spend, &spend);
+        }
+        Ok(())
+    }
+
+    fn is_staking(env: &Env, fn_name: &Symbol) -> bool {
+        STAKING_FUNCTIONS
+            .iter()
+            .any(|name| *fn_name == Symbol::new(env, name))
+    }
+
+    fn is_token_allowance(env: &Env, fn_name: &Symbol) -> bool {
+        TOKEN_ALLOWANCE_FUNCTIONS
+            .iter()
+            .any(|name| *fn_name == Symbol::new(env, name))
+    }
+
+    // Staking authorizations are `(session_id, points)`, optionally followed by the rest
+    // of what the player agrees to
+    fn stake_of(env: &Env, args: &Vec<Val>) -> Result<i128, Error> {
+        if args.len() < 2 {
+            return Err(Error::InvalidStakeArgs);
+        }
+        u32::try_from_val(env, &args.get_unchecked(0)).map_err(|_| Error::InvalidStakeArgs)?;
+        let points =
+            i128::try_from_val(env, &args.get_unchecked(1)).map_err(|_| Error::InvalidStakeArgs)?;
+        if points < 0 {
+            return Err(Error::InvalidStakeArgs);
+        }
+        Ok(points)
+    }
+
+    // Token transfers are `(from, to, amount)`
+    fn transfer_amount(env: &Env, args: &Vec<Val>) -> Result<i128, Error> {
+        if args.len() != 3 {
+            return Err(Error::InvalidStakeArgs);
+        }
+        let amount =
+            i128::try_from_val(env, &args.get_unchecked(2)).map_err(|_| Error::InvalidStakeArgs)?;
+        if amount < 0 {
+            return Err(Error::InvalidStakeArgs);
+        }
+        Ok(amount)
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for GameAccountContract {
+    type Signature = AccountSignature;
+    type Error = Error;
+
+    /// Verify the main key's signature over the payload, then the policy for every
+    /// context it covers. Stakes count against today's limit once authorized.
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signature: AccountSignature,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), Error> {
+        Self::verify_signature(&env, &signature_payload, signature)?;
+        Self::check_policy(&env, &auth_contexts)?;
+        env.storage()
+            .instance()
+            .extend_ttl(ACCOUNT_TTL_LEDGERS, ACCOUNT_TTL_LEDGERS);
+        Ok(())
+    }
+}
+
+fn base64url_32(input: &[u8; 32]) -> [u8; CHALLENGE_LEN] {
+    let mut out = [0u8; CHALLENGE_LEN];
+    let mut o = 0;
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..=chunk.len() {
+            out[o] = BASE64URL[((n >> (18 - 6 * i)) & 63) as usize];
+            o += 1;
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
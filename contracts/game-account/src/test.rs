@@ -0,0 +1,784 @@
+#![cfg(test)]
+
+// Unit tests for the game account, plus end-to-end auth against my-game's `start_game`
+// and bonded `commit_code`.
+
+extern crate std;
+
+use crate::{
+    base64url_32, AccountSignature, Error, GameAccountContract, GameAccountContractClient, Policy,
+    Signer, WebAuthnSignature,
+};
+use ed25519_dalek::Signer as _;
+use my_game::{BondConfig, MyGameContract, MyGameContractClient};
+use soroban_sdk::auth::{Context, ContractContext, CreateContractHostFnContext};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::xdr::{
+    HashIdPreimage, HashIdPreimageSorobanAuthorization, InvokeContractArgs, Limits, ScAddress,
+    ScSymbol, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+    SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, WriteXdr,
+};
+use soroban_sdk::{
+    contract, contractimpl, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+#[contract]
+pub struct HubStub;
+
+#[contractimpl]
+impl HubStub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+}
+
+const MAX_STAKE: i128 = 100;
+const DAILY_LIMIT: i128 = 250;
+
+enum Key {
+    Ed25519(ed25519_dalek::SigningKey),
+    Secp256r1(p256::ecdsa::SigningKey),
+}
+
+impl Key {
+    fn ed25519(seed: u8) -> Key {
+        Key::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[seed; 32]))
+    }
+
+    fn secp256r1(seed: u8) -> Key {
+        Key::Secp256r1(p256::ecdsa::SigningKey::from_slice(&[seed; 32]).unwrap())
+    }
+
+    fn signer(&self, env: &Env) -> Signer {
+        match self {
+            Key::Ed25519(key) => {
+                Signer::Ed25519(BytesN::from_array(env, &key.verifying_key().to_bytes()))
+            }
+            Key::Secp256r1(key) => {
+                let point = key.verifying_key().to_encoded_point(false);
+                Signer::Secp256r1(BytesN::from_array(
+                    env,
+                    point.as_bytes().try_into().unwrap(),
+                ))
+            }
+        }
+    }
+
+    fn sign(&self, env: &Env, payload: &[u8; 32]) -> AccountSignature {
+        match self {
+            Key::Ed25519(key) => {
+                AccountSignature::Ed25519(BytesN::from_array(env, &key.sign(payload).to_bytes()))
+            }
+            Key::Secp256r1(key) => {
+                webauthn_assertion(env, key, client_data_for(payload).as_bytes(), 0x05)
+            }
+        }
+    }
+}
+
+fn client_data_for(payload: &[u8; 32]) -> std::string::String {
+    let challenge = base64url_32(payload);
+    std::format!(
+        "{{\"type\":\"webauthn.get\",\"challenge\":\"{}\",\"origin\":\"https://play.example\"}}",
+        std::str::from_utf8(&challenge).unwrap()
+    )
+}
+
+fn webauthn_assertion(
+    env: &Env,
+    key: &p256::ecdsa::SigningKey,
+    client_data: &[u8],
+    flags: u8,
+) -> AccountSignature {
+    let mut authenticator_data = std::vec![0x11u8; 32];
+    authenticator_data.extend_from_slice(&[flags, 0, 0, 0, 1]);
+
+    let client_data_hash = env
+        .crypto()
+        .sha256(&Bytes::from_slice(env, client_data))
+        .to_array();
+    let mut message = authenticator_data.clone();
+    message.extend_from_slice(&client_data_hash);
+    let signature: p256::ecdsa::Signature = key.sign(&message);
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    AccountSignature::Secp256r1(WebAuthnSignature {
+        authenticator_data: Bytes::from_slice(env, &authenticator_data),
+        client_data_json: Bytes::from_slice(env, client_data),
+        signature: BytesN::from_array(env, &signature.to_bytes().into()),
+    })
+}
+
+fn setup_env() -> Env {
+    let env = Env::default();
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+    env
+}
+
+fn register_account(env: &Env, key: &Key, games: Vec<Address>) -> Address {
+    let policy = Policy {
+        games,
+        max_stake: MAX_STAKE,
+        daily_limit: DAILY_LIMIT,
+    };
+    env.register(GameAccountContract, (key.signer(env), policy))
+}
+
+fn start_game_context(env: &Env, game: &Address, session_id: u32, points: i128) -> Context {
+    Context::Contract(ContractContext {
+        contract: game.clone(),
+        fn_name: Symbol::new(env, "start_game"),
+        args: vec![env, session_id.into_val(env), points.into_val(env)],
+    })
+}
+
+fn check_auth(
+    env: &Env,
+    account: &Address,
+    key: &Key,
+    contexts: Vec<Context>,
+) -> Result<(), Error> {
+    let payload = [42u8; 32];
+    let signature = key.sign(env, &payload);
+    invoke_check_auth(env, account, &payload, signature, contexts)
+}
+
+fn invoke_check_auth(
+    env: &Env,
+    account: &Address,
+    payload: &[u8; 32],
+    signature: AccountSignature,
+    contexts: Vec<Context>,
+) -> Result<(), Error> {
+    match env.try_invoke_contract_check_auth::<Error>(
+        account,
+        &BytesN::from_array(env, payload),
+        signature.into_val(env),
+        &contexts,
+    ) {
+        Ok(()) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        Err(Err(error)) => panic!("check_auth failed outside the contract: {error:?}"),
+    }
+}
+
+fn invocation(
+    env: &Env,
+    contract: &Address,
+    function: &str,
+    args: Vec<Val>,
+) -> SorobanAuthorizedInvocation {
+    SorobanAuthorizedInvocation {
+        function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+            contract_address: ScAddress::from(contract),
+            function_name: ScSymbol(function.try_into().unwrap()),
+            args: args
+                .iter()
+                .map(|arg| ScVal::try_from_val(env, &arg).unwrap())
+                .collect::<std::vec::Vec<_>>()
+                .try_into()
+                .unwrap(),
+        }),
+        sub_invocations: Default::default(),
+    }
+}
+
+// A real authorization entry for `account`, signed over the host's payload
+fn signed_entry(
+    env: &Env,
+    account: &Address,
+    key: &Key,
+    nonce: i64,
+    contract: &Address,
+    function: &str,
+    args: Vec<Val>,
+) -> SorobanAuthorizationEntry {
+    let invocation = invocation(env, contract, function, args);
+    sign_invocation(env, account, key, nonce, invocation)
+}
+
+fn sign_invocation(
+    env: &Env,
+    account: &Address,
+    key: &Key,
+    nonce: i64,
+    invocation: SorobanAuthorizedInvocation,
+) -> SorobanAuthorizationEntry {
+    let expiration = env.ledger().sequence() + 100;
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: env.ledger().network_id().to_array().into(),
+        nonce,
+        signature_expiration_ledger: expiration,
+        invocation: invocation.clone(),
+    });
+    let payload = env
+        .crypto()
+        .sha256(&Bytes::from_slice(
+            env,
+            &preimage.to_xdr(Limits::none()).unwrap(),
+        ))
+        .to_array();
+    let signature: Val = key.sign(env, &payload).into_val(env);
+
+    SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: ScAddress::from(account),
+            nonce,
+            signature_expiration_ledger: expiration,
+            signature: ScVal::try_from_val(env, &signature).unwrap(),
+        }),
+        root_invocation: invocation,
+    }
+}
+
+fn start_game_entry(
+    env: &Env,
+    account: &Address,
+    key: &Key,
+    nonce: i64,
+    game: &Address,
+    session_id: u32,
+    points: i128,
+) -> SorobanAuthorizationEntry {
+    let args = vec![env, session_id.into_val(env), points.into_val(env)];
+    signed_entry(env, account, key, nonce, game, "start_game", args)
+}
+
+// ============================================================================
+// Signature Tests
+// ============================================================================
+
+#[test]
+fn test_base64url_challenge_encoding() {
+    let payload: [u8; 32] = core::array::from_fn(|i| i as u8);
+    assert_eq!(
+        &base64url_32(&payload),
+        b"AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8"
+    );
+}
+
+#[test]
+fn test_ed25519_and_passkey_signers_authorize() {
+    let env = setup_env();
+    let game = Address::generate(&env);
+    for key in [Key::ed25519(1), Key::secp256r1(2)] {
+        let account = register_account(&env, &key, vec![&env, game.clone()]);
+        let contexts = vec![&env, start_game_context(&env, &game, 1, MAX_STAKE)];
+        assert_eq!(check_auth(&env, &account, &key, contexts), Ok(()));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_ed25519_signature_from_another_key_is_rejected() {
+    let env = setup_env();
+    let game = Address::generate(&env);
+    let account = register_account(&env, &Key::ed25519(1), vec![&env, game.clone()]);
+    let contexts = vec![&env, start_game_context(&env, &game, 1, 10)];
+    let _ = check_auth(&env, &account, &Key::ed25519(3), contexts);
+}
+
+#[test]
+fn test_signature_kind_must_match_signer() {
+    let env = setup_env();
+    let game = Address::generate(&env);
+    let account = register_account(&env, &Key::ed25519(1), vec![&env, game.clone()]);
+    let contexts = vec![&env, start_game_context(&env, &game, 1, 10)];
+    assert_eq!(
+        check_auth(&env, &account, &Key::secp256r1(1), contexts),
+        Err(Error::SignatureMismatch)
+    );
+}
+
+#[test]
+fn test_passkey_assertion_must_carry_the_payload() {
+    let env = setup_env();
+    let game = Address::generate(&env);
+    let key = p256::ecdsa::SigningKey::from_slice(&[2; 32]).unwrap();
+    let account = register_account(&env, &Key::Secp256r1(key.clone()), vec![&env, game.clone()]);
+    let contexts = vec![&env, start_game_context(&env, &game, 1, 10)];
+
+    // Signed correctly, but over a challenge for another payload
+    let client_data = client_data_for(&[7; 32]);
+    let signature = webauthn_assertion(&env, &key, client_data.as_bytes(), 0x05);
+    assert_eq!(
+        invoke_check_auth(&env, &account, &[42; 32], signature, contexts.clone()),
+        Err(Error::InvalidClientData)
+    );
+
+    // User presence flag cleared
+    let client_data = client_data_for(&[42; 32]);
+    let signature = webauthn_assertion(&env, &key, client_data.as_bytes(), 0x04);
+    assert_eq!(
+        invoke_check_auth(&env, &account, &[42; 32], signature, contexts),
+        Err(Error::InvalidAuthenticatorData)
+    );
+}
+
+// ============================================================================
+// Policy Tests
+// ============================================================================
+
+#[test]
+fn test_only_listed_games_can_be_authorized() {
+    let env = setup_env();
+    let key = Key::ed25519(1);
+    let game = Address::generate(&env);
+    let account = register_account(&env, &key, vec![&env, game.clone()]);
+
+    let other_game = Address::generate(&env);
+    let contexts = vec![&env, start_game_context(&env, &other_game, 1, 10)];
+    assert_eq!(
+        check_auth(&env, &account, &key, contexts),
+        Err(Error::GameNotAllowed)
+    );
+
+    let transfer = Context::Contract(ContractContext {
+        contract: Address::generate(&env),
+        fn_name: Symbol::new(&env, "transfer"),
+        args: Vec::new(&env),
+    });
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, transfer]),
+        Err(Error::GameNotAllowed)
+    );
+
+    let deploy = Context::CreateContractHostFn(CreateContractHostFnContext {
+        executable: soroban_sdk::auth::ContractExecutable::Wasm(BytesN::from_array(&env, &[0; 32])),
+        salt: BytesN::from_array(&env, &[0; 32]),
+    });
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, deploy]),
+        Err(Error::ContextNotAllowed)
+    );
+
+    // Moves and the account's own admin calls need no stake
+    let guess = Context::Contract(ContractContext {
+        contract: game.clone(),
+        fn_name: Symbol::new(&env, "submit_guess"),
+        args: Vec::new(&env),
+    });
+    let set_policy = Context::Contract(ContractContext {
+        contract: account.clone(),
+        fn_name: Symbol::new(&env, "set_policy"),
+        args: Vec::new(&env),
+    });
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, guess, set_policy]),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_stake_is_capped_per_session() {
+    let env = setup_env();
+    let key = Key::ed25519(1);
+    let game = Address::generate(&env);
+    let account = register_account(&env, &key, vec![&env, game.clone()]);
+
+    let contexts = vec![&env, start_game_context(&env, &game, 1, MAX_STAKE + 1)];
+    assert_eq!(
+        check_auth(&env, &account, &key, contexts),
+        Err(Error::StakeAboveLimit)
+    );
+
+    let unexpected = Context::Contract(ContractContext {
+        contract: game.clone(),
+        fn_name: Symbol::new(&env, "start_game"),
+        args: vec![&env, 1u32.into_val(&env)],
+    });
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, unexpected]),
+        Err(Error::InvalidStakeArgs)
+    );
+    assert_eq!(
+        check_auth(
+            &env,
+            &account,
+            &key,
+            vec![&env, start_game_context(&env, &game, 1, -5)]
+        ),
+        Err(Error::InvalidStakeArgs)
+    );
+}
+
+#[test]
+fn test_daily_limit_accumulates_and_resets() {
+    let env = setup_env();
+    let key = Key::ed25519(1);
+    let game = Address::generate(&env);
+    let account = register_account(&env, &key, vec![&env, game.clone()]);
+    let client = GameAccountContractClient::new(&env, &account);
+
+    assert_eq!(
+        check_auth(
+            &env,
+            &account,
+            &key,
+            vec![&env, start_game_context(&env, &game, 1, 100)]
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        check_auth(
+            &env,
+            &account,
+            &key,
+            vec![&env, start_game_context(&env, &game, 2, 100)]
+        ),
+        Ok(())
+    );
+    assert_eq!(client.get_daily_spend(), 200);
+
+    // A rejected stake does not count
+    assert_eq!(
+        check_auth(
+            &env,
+            &account,
+            &key,
+            vec![&env, start_game_context(&env, &game, 3, 100)]
+        ),
+        Err(Error::DailyLimitExceeded)
+    );
+    assert_eq!(client.get_daily_spend(), 200);
+    assert_eq!(
+        check_auth(
+            &env,
+            &account,
+            &key,
+            vec![&env, start_game_context(&env, &game, 3, 50)]
+        ),
+        Ok(())
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(client.get_daily_spend(), 0);
+    assert_eq!(
+        check_auth(
+            &env,
+            &account,
+            &key,
+            vec![&env, start_game_context(&env, &game, 4, 100)]
+        ),
+        Ok(())
+    );
+    assert_eq!(client.get_daily_spend(), 100);
+}
+
+// Every staking entry point, with the authorization args its game asks for
+fn staking_contexts(env: &Env, game: &Address, points: i128) -> std::vec::Vec<Context> {
+    let session_points = || vec![env, 1u32.into_val(env), points.into_val(env)];
+    let mut hint_args = session_points();
+    for field in [0u32, 2, 3] {
+        hint_args.push_back(field.into_val(env));
+    }
+    [
+        ("start_table", session_points()),
+        ("double_down", session_points()),
+        ("split", session_points()),
+        ("start_pig", session_points()),
+        ("start_duel", session_points()),
+        ("request_hint", hint_args),
+    ]
+    .into_iter()
+    .map(|(fn_name, args)| {
+        Context::Contract(ContractContext {
+            contract: game.clone(),
+            fn_name: Symbol::new(env, fn_name),
+            args,
+        })
+    })
+    .collect()
+}
+
+#[test]
+fn test_every_staking_call_is_capped() {
+    let env = setup_env();
+    let key = Key::ed25519(1);
+    let game = Address::generate(&env);
+    let account = register_account(&env, &key, vec![&env, game.clone()]);
+    let client = GameAccountContractClient::new(&env, &account);
+
+    for context in staking_contexts(&env, &game, MAX_STAKE + 1) {
+        assert_eq!(
+            check_auth(&env, &account, &key, vec![&env, context]),
+            Err(Error::StakeAboveLimit)
+        );
+    }
+
+    // Each fits under the cap, but not under what is left of today's limit
+    assert_eq!(
+        check_auth(
+            &env,
+            &account,
+            &key,
+            vec![
+                &env,
+                start_game_context(&env, &game, 1, 100),
+                start_game_context(&env, &game, 2, 100)
+            ]
+        ),
+        Ok(())
+    );
+    for context in staking_contexts(&env, &game, 51) {
+        assert_eq!(
+            check_auth(&env, &account, &key, vec![&env, context]),
+            Err(Error::DailyLimitExceeded)
+        );
+    }
+    assert_eq!(client.get_daily_spend(), 200);
+
+    for context in staking_contexts(&env, &game, 5) {
+        assert_eq!(
+            check_auth(&env, &account, &key, vec![&env, context]),
+            Ok(())
+        );
+    }
+    assert_eq!(client.get_daily_spend(), 230);
+}
+
+fn transfer_context(env: &Env, token: &Address, from: &Address, amount: i128) -> Context {
+    Context::Contract(ContractContext {
+        contract: token.clone(),
+        fn_name: Symbol::new(env, "transfer"),
+        args: vec![
+            env,
+            from.into_val(env),
+            Address::generate(env).into_val(env),
+            amount.into_val(env),
+        ],
+    })
+}
+
+#[test]
+fn test_token_transfers_count_against_the_limits() {
+    let env = setup_env();
+    let key = Key::ed25519(1);
+    let game = Address::generate(&env);
+    let token = Address::generate(&env);
+    let account = register_account(&env, &key, vec![&env, game.clone(), token.clone()]);
+    let client = GameAccountContractClient::new(&env, &account);
+
+    let context = transfer_context(&env, &token, &account, MAX_STAKE + 1);
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, context]),
+        Err(Error::StakeAboveLimit)
+    );
+    let context = transfer_context(&env, &token, &account, -1);
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, context]),
+        Err(Error::InvalidStakeArgs)
+    );
+    let approve = Context::Contract(ContractContext {
+        contract: token.clone(),
+        fn_name: Symbol::new(&env, "approve"),
+        args: Vec::new(&env),
+    });
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, approve]),
+        Err(Error::ContextNotAllowed)
+    );
+
+    // A stake and a bond in one authorization share today's limit
+    let contexts = vec![
+        &env,
+        start_game_context(&env, &game, 1, 100),
+        transfer_context(&env, &token, &account, 100),
+    ];
+    assert_eq!(check_auth(&env, &account, &key, contexts), Ok(()));
+    assert_eq!(client.get_daily_spend(), 200);
+    let context = transfer_context(&env, &token, &account, 51);
+    assert_eq!(
+        check_auth(&env, &account, &key, vec![&env, context]),
+        Err(Error::DailyLimitExceeded)
+    );
+}
+
+#[test]
+fn test_policy_updates_are_validated() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let key = Key::ed25519(1);
+    let account = register_account(&env, &key, Vec::new(&env));
+    let client = GameAccountContractClient::new(&env, &account);
+
+    let game = Address::generate(&env);
+    let policy = Policy {
+        games: vec![&env, game],
+        max_stake: 10,
+        daily_limit: 20,
+    };
+    client.set_policy(&policy);
+    assert_eq!(client.get_policy(), policy);
+
+    let bad = Policy {
+        max_stake: -1,
+        ..policy
+    };
+    assert_eq!(client.try_set_policy(&bad), Err(Ok(Error::InvalidPolicy)));
+
+    client.set_signer(&Key::secp256r1(2).signer(&env));
+    assert_eq!(client.get_signer(), Key::secp256r1(2).signer(&env));
+}
+
+// ============================================================================
+// End-to-End Tests
+// ============================================================================
+
+#[test]
+fn test_accounts_start_my_game_with_real_signatures() {
+    let env = setup_env();
+    let hub = env.register(HubStub, ());
+    let game = env.register(MyGameContract, (&Address::generate(&env), &hub));
+    let my_game = MyGameContractClient::new(&env, &game);
+
+    let key1 = Key::ed25519(1);
+    let key2 = Key::secp256r1(2);
+    let player1 = register_account(&env, &key1, vec![&env, game.clone()]);
+    let player2 = register_account(&env, &key2, vec![&env, game.clone()]);
+
+    env.set_auths(&[
+        start_game_entry(&env, &player1, &key1, 1, &game, 7, 60),
+        start_game_entry(&env, &player2, &key2, 1, &game, 7, 80),
+    ]);
+    my_game.start_game(&7, &player1, &player2, &60, &80);
+    assert_eq!(
+        GameAccountContractClient::new(&env, &player1).get_daily_spend(),
+        60
+    );
+    assert_eq!(
+        GameAccountContractClient::new(&env, &player2).get_daily_spend(),
+        80
+    );
+
+    // Over the per-session cap, even though the key signed it
+    env.set_auths(&[
+        start_game_entry(&env, &player1, &key1, 2, &game, 8, 10),
+        start_game_entry(&env, &player2, &key2, 2, &game, 8, MAX_STAKE + 1),
+    ]);
+    assert!(my_game
+        .try_start_game(&8, &player1, &player2, &10, &(MAX_STAKE + 1))
+        .is_err());
+    assert_eq!(
+        GameAccountContractClient::new(&env, &player1).get_daily_spend(),
+        60
+    );
+
+    // Hand the codebreaker's moves to a session key, then play without the passkey
+    let session_key = Address::generate(&env);
+    let scope = vec![&env, Symbol::new(&env, "submit_guess")];
+    let args = vec![
+        &env,
+        7u32.into_val(&env),
+        player2.into_val(&env),
+        session_key.into_val(&env),
+        scope.into_val(&env),
+        200u32.into_val(&env),
+    ];
+    env.set_auths(&[signed_entry(
+        &env,
+        &player2,
+        &key2,
+        3,
+        &game,
+        "register_session_key",
+        args,
+    )]);
+    my_game.register_session_key(&7, &player2, &session_key, &scope, &200);
+
+    env.mock_all_auths();
+    my_game.commit_code(&7, &BytesN::from_array(&env, &[9; 32]));
+    my_game.submit_guess_as(&7, &session_key, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    assert_eq!(env.auths()[0].0, session_key);
+}
+
+#[test]
+fn test_account_posts_my_game_bond_within_its_limits() {
+    let env = setup_env();
+    env.mock_all_auths();
+    let hub = env.register(HubStub, ());
+    let game = env.register(MyGameContract, (&Address::generate(&env), &hub));
+    let my_game = MyGameContractClient::new(&env, &game);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    let key1 = Key::ed25519(1);
+    let player1 = register_account(&env, &key1, vec![&env, game.clone(), token.clone()]);
+    let unlisted = register_account(&env, &key1, vec![&env, game.clone()]);
+    for account in [&player1, &unlisted] {
+        StellarAssetClient::new(&env, &token).mint(account, &1_000);
+    }
+    my_game.start_game(&7, &player1, &Address::generate(&env), &60, &60);
+    my_game.start_game(&8, &unlisted, &Address::generate(&env), &60, &60);
+
+    // The codemaker signs the commit together with the bond transfer under it
+    let commitment = BytesN::from_array(&env, &[9; 32]);
+    let commit_entry = |account: &Address, nonce: i64, session_id: u32, bond: i128| {
+        let mut commit = invocation(
+            &env,
+            &game,
+            "commit_code",
+            vec![&env, session_id.into_val(&env), commitment.into_val(&env)],
+        );
+        let transfer = invocation(
+            &env,
+            &token,
+            "transfer",
+            vec![
+                &env,
+                account.into_val(&env),
+                game.into_val(&env),
+                bond.into_val(&env),
+            ],
+        );
+        commit.sub_invocations = std::vec![transfer].try_into().unwrap();
+        sign_invocation(&env, account, &key1, nonce, commit)
+    };
+    let set_bond = |amount: i128| {
+        env.mock_all_auths();
+        my_game.set_bond_config(&Some(BondConfig {
+            token: token.clone(),
+            amount,
+            feedback_ledgers: 10,
+        }));
+    };
+
+    set_bond(MAX_STAKE + 1);
+    env.set_auths(&[commit_entry(&player1, 1, 7, MAX_STAKE + 1)]);
+    assert!(my_game.try_commit_code(&7, &commitment).is_err());
+
+    set_bond(60);
+    env.set_auths(&[commit_entry(&unlisted, 1, 8, 60)]);
+    assert!(my_game.try_commit_code(&8, &commitment).is_err());
+
+    env.set_auths(&[commit_entry(&player1, 2, 7, 60)]);
+    my_game.commit_code(&7, &commitment);
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&game), 60);
+    assert_eq!(
+        GameAccountContractClient::new(&env, &player1).get_daily_spend(),
+        60
+    );
+}
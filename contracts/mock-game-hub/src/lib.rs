@@ -1,12 +1,16 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractevent, contractimpl, Address, Env};
+use soroban_sdk::{contract, contractevent, contractimpl, Address, Env, Vec};
 
 /// Mock Game Hub contract for game studio development
 ///
 /// This contract provides the same external interface that games expect
 /// (start_game, end_game) but does nothing internally. It exists purely
 /// for game contracts to compile and integrate during development.
+///
+/// It also implements extensions the Game Hub interface does not define: N-player
+/// sessions (start_game_multi, end_game_multi) and add_points. Games only call them once
+/// their admin turns on `set_hub_extensions`, which is only safe against a hub like this one.
 #[contract]
 pub struct MockGameHub;
 
@@ -20,12 +24,33 @@ pub struct GameStarted {
     pub player2_points: i128,
 }
 
+#[contractevent]
+pub struct MultiGameStarted {
+    pub session_id: u32,
+    pub game_id: Address,
+    pub players: Vec<Address>,
+    pub points: Vec<i128>,
+}
+
+#[contractevent]
+pub struct PointsAdded {
+    pub session_id: u32,
+    pub player: Address,
+    pub points: i128,
+}
+
 #[contractevent]
 pub struct GameEnded {
     pub session_id: u32,
     pub player1_won: bool,
 }
 
+#[contractevent]
+pub struct MultiGameEnded {
+    pub session_id: u32,
+    pub winners: Vec<Address>,
+}
+
 #[contractimpl]
 impl MockGameHub {
     /// Start a game session
@@ -60,6 +85,49 @@ impl MockGameHub {
         env.storage().instance().extend_ttl(17_280, 518_400);
     }
 
+    /// Start a game session for any number of players
+    ///
+    /// # Arguments
+    /// * `game_id` - Address of the game contract calling this method
+    /// * `session_id` - Unique identifier for this game session
+    /// * `players` - Addresses of the seated players, in seat order
+    /// * `points` - Points amount for each player, in seat order (ignored in mock)
+    pub fn start_game_multi(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    ) {
+        // No auth required for mock
+        MultiGameStarted {
+            session_id,
+            game_id,
+            players,
+            points,
+        }
+        .publish(&env);
+        // bump instance ttl if required
+        env.storage().instance().extend_ttl(17_280, 518_400);
+    }
+
+    /// Lock additional points for a player in a running session
+    /// (e.g. doubling down or splitting a hand)
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session the points are added to
+    /// * `player` - Address of the player committing the points
+    /// * `points` - Additional points amount (ignored in mock)
+    pub fn add_points(env: Env, session_id: u32, player: Address, points: i128) {
+        // No auth required for mock
+        PointsAdded {
+            session_id,
+            player,
+            points,
+        }
+        .publish(&env);
+    }
+
     /// End a game session and declare winner
     ///
     /// # Arguments
@@ -77,21 +145,54 @@ impl MockGameHub {
         }
         .publish(&env);
     }
+
+    /// End a game session with one or more winners
+    ///
+    /// Works for sessions started with either `start_game` or `start_game_multi`.
+    /// Several winners share the payout (a tie).
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `winners` - Addresses of the winning players
+    pub fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>) {
+        // No auth required for mock
+        MultiGameEnded {
+            session_id,
+            winners,
+        }
+        .publish(&env);
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::vec;
     #[test]
     fn test_start_and_end_game() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, MockGameHub);
+        let contract_id = env.register(MockGameHub, ());
         let client = MockGameHubClient::new(&env, &contract_id);
         let game_id = Address::generate(&env);
         let player1 = Address::generate(&env);
         let player2 = Address::generate(&env);
         client.start_game(&game_id, &1, &player1, &player2, &1000, &1000);
+        client.add_points(&1, &player1, &500);
         client.end_game(&1, &true);
     }
+
+    #[test]
+    fn test_start_and_end_multi_game() {
+        let env = Env::default();
+        let contract_id = env.register(MockGameHub, ());
+        let client = MockGameHubClient::new(&env, &contract_id);
+        let game_id = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        let player3 = Address::generate(&env);
+        let players = vec![&env, player1.clone(), player2.clone(), player3];
+        client.start_game_multi(&game_id, &2, &players, &vec![&env, 1000, 1000, 500]);
+        client.end_game_multi(&2, &vec![&env, player1, player2]);
+    }
 }
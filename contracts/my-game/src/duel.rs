@@ -0,0 +1,548 @@
+//! Duel mode: both players commit a code, then race to break each other's.
+//!
+//! Players take turns guessing while both are still breaking. Each guess has to be
+//! answered by the opponent with a feedback proof against the opponent's own commitment.
+//! The player who solves in fewer attempts wins; a player who solves first still leaves
+//! the opponent their remaining attempts up to the same count, and equal counts are
+//! settled by the duel's tie policy. A draw ends the game through `end_game_multi`, so
+//! the policies that can draw need the hub extensions (`set_hub_extensions`); on the
+//! standard Game Hub use `DuelTiePolicy::Player1Wins`.
+//!
+//! With a bond configured (`set_bond_config`) each player posts the bond when committing,
+//! and every guess gives the opponent `feedback_ledgers` to answer it. A player whose guess
+//! goes unanswered, or whose opponent never commits, can `claim_duel_timeout` for the win
+//! and both bonds. When both players leave a guess unanswered, the guess that was due
+//! first decides who can claim. While the verifier is broken, `report_verifier_fault`
+//! restarts the window of every pending guess, so neither bond can be taken.
+
+use mastermind_core::Feedback;
+use soroban_sdk::{
+    contractimpl, contracttype, token, vec, Address, Bytes, BytesN, Env, IntoVal, Vec,
+};
+
+// The contract's client and args types are needed by `#[contractimpl]` in this module
+use crate::{
+    verifier::VerifierBackend, DataKey, Error, GameHubClient, MyGameContract, MyGameContractArgs,
+    MyGameContractClient, TurnRecord, VerifierFaultReported, GAME_TTL_LEDGERS, MAX_ATTEMPTS,
+};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DuelTiePolicy {
+    Draw,        // Equal attempt counts draw
+    MostExact,   // More exact pegs over all attempts wins; still equal draws
+    Player1Wins, // Equal attempt counts go to player 1; never draws
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DuelSide {
+    pub player: Address,
+    pub points: i128,
+    pub commitment: Option<BytesN<32>>, // This player's code, broken by the opponent
+    pub attempts_used: u32,             // This player's guesses answered so far
+    pub pending_guess_id: Option<u32>,  // This player's guess awaiting the opponent's proof
+    pub solved_at: u32,                 // Attempt that cracked the opponent's code, 0 if not yet
+    pub exact_total: u32,               // Exact pegs over all of this player's answered guesses
+    pub feedback_deadline: u32,         // Last ledger for the opponent to answer the pending guess
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DuelGame {
+    pub sides: Vec<DuelSide>,
+    pub max_attempts: u32,
+    pub tie_policy: DuelTiePolicy,
+    pub turn: u32, // Side to guess next while both are still breaking
+    pub next_guess_id: u32,
+    pub winners: Vec<Address>, // Both players on a draw
+    pub ended: bool,
+    pub bond_token: Option<Address>,
+    pub bond: i128, // Posted by each player on commit
+    pub feedback_ledgers: u32,
+    pub commit_deadline: u32, // Last ledger for both players to commit
+    pub verifier_backend: VerifierBackend,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DuelTurn {
+    pub side: u32, // Side that guessed
+    pub turn: TurnRecord,
+}
+
+#[contractimpl]
+impl MyGameContract {
+    pub fn start_duel(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        tie_policy: DuelTiePolicy,
+    ) -> Result<(), Error> {
+        if player1 == player2 {
+            panic!(
+                "Cannot play against yourself: Player 1 and Player 2 must be different addresses"
+            );
+        }
+
+        player1.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player1_points.into_val(&env),
+        ]);
+        player2.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player2_points.into_val(&env),
+        ]);
+
+        // A draw is reported through `end_game_multi`, which needs the hub extensions
+        if tie_policy != DuelTiePolicy::Player1Wins
+            && !MyGameContract::get_hub_extensions(env.clone())
+        {
+            return Err(Error::HubExtensionsRequired);
+        }
+
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        game_hub.start_game(
+            &env.current_contract_address(),
+            &session_id,
+            &player1,
+            &player2,
+            &player1_points,
+            &player2_points,
+        );
+
+        let side = |player: Address, points: i128| DuelSide {
+            player,
+            points,
+            commitment: None,
+            attempts_used: 0,
+            pending_guess_id: None,
+            solved_at: 0,
+            exact_total: 0,
+            feedback_deadline: 0,
+        };
+        let mut duel = DuelGame {
+            sides: vec![
+                &env,
+                side(player1, player1_points),
+                side(player2, player2_points),
+            ],
+            max_attempts: MAX_ATTEMPTS,
+            tie_policy,
+            turn: 0,
+            next_guess_id: 0,
+            winners: Vec::new(&env),
+            ended: false,
+            bond_token: None,
+            bond: 0,
+            feedback_ledgers: 0,
+            commit_deadline: 0,
+            verifier_backend: MyGameContract::get_verifier_backend(env.clone()),
+        };
+        if let Some(config) = MyGameContract::get_bond_config(env.clone()) {
+            duel.bond_token = Some(config.token);
+            duel.bond = config.amount;
+            duel.feedback_ledgers = config.feedback_ledgers;
+            duel.commit_deadline = env.ledger().sequence() + config.feedback_ledgers;
+        }
+
+        write_duel(&env, session_id, &duel);
+        Ok(())
+    }
+
+    pub fn commit_duel_code(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), Error> {
+        let mut duel = load_duel(&env, session_id)?;
+        if duel.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        player.require_auth();
+
+        let seat = seat_of(&duel, &player)?;
+        let mut side = duel.sides.get(seat).unwrap();
+        if side.commitment.is_some() {
+            return Err(Error::CommitmentAlreadySet);
+        }
+        if let Some(bond_token) = &duel.bond_token {
+            if duel.bond > 0 {
+                token::Client::new(&env, bond_token).transfer(
+                    &player,
+                    env.current_contract_address(),
+                    &duel.bond,
+                );
+            }
+        }
+        side.commitment = Some(commitment);
+        duel.sides.set(seat, side);
+
+        write_duel(&env, session_id, &duel);
+        Ok(())
+    }
+
+    pub fn submit_duel_guess(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        guess: BytesN<4>,
+    ) -> Result<u32, Error> {
+        let mut duel = load_duel(&env, session_id)?;
+        if duel.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if duel.sides.iter().any(|side| side.commitment.is_none()) {
+            return Err(Error::CommitmentNotSet);
+        }
+        player.require_auth();
+
+        let seat = seat_of(&duel, &player)?;
+        let mut side = duel.sides.get(seat).unwrap();
+        if side.pending_guess_id.is_some() {
+            return Err(Error::GuessPendingFeedback);
+        }
+        if side.solved_at > 0 || side.attempts_used >= duel.max_attempts {
+            return Err(Error::AttemptsExhausted);
+        }
+        // Turns only alternate while the opponent is still breaking too
+        let opponent = duel.sides.get(1 - seat).unwrap();
+        if is_breaking(&opponent, duel.max_attempts) && duel.turn != seat {
+            return Err(Error::NotYourTurn);
+        }
+        MyGameContract::parse_guess(&guess)?;
+
+        let guess_id = duel.next_guess_id;
+        duel.next_guess_id += 1;
+        side.pending_guess_id = Some(guess_id);
+        if duel.feedback_ledgers > 0 {
+            side.feedback_deadline = env.ledger().sequence() + duel.feedback_ledgers;
+        }
+        duel.sides.set(seat, side);
+        duel.turn = 1 - seat;
+
+        write_duel_turn(
+            &env,
+            session_id,
+            &DuelTurn {
+                side: seat,
+                turn: TurnRecord {
+                    guess_id,
+                    guess,
+                    exact: 0,
+                    partial: 0,
+                    proof_hash: None,
+                },
+            },
+        );
+        write_duel(&env, session_id, &duel);
+        Ok(guess_id)
+    }
+
+    pub fn submit_duel_feedback(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        let mut duel = load_duel(&env, session_id)?;
+        if duel.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        player.require_auth();
+
+        // `player` answers the opponent's pending guess against their own commitment
+        let maker_seat = seat_of(&duel, &player)?;
+        let breaker_seat = 1 - maker_seat;
+        let commitment = duel
+            .sides
+            .get(maker_seat)
+            .unwrap()
+            .commitment
+            .ok_or(Error::CommitmentNotSet)?;
+        let mut breaker = duel.sides.get(breaker_seat).unwrap();
+        let pending_guess_id = breaker.pending_guess_id.ok_or(Error::NoPendingGuess)?;
+        if pending_guess_id != guess_id {
+            return Err(Error::InvalidGuessId);
+        }
+        let feedback = Feedback::new(exact, partial).map_err(|_| Error::InvalidFeedback)?;
+
+        let mut record = load_duel_turn(&env, session_id, guess_id)?;
+        let guess = MyGameContract::parse_guess(&record.turn.guess)?;
+        let expected_public_inputs = MyGameContract::build_public_inputs(
+            &env,
+            session_id,
+            guess_id,
+            &commitment,
+            &guess,
+            &feedback,
+        );
+        let (public_inputs, proof_bytes) = duel.verifier_backend.split_proof_blob(&proof_blob)?;
+        if expected_public_inputs != public_inputs {
+            return Err(Error::InvalidPublicInputs);
+        }
+        let proof_hash = duel
+            .verifier_backend
+            .verify(&env, &public_inputs, &proof_bytes)?;
+
+        record.turn.exact = exact;
+        record.turn.partial = partial;
+        record.turn.proof_hash = Some(proof_hash);
+        write_duel_turn(&env, session_id, &record);
+
+        breaker.pending_guess_id = None;
+        breaker.attempts_used += 1;
+        breaker.exact_total += exact;
+        if feedback.is_solved() {
+            breaker.solved_at = breaker.attempts_used;
+        }
+        duel.sides.set(breaker_seat, breaker);
+
+        if let Some(winners) = duel_result(&env, &duel) {
+            finish_duel(&env, session_id, &mut duel, winners);
+        }
+
+        write_duel(&env, session_id, &duel);
+        Ok(())
+    }
+
+    // `player`'s opponent stalled: left their guess unanswered past the deadline, or never
+    // committed. `player` wins and takes both bonds, unless `player` is also overdue on an
+    // opponent's guess that was due first.
+    pub fn claim_duel_timeout(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        let mut duel = load_duel(&env, session_id)?;
+        if duel.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        player.require_auth();
+
+        let seat = seat_of(&duel, &player)?;
+        let side = duel.sides.get(seat).unwrap();
+        let opponent = duel.sides.get(1 - seat).unwrap();
+        let awaiting_commit = side.commitment.is_some() && opponent.commitment.is_none();
+        if side.pending_guess_id.is_none() && !awaiting_commit {
+            return Err(Error::NoPendingGuess);
+        }
+        let sequence = env.ledger().sequence();
+        let feedback_expired = side.pending_guess_id.is_some() && sequence > side.feedback_deadline;
+        let commit_expired = awaiting_commit && sequence > duel.commit_deadline;
+        if duel.feedback_ledgers == 0 || !(feedback_expired || commit_expired) {
+            return Err(Error::FeedbackNotExpired);
+        }
+        // With both sides in default, the guess that has waited longer takes the win
+        if let (Some(own_guess_id), Some(opponent_guess_id)) =
+            (side.pending_guess_id, opponent.pending_guess_id)
+        {
+            let overdue_first = (opponent.feedback_deadline, opponent_guess_id)
+                < (side.feedback_deadline, own_guess_id);
+            if sequence > opponent.feedback_deadline && overdue_first {
+                return Err(Error::FeedbackOverdue);
+            }
+        }
+
+        finish_duel(&env, session_id, &mut duel, vec![&env, player]);
+        write_duel(&env, session_id, &duel);
+        Ok(())
+    }
+
+    pub fn get_duel(env: Env, session_id: u32) -> Result<DuelGame, Error> {
+        load_duel(&env, session_id)
+    }
+
+    pub fn get_duel_turn(env: Env, session_id: u32, guess_id: u32) -> Result<DuelTurn, Error> {
+        load_duel(&env, session_id)?;
+        load_duel_turn(&env, session_id, guess_id)
+    }
+}
+
+// `report_verifier_fault` for a duel. Either side may owe an answer, and the probe is
+// what shows the fault, so no seat has to sign.
+pub(crate) fn report_verifier_fault(env: &Env, session_id: u32) -> Result<(), Error> {
+    let mut duel = load_duel(env, session_id)?;
+    if duel.ended {
+        return Err(Error::GameAlreadyEnded);
+    }
+    if duel
+        .sides
+        .iter()
+        .all(|side| side.pending_guess_id.is_none())
+    {
+        return Err(Error::NoPendingGuess);
+    }
+    let cause = duel
+        .verifier_backend
+        .probe(env)?
+        .ok_or(Error::NoVerifierFault)?;
+
+    let mut feedback_deadline = 0;
+    if duel.feedback_ledgers > 0 {
+        feedback_deadline = env.ledger().sequence() + duel.feedback_ledgers;
+        for seat in 0..duel.sides.len() {
+            let mut side = duel.sides.get(seat).unwrap();
+            if side.pending_guess_id.is_some() {
+                side.feedback_deadline = feedback_deadline;
+                duel.sides.set(seat, side);
+            }
+        }
+    }
+    VerifierFaultReported {
+        session_id,
+        cause: cause as u32,
+        feedback_deadline,
+        hint_deadline: 0,
+    }
+    .publish(env);
+
+    write_duel(env, session_id, &duel);
+    Ok(())
+}
+
+// Report the result to the hub and pay out the posted bonds: all to a sole winner, each
+// back to its owner on a draw
+fn finish_duel(env: &Env, session_id: u32, duel: &mut DuelGame, winners: Vec<Address>) {
+    let game_hub_addr: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .expect("GameHub address not set");
+    let game_hub = GameHubClient::new(env, &game_hub_addr);
+    if winners.len() == 1 {
+        let player1_won = winners.get(0).unwrap() == duel.sides.get(0).unwrap().player;
+        game_hub.end_game(&session_id, &player1_won);
+    } else {
+        game_hub.end_game_multi(&session_id, &winners);
+    }
+
+    if let Some(bond_token) = &duel.bond_token {
+        if duel.bond > 0 {
+            let bond_token = token::Client::new(env, bond_token);
+            for side in duel.sides.iter().filter(|side| side.commitment.is_some()) {
+                let to = if winners.len() == 1 {
+                    winners.get(0).unwrap()
+                } else {
+                    side.player
+                };
+                bond_token.transfer(&env.current_contract_address(), &to, &duel.bond);
+            }
+        }
+    }
+
+    duel.winners = winners;
+    duel.ended = true;
+}
+
+// A side is still breaking until it solves or runs out of attempts
+fn is_breaking(side: &DuelSide, max_attempts: u32) -> bool {
+    side.solved_at == 0 && side.attempts_used < max_attempts
+}
+
+// Winners once the result can no longer change, otherwise `None`
+fn duel_result(env: &Env, duel: &DuelGame) -> Option<Vec<Address>> {
+    let first = duel.sides.get(0).unwrap();
+    let second = duel.sides.get(1).unwrap();
+    // Attempts a side needed, or one past the limit if it never solved
+    let needed = |side: &DuelSide| {
+        if side.solved_at > 0 {
+            side.solved_at
+        } else {
+            duel.max_attempts + 1
+        }
+    };
+
+    let first_needed = needed(&first);
+    let second_needed = needed(&second);
+    let first_done = !is_breaking(&first, duel.max_attempts);
+    let second_done = !is_breaking(&second, duel.max_attempts);
+
+    // A solve is final once the other side has used as many attempts without matching it
+    let winner = if first.solved_at > 0
+        && second.attempts_used >= first_needed
+        && second_needed > first_needed
+    {
+        Some(first.player.clone())
+    } else if second.solved_at > 0
+        && first.attempts_used >= second_needed
+        && first_needed > second_needed
+    {
+        Some(second.player.clone())
+    } else if first_done && second_done {
+        if first_needed < second_needed {
+            Some(first.player.clone())
+        } else if second_needed < first_needed {
+            Some(second.player.clone())
+        } else {
+            return Some(tie_result(env, duel, &first, &second));
+        }
+    } else {
+        None
+    };
+    winner.map(|player| vec![env, player])
+}
+
+fn tie_result(env: &Env, duel: &DuelGame, first: &DuelSide, second: &DuelSide) -> Vec<Address> {
+    match duel.tie_policy {
+        DuelTiePolicy::Player1Wins => return vec![env, first.player.clone()],
+        DuelTiePolicy::MostExact => {
+            if first.exact_total > second.exact_total {
+                return vec![env, first.player.clone()];
+            }
+            if second.exact_total > first.exact_total {
+                return vec![env, second.player.clone()];
+            }
+        }
+        DuelTiePolicy::Draw => {}
+    }
+    vec![env, first.player.clone(), second.player.clone()]
+}
+
+fn seat_of(duel: &DuelGame, player: &Address) -> Result<u32, Error> {
+    duel.sides
+        .iter()
+        .position(|side| side.player == *player)
+        .map(|seat| seat as u32)
+        .ok_or(Error::NotPlayer)
+}
+
+fn load_duel(env: &Env, session_id: u32) -> Result<DuelGame, Error> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::Duel(session_id))
+        .ok_or(Error::GameNotFound)
+}
+
+fn write_duel(env: &Env, session_id: u32, duel: &DuelGame) {
+    let duel_key = DataKey::Duel(session_id);
+    env.storage().temporary().set(&duel_key, duel);
+    env.storage()
+        .temporary()
+        .extend_ttl(&duel_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+}
+
+fn load_duel_turn(env: &Env, session_id: u32, guess_id: u32) -> Result<DuelTurn, Error> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::DuelTurn(session_id, guess_id))
+        .ok_or(Error::InvalidGuessId)
+}
+
+fn write_duel_turn(env: &Env, session_id: u32, turn: &DuelTurn) {
+    let turn_key = DataKey::DuelTurn(session_id, turn.turn.guess_id);
+    env.storage().temporary().set(&turn_key, turn);
+    env.storage()
+        .temporary()
+        .extend_ttl(&turn_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+}
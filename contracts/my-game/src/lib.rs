@@ -1,8 +1,9 @@
 #![no_std]
 
+use mastermind_core::{Code, Feedback, Hint, Rules, Turn};
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype, vec, Address, Bytes,
-    BytesN, Env, IntoVal, Vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype, token,
+    vec, Address, Bytes, BytesN, Env, IntoVal, Vec,
 };
 
 #[contractclient(name = "GameHubClient")]
@@ -18,6 +19,11 @@ pub trait GameHub {
     );
 
     fn end_game(env: Env, session_id: u32, player1_won: bool);
+
+    // Extensions, only called when `set_hub_extensions` is on
+    fn add_points(env: Env, session_id: u32, player: Address, points: i128);
+
+    fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>);
 }
 
 #[contracterror]
@@ -54,22 +60,127 @@ pub enum Error {
     VerifierNotSet = 13,
     InvalidProofBlob = 14,
     InvalidGuess = 15,
+    InconsistentFeedback = 16,
+    GameNotEnded = 17,
+    AlreadyRevealed = 18,
+    RevealMismatch = 19,
+    InvalidConfig = 20,
+    FeedbackNotExpired = 21,
+    HintUnavailable = 22,
+    HintPending = 23,
+    NoPendingHint = 24,
+    InvalidHint = 25,
+    NotYourTurn = 26,
+    InvalidBatch = 27,
+    VerifierMisconfigured = 28,
+    VerifierUnavailable = 29,
+    MalformedProof = 30,
+    NoVerifierFault = 31,
+    ProofAlreadyUsed = 32,
+    SessionKeyInvalid = 33,
+    SessionKeyExpired = 34,
+    VerifierProbeNotSet = 35,
+    HubExtensionsRequired = 36,
+    FeedbackOverdue = 37,
+    RevealNotExpired = 38,
+}
+
+// Every failed verifier call, with the `Error` it became. Failed calls revert, so this
+// shows up in diagnostic events only.
+#[contractevent]
+pub struct VerifierFailure {
+    pub verifier: Address,
+    pub cause: u32,
+}
+
+// The codemaker showed a verifier is broken; carries the deadlines after the restart
+#[contractevent]
+pub struct VerifierFaultReported {
+    pub session_id: u32,
+    pub cause: u32,
+    pub feedback_deadline: u32,
+    pub hint_deadline: u32,
+}
+
+// Blake2s is what the circuit proves feedback against. The host-hash schemes are for
+// circuits that commit with sha256/keccak256 instead; they make reveals cheaper to check.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitmentScheme {
+    Blake2s,
+    Sha256,
+    Keccak256,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct GuessRecord {
+pub struct TurnRecord {
     pub guess_id: u32,
     pub guess: BytesN<4>,
+    pub exact: u32,
+    pub partial: u32,
+    pub proof_hash: Option<BytesN<32>>,
+}
+
+// Hint public inputs carry the statement type, 1-3; feedback public inputs carry none.
+// Each kind is proven by its own circuit in zk/my-game-hint-circuits.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HintKind {
+    MostFrequentColor,
+    PositionIs,
+    ColorCount,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HintRecord {
+    pub hint_id: u32,
+    pub kind: HintKind,
+    pub position: u32,
+    pub color: u32,
+    pub price: i128,
+    pub answer: u32,
+    pub proof_hash: Option<BytesN<32>>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HintConfig {
+    pub price: i128,
+    pub verifier: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondConfig {
+    pub token: Address,
+    pub amount: i128,
+    pub feedback_ledgers: u32, // Window to answer each guess, and to reveal after the end
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditReport {
+    pub turns: u32,
+    pub consistent_codes: u32,
+    pub first_contradiction: Option<u32>,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct FeedbackRecord {
+pub struct FeedbackAnswer {
     pub guess_id: u32,
     pub exact: u32,
     pub partial: u32,
-    pub proof_hash: BytesN<32>,
+    pub proof_blob: Bytes,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FeedbackPair {
+    pub exact: u32,
+    pub partial: u32,
 }
 
 #[contracttype]
@@ -80,28 +191,63 @@ pub struct Game {
     pub player1_points: i128,
     pub player2_points: i128,
     pub commitment: Option<BytesN<32>>,
+    pub commitment_scheme: CommitmentScheme,
     pub max_attempts: u32,
     pub attempts_used: u32,
     pub next_guess_id: u32,
-    pub pending_guess_id: Option<u32>,
-    pub guesses: Vec<GuessRecord>,
-    pub feedbacks: Vec<FeedbackRecord>,
+    pub pending_guess_id: Option<u32>, // Oldest guess awaiting feedback
+    pub queue_depth: u32,              // Guesses that may await feedback at once
+    pub verifier_backend: VerifierBackend,
     pub winner: Option<Address>,
     pub solved: bool,
     pub ended: bool,
+    pub revealed_secret: Option<BytesN<4>>,
+    pub audited: bool,
+    pub bond_token: Option<Address>,
+    pub bond: i128, // Held until the codemaker reveals after a normal end
+    pub feedback_ledgers: u32,
+    pub feedback_deadline: u32,
+    pub reveal_deadline: u32, // Last ledger to reveal and reclaim the bond
+    pub next_hint_id: u32,
+    pub pending_hint_id: Option<u32>,
+    pub hint_deadline: u32,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Game(u32),
+    Turn(u32, u32),
     GameHubAddress,
     Admin,
     VerifierAddress,
+    BatchVerifierAddress,
+    VerifierBackend,
+    VerifierProbe(Address),
+    Groth16Vk,
+    UsedProof(BytesN<32>),
+    SessionKey(u32, Address),
+    ConsistencyCheck,
+    BondConfig,
+    QueueDepth,
+    Hint(u32, u32),
+    HintConfig(HintKind),
+    Duel(u32),
+    DuelTurn(u32, u32),
+    Puzzle(u32),
+    PuzzleTrack(u32, Address),
+    PuzzleTurn(u32, Address, u32),
+    Leaderboard(u32),
+    HubExtensions,
 }
 
 const GAME_TTL_LEDGERS: u32 = 518_400;
+const PROOF_TTL_LEDGERS: u32 = 3_110_400;
 const MAX_ATTEMPTS: u32 = 12;
+const MAX_TURNS_PAGE: u32 = 32;
+const FEEDBACK_BATCH_SIZE: u32 = 4;
+const PROOF_FIELDS: [u32; 3] = [456, 440, 234];
+const RULES: Rules = Rules::CLASSIC;
 
 #[contract]
 pub struct MyGameContract;
@@ -122,28 +268,70 @@ impl MyGameContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
+    ) -> Result<(), Error> {
+        Self::open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            [None, None],
+        )
+    }
+
+    // `start_game` that also registers each player's session key, signed for in the same
+    // auth entry as the points. Without keys it is exactly `start_game`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_game_with_session_keys(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        player1_key: Option<SessionKey>,
+        player2_key: Option<SessionKey>,
+    ) -> Result<(), Error> {
+        Self::open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            [player1_key, player2_key],
+        )
+    }
+
+    fn open_game(
+        env: &Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        session_keys: [Option<SessionKey>; 2],
     ) -> Result<(), Error> {
         if player1 == player2 {
             panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
         }
 
-        player1.require_auth_for_args(vec![
-            &env,
-            session_id.into_val(&env),
-            player1_points.into_val(&env),
-        ]);
-        player2.require_auth_for_args(vec![
-            &env,
-            session_id.into_val(&env),
-            player2_points.into_val(&env),
-        ]);
+        let seats = [(&player1, player1_points), (&player2, player2_points)];
+        for ((player, points), session_key) in seats.into_iter().zip(&session_keys) {
+            let mut args = vec![env, session_id.into_val(env), points.into_val(env)];
+            if let Some(session_key) = session_key {
+                args.push_back(session_key.into_val(env));
+            }
+            player.require_auth_for_args(args);
+        }
 
         let game_hub_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::GameHubAddress)
             .expect("GameHub address not set");
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        let game_hub = GameHubClient::new(env, &game_hub_addr);
 
         game_hub.start_game(
             &env.current_contract_address(),
@@ -160,22 +348,49 @@ impl MyGameContract {
             player1_points,
             player2_points,
             commitment: None,
+            commitment_scheme: CommitmentScheme::Blake2s,
             max_attempts: MAX_ATTEMPTS,
             attempts_used: 0,
             next_guess_id: 0,
             pending_guess_id: None,
-            guesses: Vec::new(&env),
-            feedbacks: Vec::new(&env),
+            queue_depth: Self::get_queue_depth(env.clone()),
+            verifier_backend: Self::get_verifier_backend(env.clone()),
             winner: None,
             solved: false,
             ended: false,
+            revealed_secret: None,
+            audited: false,
+            bond_token: None,
+            bond: 0,
+            feedback_ledgers: 0,
+            feedback_deadline: 0,
+            next_hint_id: 0,
+            pending_hint_id: None,
+            hint_deadline: 0,
+            reveal_deadline: 0,
         };
 
-        Self::write_game(&env, session_id, &game);
+        Self::write_game(env, session_id, &game);
+
+        let players = [game.player1.clone(), game.player2.clone()];
+        for (player, session_key) in players.iter().zip(session_keys) {
+            if let Some(session_key) = session_key {
+                Self::set_session_key(env, session_id, &game, player, session_key)?;
+            }
+        }
         Ok(())
     }
 
     pub fn commit_code(env: Env, session_id: u32, commitment: BytesN<32>) -> Result<(), Error> {
+        Self::commit_code_with_scheme(env, session_id, commitment, CommitmentScheme::Blake2s)
+    }
+
+    pub fn commit_code_with_scheme(
+        env: Env,
+        session_id: u32,
+        commitment: BytesN<32>,
+        scheme: CommitmentScheme,
+    ) -> Result<(), Error> {
         let mut game = Self::load_game(&env, session_id)?;
         if game.ended {
             return Err(Error::GameAlreadyEnded);
@@ -186,11 +401,44 @@ impl MyGameContract {
         }
 
         game.commitment = Some(commitment);
+        game.commitment_scheme = scheme;
+
+        if let Some(config) = Self::get_bond_config(env.clone()) {
+            if config.amount > 0 {
+                token::Client::new(&env, &config.token).transfer(
+                    &game.player1,
+                    env.current_contract_address(),
+                    &config.amount,
+                );
+            }
+            game.bond_token = Some(config.token);
+            game.bond = config.amount;
+            game.feedback_ledgers = config.feedback_ledgers;
+        }
         Self::write_game(&env, session_id, &game);
         Ok(())
     }
 
     pub fn submit_guess(env: Env, session_id: u32, guess: BytesN<4>) -> Result<u32, Error> {
+        Self::place_guess(env, session_id, None, guess)
+    }
+
+    // `signer` is the codebreaker or their session key
+    pub fn submit_guess_as(
+        env: Env,
+        session_id: u32,
+        signer: Address,
+        guess: BytesN<4>,
+    ) -> Result<u32, Error> {
+        Self::place_guess(env, session_id, Some(signer), guess)
+    }
+
+    fn place_guess(
+        env: Env,
+        session_id: u32,
+        signer: Option<Address>,
+        guess: BytesN<4>,
+    ) -> Result<u32, Error> {
         let mut game = Self::load_game(&env, session_id)?;
         if game.ended {
             return Err(Error::GameAlreadyEnded);
@@ -198,21 +446,37 @@ impl MyGameContract {
         if game.commitment.is_none() {
             return Err(Error::CommitmentNotSet);
         }
-        if game.attempts_used >= game.max_attempts {
+        // Queued guesses count against the attempts too
+        if game.next_guess_id >= game.max_attempts {
             return Err(Error::AttemptsExhausted);
         }
-        if game.pending_guess_id.is_some() {
+        if game.next_guess_id - game.attempts_used >= game.queue_depth {
             return Err(Error::GuessPendingFeedback);
         }
 
-        game.player2.require_auth();
-        Self::validate_guess_digits(&guess)?;
+        Self::authorize_move(&env, session_id, &game.player2, signer, "submit_guess")?;
+        Self::parse_guess(&guess)?;
 
         let guess_id = game.next_guess_id;
         game.next_guess_id += 1;
-        game.pending_guess_id = Some(guess_id);
-        game.guesses.push_back(GuessRecord { guess_id, guess });
+        if game.pending_guess_id.is_none() {
+            game.pending_guess_id = Some(guess_id);
+            if game.feedback_ledgers > 0 {
+                game.feedback_deadline = env.ledger().sequence() + game.feedback_ledgers;
+            }
+        }
 
+        Self::write_turn(
+            &env,
+            session_id,
+            &TurnRecord {
+                guess_id,
+                guess,
+                exact: 0,
+                partial: 0,
+                proof_hash: None,
+            },
+        );
         Self::write_game(&env, session_id, &game);
         Ok(guess_id)
     }
@@ -224,26 +488,177 @@ impl MyGameContract {
         exact: u32,
         partial: u32,
         proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        Self::answer_guess(env, session_id, None, guess_id, exact, partial, proof_blob)
+    }
+
+    // `signer` is the codemaker or their session key
+    pub fn submit_feedback_proof_as(
+        env: Env,
+        session_id: u32,
+        signer: Address,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        Self::answer_guess(
+            env,
+            session_id,
+            Some(signer),
+            guess_id,
+            exact,
+            partial,
+            proof_blob,
+        )
+    }
+
+    fn answer_guess(
+        env: Env,
+        session_id: u32,
+        signer: Option<Address>,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+        proof_blob: Bytes,
     ) -> Result<(), Error> {
         let mut game = Self::load_game(&env, session_id)?;
         if game.ended {
             return Err(Error::GameAlreadyEnded);
         }
-        game.player1.require_auth();
+        Self::authorize_move(
+            &env,
+            session_id,
+            &game.player1,
+            signer,
+            "submit_feedback_proof",
+        )?;
 
-        let commitment = game.commitment.clone().ok_or(Error::CommitmentNotSet)?;
-        let pending_guess_id = game.pending_guess_id.ok_or(Error::NoPendingGuess)?;
-        if pending_guess_id != guess_id {
-            return Err(Error::InvalidGuessId);
+        Self::apply_feedback(
+            &env,
+            session_id,
+            &mut game,
+            guess_id,
+            exact,
+            partial,
+            &proof_blob,
+        )?;
+        Self::write_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    pub fn submit_feedback_batch(
+        env: Env,
+        session_id: u32,
+        answers: Vec<FeedbackAnswer>,
+    ) -> Result<(), Error> {
+        Self::answer_batch(env, session_id, None, answers)
+    }
+
+    // `signer` is the codemaker or their session key
+    pub fn submit_feedback_batch_as(
+        env: Env,
+        session_id: u32,
+        signer: Address,
+        answers: Vec<FeedbackAnswer>,
+    ) -> Result<(), Error> {
+        Self::answer_batch(env, session_id, Some(signer), answers)
+    }
+
+    fn answer_batch(
+        env: Env,
+        session_id: u32,
+        signer: Option<Address>,
+        answers: Vec<FeedbackAnswer>,
+    ) -> Result<(), Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
         }
-        if exact > 4 || partial > 4 || exact + partial > 4 {
-            return Err(Error::InvalidFeedback);
+        Self::authorize_move(
+            &env,
+            session_id,
+            &game.player1,
+            signer,
+            "submit_feedback_batch",
+        )?;
+
+        // Answers go in queue order; one past the end of the game fails the whole batch
+        for answer in answers.iter() {
+            if game.ended {
+                return Err(Error::GameAlreadyEnded);
+            }
+            Self::apply_feedback(
+                &env,
+                session_id,
+                &mut game,
+                answer.guess_id,
+                answer.exact,
+                answer.partial,
+                &answer.proof_blob,
+            )?;
         }
+        Self::write_game(&env, session_id, &game);
+        Ok(())
+    }
 
-        let guess = Self::guess_by_id(&game, guess_id).ok_or(Error::InvalidGuessId)?;
-        let expected_public_inputs =
-            Self::build_public_inputs(&env, session_id, guess_id, &commitment, &guess, exact, partial);
-        let (public_inputs, proof_bytes) = Self::split_proof_blob(&proof_blob)?;
+    pub fn submit_feedback_aggregate(
+        env: Env,
+        session_id: u32,
+        first_guess_id: u32,
+        feedback: Vec<FeedbackPair>,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        Self::answer_aggregate(env, session_id, None, first_guess_id, feedback, proof_blob)
+    }
+
+    // `signer` is the codemaker or their session key
+    pub fn submit_feedback_aggregate_as(
+        env: Env,
+        session_id: u32,
+        signer: Address,
+        first_guess_id: u32,
+        feedback: Vec<FeedbackPair>,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        Self::answer_aggregate(
+            env,
+            session_id,
+            Some(signer),
+            first_guess_id,
+            feedback,
+            proof_blob,
+        )
+    }
+
+    fn answer_aggregate(
+        env: Env,
+        session_id: u32,
+        signer: Option<Address>,
+        first_guess_id: u32,
+        feedback: Vec<FeedbackPair>,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        Self::authorize_move(
+            &env,
+            session_id,
+            &game.player1,
+            signer,
+            "submit_feedback_aggregate",
+        )?;
+
+        let pending_guess_id = game.pending_guess_id.ok_or(Error::NoPendingGuess)?;
+        if pending_guess_id != first_guess_id {
+            return Err(Error::InvalidGuessId);
+        }
+        let (turns, expected_public_inputs) =
+            Self::expected_aggregate_inputs(&env, &game, session_id, first_guess_id, &feedback)?;
+        let (public_inputs, proof_bytes) =
+            Self::split_proof_blob_at(&proof_blob, expected_public_inputs.len())?;
         if expected_public_inputs != public_inputs {
             return Err(Error::InvalidPublicInputs);
         }
@@ -251,78 +666,461 @@ impl MyGameContract {
         let verifier_addr: Address = env
             .storage()
             .instance()
-            .get(&DataKey::VerifierAddress)
+            .get(&DataKey::BatchVerifierAddress)
             .ok_or(Error::VerifierNotSet)?;
-        let verifier = UltraHonkVerifierClient::new(&env, &verifier_addr);
-        match verifier.try_verify_proof(&public_inputs, &proof_bytes) {
-            Ok(Ok(())) => {}
-            _ => return Err(Error::InvalidProof),
+        // Every turn in the batch points at the one proof
+        let proof_hash = Self::verify_proof(&env, &verifier_addr, &public_inputs, &proof_bytes)?;
+        for (turn, pair) in turns.iter().zip(feedback.iter()) {
+            if game.ended {
+                return Err(Error::GameAlreadyEnded);
+            }
+            Self::record_feedback(
+                &env,
+                session_id,
+                &mut game,
+                turn,
+                pair.exact,
+                pair.partial,
+                proof_hash.clone(),
+            )?;
         }
+        Self::write_game(&env, session_id, &game);
+        Ok(())
+    }
 
-        let proof_hash = env.crypto().keccak256(&proof_blob);
-        game.feedbacks.push_back(FeedbackRecord {
-            guess_id,
-            exact,
-            partial,
-            proof_hash: proof_hash.into(),
-        });
-        game.pending_guess_id = None;
-        game.attempts_used += 1;
+    pub fn expected_aggregate_public_inputs(
+        env: Env,
+        session_id: u32,
+        first_guess_id: u32,
+        feedback: Vec<FeedbackPair>,
+    ) -> Result<Bytes, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        let (_, public_inputs) =
+            Self::expected_aggregate_inputs(&env, &game, session_id, first_guess_id, &feedback)?;
+        Ok(public_inputs)
+    }
 
-        if exact == 4 {
-            let game_hub_addr: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::GameHubAddress)
-                .expect("GameHub address not set");
-            let game_hub = GameHubClient::new(&env, &game_hub_addr);
-            game_hub.end_game(&session_id, &false);
-            game.solved = true;
-            game.ended = true;
-            game.winner = Some(game.player2.clone());
-        } else if game.attempts_used >= game.max_attempts {
-            let game_hub_addr: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::GameHubAddress)
-                .expect("GameHub address not set");
-            let game_hub = GameHubClient::new(&env, &game_hub_addr);
-            game_hub.end_game(&session_id, &true);
-            game.solved = false;
-            game.ended = true;
-            game.winner = Some(game.player1.clone());
+    pub fn claim_timeout(env: Env, session_id: u32) -> Result<(), Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        if game.ended {
+            return Self::claim_reveal_timeout(&env, session_id, game);
+        }
+        game.player2.require_auth();
+        if game.pending_guess_id.is_none() && game.pending_hint_id.is_none() {
+            return Err(Error::NoPendingGuess);
+        }
+        let sequence = env.ledger().sequence();
+        let feedback_expired = game.pending_guess_id.is_some() && sequence > game.feedback_deadline;
+        let hint_expired = game.pending_hint_id.is_some() && sequence > game.hint_deadline;
+        if game.feedback_ledgers == 0 || !(feedback_expired || hint_expired) {
+            return Err(Error::FeedbackNotExpired);
         }
 
+        // The codemaker stalled: the codebreaker wins and takes the bond
+        Self::finish_game(&env, session_id, &mut game, false);
+        let codebreaker = game.player2.clone();
+        Self::pay_bond(&env, &mut game, &codebreaker);
         Self::write_game(&env, session_id, &game);
         Ok(())
     }
 
-    pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
-        Self::load_game(&env, session_id)
+    // The game ended normally but the codemaker never revealed: the held bond goes to
+    // the codebreaker
+    fn claim_reveal_timeout(env: &Env, session_id: u32, mut game: Game) -> Result<(), Error> {
+        if game.bond == 0 || game.revealed_secret.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        game.player2.require_auth();
+        if env.ledger().sequence() <= game.reveal_deadline {
+            return Err(Error::RevealNotExpired);
+        }
+        let codebreaker = game.player2.clone();
+        Self::pay_bond(env, &mut game, &codebreaker);
+        Self::write_game(env, session_id, &game);
+        Ok(())
     }
 
-    pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
-    }
+    // The codemaker can't answer because the verifier is broken, not because of their
+    // proof: restart the window of each pending guess or hint whose verifier is faulty,
+    // so claim_timeout can't take the bond. Duel sessions are handled in `duel`.
+    pub fn report_verifier_fault(env: Env, session_id: u32) -> Result<(), Error> {
+        let mut game = match Self::load_game(&env, session_id) {
+            Err(Error::GameNotFound) => return duel::report_verifier_fault(&env, session_id),
+            result => result?,
+        };
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        game.player1.require_auth();
+        if game.pending_guess_id.is_none() && game.pending_hint_id.is_none() {
+            return Err(Error::NoPendingGuess);
+        }
 
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
-    }
+        let mut cause = None;
+        if game.pending_guess_id.is_some() {
+            if let Some(fault) = game.verifier_backend.probe(&env)? {
+                cause = Some(fault);
+                if game.feedback_ledgers > 0 {
+                    game.feedback_deadline = env.ledger().sequence() + game.feedback_ledgers;
+                }
+            }
+        }
+        if let Some(hint_id) = game.pending_hint_id {
+            let hint = Self::load_hint(&env, session_id, hint_id)?;
+            let config =
+                Self::get_hint_config(env.clone(), hint.kind).ok_or(Error::HintUnavailable)?;
+            if let Some(fault) = probe_verifier(&env, &config.verifier)? {
+                cause.get_or_insert(fault);
+                if game.feedback_ledgers > 0 {
+                    game.hint_deadline = env.ledger().sequence() + game.feedback_ledgers;
+                }
+            }
+        }
+        let cause = cause.ok_or(Error::NoVerifierFault)?;
+        VerifierFaultReported {
+            session_id,
+            cause: cause as u32,
+            feedback_deadline: game.feedback_deadline,
+            hint_deadline: game.hint_deadline,
+        }
+        .publish(&env);
 
-    pub fn get_hub(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set")
+        Self::write_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    pub fn request_hint(
+        env: Env,
+        session_id: u32,
+        kind: HintKind,
+        position: u32,
+        color: u32,
+    ) -> Result<u32, Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.commitment.is_none() {
+            return Err(Error::CommitmentNotSet);
+        }
+        if game.pending_hint_id.is_some() {
+            return Err(Error::HintPending);
+        }
+
+        Self::parse_hint(kind, position, color)?;
+        let config = Self::get_hint_config(env.clone(), kind).ok_or(Error::HintUnavailable)?;
+        // A paid hint locks its price through `add_points`, which needs the hub extensions
+        if config.price > 0 && !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
+        let player2_points = game
+            .player2_points
+            .checked_add(config.price)
+            .ok_or(Error::InvalidConfig)?;
+        // Points first, as for `start_game`, so accounts can cap what a hint costs
+        game.player2.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            config.price.into_val(&env),
+            kind.into_val(&env),
+            position.into_val(&env),
+            color.into_val(&env),
+        ]);
+
+        // The price goes into the session's pot, for whoever wins it
+        if config.price > 0 {
+            let game_hub_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::GameHubAddress)
+                .expect("GameHub address not set");
+            let game_hub = GameHubClient::new(&env, &game_hub_addr);
+            game_hub.add_points(&session_id, &game.player2, &config.price);
+            game.player2_points = player2_points;
+        }
+
+        let hint_id = game.next_hint_id;
+        game.next_hint_id += 1;
+        game.pending_hint_id = Some(hint_id);
+        if game.feedback_ledgers > 0 {
+            game.hint_deadline = env.ledger().sequence() + game.feedback_ledgers;
+        }
+
+        Self::write_hint(
+            &env,
+            session_id,
+            &HintRecord {
+                hint_id,
+                kind,
+                position,
+                color,
+                price: config.price,
+                answer: 0,
+                proof_hash: None,
+            },
+        );
+        Self::write_game(&env, session_id, &game);
+        Ok(hint_id)
+    }
+
+    pub fn answer_hint(
+        env: Env,
+        session_id: u32,
+        hint_id: u32,
+        answer: u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        Self::prove_hint(env, session_id, None, hint_id, answer, proof_blob)
+    }
+
+    // `signer` is the codemaker or their session key
+    pub fn answer_hint_as(
+        env: Env,
+        session_id: u32,
+        signer: Address,
+        hint_id: u32,
+        answer: u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        Self::prove_hint(env, session_id, Some(signer), hint_id, answer, proof_blob)
+    }
+
+    fn prove_hint(
+        env: Env,
+        session_id: u32,
+        signer: Option<Address>,
+        hint_id: u32,
+        answer: u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        Self::authorize_move(&env, session_id, &game.player1, signer, "answer_hint")?;
+
+        let pending_hint_id = game.pending_hint_id.ok_or(Error::NoPendingHint)?;
+        if pending_hint_id != hint_id {
+            return Err(Error::InvalidHint);
+        }
+        let (mut record, expected_public_inputs) =
+            Self::expected_hint_inputs(&env, &game, session_id, hint_id, answer)?;
+        let (public_inputs, proof_bytes) = Self::split_proof_blob(&proof_blob)?;
+        if expected_public_inputs != public_inputs {
+            return Err(Error::InvalidPublicInputs);
+        }
+
+        // Each hint kind has its own circuit, so its own verifier
+        let config =
+            Self::get_hint_config(env.clone(), record.kind).ok_or(Error::HintUnavailable)?;
+        let proof_hash =
+            Self::verify_proof(&env, &config.verifier, &public_inputs, &proof_bytes)?;
+
+        record.answer = answer;
+        record.proof_hash = Some(proof_hash);
+        Self::write_hint(&env, session_id, &record);
+        game.pending_hint_id = None;
+        Self::write_game(&env, session_id, &game);
+        Ok(())
+    }
+
+    pub fn expected_hint_public_inputs(
+        env: Env,
+        session_id: u32,
+        hint_id: u32,
+        answer: u32,
+    ) -> Result<Bytes, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        let (_, public_inputs) =
+            Self::expected_hint_inputs(&env, &game, session_id, hint_id, answer)?;
+        Ok(public_inputs)
+    }
+
+    pub fn get_hint(env: Env, session_id: u32, hint_id: u32) -> Result<HintRecord, Error> {
+        Self::load_game(&env, session_id)?;
+        Self::load_hint(&env, session_id, hint_id)
+    }
+
+    pub fn reveal_secret(
+        env: Env,
+        session_id: u32,
+        secret: BytesN<4>,
+        salt: BytesN<16>,
+    ) -> Result<bool, Error> {
+        let mut game = Self::load_game(&env, session_id)?;
+        if !game.ended {
+            return Err(Error::GameNotEnded);
+        }
+        if game.revealed_secret.is_some() {
+            return Err(Error::AlreadyRevealed);
+        }
+        let commitment = game.commitment.clone().ok_or(Error::CommitmentNotSet)?;
+
+        let code = Self::parse_guess(&secret)?;
+        let opened: BytesN<32> = match game.commitment_scheme {
+            CommitmentScheme::Blake2s => {
+                BytesN::from_array(&env, &code.commitment(&salt.to_array()))
+            }
+            CommitmentScheme::Sha256 => {
+                env.crypto().sha256(&Self::reveal_preimage(&env, &secret, &salt)).into()
+            }
+            CommitmentScheme::Keccak256 => {
+                env.crypto().keccak256(&Self::reveal_preimage(&env, &secret, &salt)).into()
+            }
+        };
+        if opened != commitment {
+            return Err(Error::RevealMismatch);
+        }
+
+        // Every proof-backed feedback and hint must be what the revealed code actually gives
+        let (history, len) = Self::load_history(&env, session_id, game.next_guess_id)?;
+        game.audited = code.is_consistent(&history[..len]);
+        for hint_id in 0..game.next_hint_id {
+            let record = Self::load_hint(&env, session_id, hint_id)?;
+            if record.proof_hash.is_some() {
+                let hint = Self::parse_hint(record.kind, record.position, record.color)?;
+                game.audited &= hint.answer(&code) == record.answer;
+            }
+        }
+        game.revealed_secret = Some(secret);
+
+        // A clean reveal returns the held bond; one that exposes a wrong answer forfeits it
+        let bond_to = if game.audited {
+            game.player1.clone()
+        } else {
+            game.player2.clone()
+        };
+        Self::pay_bond(&env, &mut game, &bond_to);
+        Self::write_game(&env, session_id, &game);
+        Ok(game.audited)
+    }
+
+    pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
+        Self::load_game(&env, session_id)
+    }
+
+    pub fn expected_public_inputs(
+        env: Env,
+        session_id: u32,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+    ) -> Result<Bytes, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        let (_, public_inputs) =
+            Self::expected_inputs(&env, &game, session_id, guess_id, exact, partial)?;
+        Ok(public_inputs)
+    }
+
+    pub fn validate_proof_blob(
+        env: Env,
+        session_id: u32,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        let game = Self::load_game(&env, session_id)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        Self::preflight_proof(&env, &game, session_id, guess_id, exact, partial, &proof_blob)?;
+        Ok(())
+    }
+
+    pub fn audit_game(env: Env, session_id: u32) -> Result<AuditReport, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let game = Self::load_game(&env, session_id)?;
+        let answered = game.pending_guess_id.unwrap_or(game.next_guess_id);
+        let (history, len) = Self::load_history(&env, session_id, answered)?;
+        let history = &history[..len];
+
+        // A code first fails the history at some turn; the history is contradictory from
+        // the latest such turn on, since no code gets past it.
+        let mut consistent_codes = 0;
+        let mut latest_failure = 0;
+        for code in RULES.codes() {
+            match history
+                .iter()
+                .position(|turn| code.score(&turn.guess) != turn.feedback)
+            {
+                Some(failure) => latest_failure = latest_failure.max(failure),
+                None => consistent_codes += 1,
+            }
+        }
+
+        Ok(AuditReport {
+            turns: len as u32,
+            consistent_codes,
+            first_contradiction: if consistent_codes == 0 && len > 0 {
+                Some(latest_failure as u32)
+            } else {
+                None
+            },
+        })
+    }
+
+    // `proof_hash` is keccak256 of the proof bytes alone, without the blob header and
+    // public inputs, so re-encoding a blob doesn't get a proof accepted twice. Groth16
+    // proofs are keyed by their public inputs instead (see `verifier`). Turn and hint
+    // records keep the same hash.
+    pub fn was_proof_used(env: Env, proof_hash: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::UsedProof(proof_hash))
+    }
+
+    pub fn get_turn(env: Env, session_id: u32, guess_id: u32) -> Result<TurnRecord, Error> {
+        Self::load_game(&env, session_id)?;
+        Self::load_turn(&env, session_id, guess_id)
+    }
+
+    pub fn get_turns(
+        env: Env,
+        session_id: u32,
+        from: u32,
+        limit: u32,
+    ) -> Result<Vec<TurnRecord>, Error> {
+        let game = Self::load_game(&env, session_id)?;
+        let end = from
+            .saturating_add(limit.min(MAX_TURNS_PAGE))
+            .min(game.next_guess_id);
+
+        let mut turns = Vec::new(&env);
+        for guess_id in from..end {
+            turns.push_back(Self::load_turn(&env, session_id, guess_id)?);
+        }
+        Ok(turns)
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    pub fn set_admin(env: Env, new_admin: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    }
+
+    pub fn get_hub(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set")
     }
 
     pub fn set_hub(env: Env, new_hub: Address) {
@@ -351,6 +1149,210 @@ impl MyGameContract {
         env.storage().instance().set(&DataKey::VerifierAddress, &verifier);
     }
 
+    pub fn get_verifier_probe(env: Env, verifier: Address) -> Option<Bytes> {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerifierProbe(verifier))
+    }
+
+    // A proof blob `verifier` accepts, for `report_verifier_fault` to check it against
+    pub fn set_verifier_probe(
+        env: Env,
+        verifier: Address,
+        proof_blob: Option<Bytes>,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        let key = DataKey::VerifierProbe(verifier);
+        match proof_blob {
+            Some(proof_blob) => {
+                Self::split_proof_blob(&proof_blob).map_err(|_| Error::InvalidConfig)?;
+                env.storage().instance().set(&key, &proof_blob);
+            }
+            None => env.storage().instance().remove(&key),
+        }
+        Ok(())
+    }
+
+    pub fn get_verifier_backend(env: Env) -> VerifierBackend {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerifierBackend)
+            .unwrap_or(VerifierBackend::UltraHonk)
+    }
+
+    pub fn set_verifier_backend(env: Env, backend: VerifierBackend) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::VerifierBackend, &backend);
+    }
+
+    pub fn get_groth16_vk(env: Env) -> Option<Groth16VerifyingKey> {
+        env.storage().instance().get(&DataKey::Groth16Vk)
+    }
+
+    pub fn set_groth16_vk(env: Env, vk: Option<Groth16VerifyingKey>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        match vk {
+            Some(vk) => {
+                if vk.ic.is_empty() {
+                    return Err(Error::InvalidConfig);
+                }
+                env.storage().instance().set(&DataKey::Groth16Vk, &vk);
+            }
+            None => env.storage().instance().remove(&DataKey::Groth16Vk),
+        }
+        Ok(())
+    }
+
+    pub fn get_batch_verifier(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BatchVerifierAddress)
+    }
+
+    pub fn set_batch_verifier(env: Env, verifier: Option<Address>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        match verifier {
+            Some(verifier) => env
+                .storage()
+                .instance()
+                .set(&DataKey::BatchVerifierAddress, &verifier),
+            None => env.storage().instance().remove(&DataKey::BatchVerifierAddress),
+        }
+    }
+
+    pub fn get_consistency_check(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConsistencyCheck)
+            .unwrap_or(false)
+    }
+
+    pub fn set_consistency_check(env: Env, enabled: bool) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::ConsistencyCheck, &enabled);
+    }
+
+    // Only turn the extensions on for a hub that implements `end_game_multi`; the standard
+    // Game Hub only has `start_game` and `end_game`
+    pub fn get_hub_extensions(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::HubExtensions)
+            .unwrap_or(false)
+    }
+
+    pub fn set_hub_extensions(env: Env, enabled: bool) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::HubExtensions, &enabled);
+    }
+
+    pub fn get_bond_config(env: Env) -> Option<BondConfig> {
+        env.storage().instance().get(&DataKey::BondConfig)
+    }
+
+    pub fn set_bond_config(env: Env, config: Option<BondConfig>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        match config {
+            Some(config) => {
+                if config.amount < 0 || config.feedback_ledgers == 0 {
+                    return Err(Error::InvalidConfig);
+                }
+                env.storage().instance().set(&DataKey::BondConfig, &config);
+            }
+            None => env.storage().instance().remove(&DataKey::BondConfig),
+        }
+        Ok(())
+    }
+
+    pub fn get_queue_depth(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::QueueDepth)
+            .unwrap_or(1)
+    }
+
+    pub fn set_queue_depth(env: Env, depth: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        if depth == 0 || depth > MAX_ATTEMPTS {
+            return Err(Error::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::QueueDepth, &depth);
+        Ok(())
+    }
+
+    pub fn get_hint_config(env: Env, kind: HintKind) -> Option<HintConfig> {
+        env.storage().instance().get(&DataKey::HintConfig(kind))
+    }
+
+    pub fn set_hint_config(
+        env: Env,
+        kind: HintKind,
+        config: Option<HintConfig>,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        match config {
+            Some(config) => {
+                if config.price < 0 {
+                    return Err(Error::InvalidConfig);
+                }
+                env.storage()
+                    .instance()
+                    .set(&DataKey::HintConfig(kind), &config);
+            }
+            None => env.storage().instance().remove(&DataKey::HintConfig(kind)),
+        }
+        Ok(())
+    }
+
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
         let admin: Address = env
             .storage()
@@ -361,6 +1363,105 @@ impl MyGameContract {
         env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
+    // Any bond stays with the contract until `pay_bond`
+    fn finish_game(env: &Env, session_id: u32, game: &mut Game, player1_won: bool) {
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        let game_hub = GameHubClient::new(env, &game_hub_addr);
+        game_hub.end_game(&session_id, &player1_won);
+
+        game.ended = true;
+        game.winner = Some(if player1_won {
+            game.player1.clone()
+        } else {
+            game.player2.clone()
+        });
+    }
+
+    fn pay_bond(env: &Env, game: &mut Game, to: &Address) {
+        if let Some(bond_token) = &game.bond_token {
+            if game.bond > 0 {
+                token::Client::new(env, bond_token).transfer(
+                    &env.current_contract_address(),
+                    to,
+                    &game.bond,
+                );
+                game.bond = 0;
+            }
+        }
+    }
+
+    // Verify and record the feedback for the oldest queued guess
+    fn apply_feedback(
+        env: &Env,
+        session_id: u32,
+        game: &mut Game,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+        proof_blob: &Bytes,
+    ) -> Result<(), Error> {
+        let (turn, public_inputs, proof_bytes) =
+            Self::preflight_proof(env, game, session_id, guess_id, exact, partial, proof_blob)?;
+        let proof_hash = game
+            .verifier_backend
+            .verify(env, &public_inputs, &proof_bytes)?;
+        Self::record_feedback(env, session_id, game, turn, exact, partial, proof_hash)
+    }
+
+    // Record verified feedback, ending the game on a solve or the last attempt. Guesses
+    // still queued behind a solve stay unanswered.
+    fn record_feedback(
+        env: &Env,
+        session_id: u32,
+        game: &mut Game,
+        mut turn: TurnRecord,
+        exact: u32,
+        partial: u32,
+        proof_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let guess_id = turn.guess_id;
+        if Self::get_consistency_check(env.clone()) {
+            let (mut history, len) = Self::load_history(env, session_id, guess_id)?;
+            history[len] = Turn {
+                guess: Self::parse_guess(&turn.guess)?,
+                feedback: Feedback::new(exact, partial).map_err(|_| Error::InvalidFeedback)?,
+            };
+            if !RULES.codes().any(|code| code.is_consistent(&history[..=len])) {
+                return Err(Error::InconsistentFeedback);
+            }
+        }
+
+        turn.exact = exact;
+        turn.partial = partial;
+        turn.proof_hash = Some(proof_hash);
+        Self::write_turn(env, session_id, &turn);
+        game.attempts_used += 1;
+        game.pending_guess_id = None;
+        if guess_id + 1 < game.next_guess_id {
+            game.pending_guess_id = Some(guess_id + 1);
+            if game.feedback_ledgers > 0 {
+                game.feedback_deadline = env.ledger().sequence() + game.feedback_ledgers;
+            }
+        }
+
+        if exact == 4 {
+            game.solved = true;
+            game.pending_guess_id = None;
+            Self::finish_game(env, session_id, game, false);
+        } else if game.attempts_used >= game.max_attempts {
+            Self::finish_game(env, session_id, game, true);
+        }
+        // The codemaker gets the bond back by revealing a code that matches every answer
+        if game.ended && game.bond > 0 {
+            game.reveal_deadline = env.ledger().sequence() + game.feedback_ledgers;
+        }
+        Ok(())
+    }
+
     fn load_game(env: &Env, session_id: u32) -> Result<Game, Error> {
         let game_key = DataKey::Game(session_id);
         env.storage()
@@ -377,48 +1478,217 @@ impl MyGameContract {
             .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
     }
 
-    fn guess_by_id(game: &Game, guess_id: u32) -> Option<BytesN<4>> {
-        let mut i = 0;
-        while i < game.guesses.len() {
-            let record = game.guesses.get(i).unwrap();
-            if record.guess_id == guess_id {
-                return Some(record.guess);
+    fn load_turn(env: &Env, session_id: u32, guess_id: u32) -> Result<TurnRecord, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Turn(session_id, guess_id))
+            .ok_or(Error::InvalidGuessId)
+    }
+
+    fn write_turn(env: &Env, session_id: u32, turn: &TurnRecord) {
+        let turn_key = DataKey::Turn(session_id, turn.guess_id);
+        env.storage().temporary().set(&turn_key, turn);
+        env.storage()
+            .temporary()
+            .extend_ttl(&turn_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
+
+    // Answered turns before `until`, with room for one more turn after them.
+    fn load_history(
+        env: &Env,
+        session_id: u32,
+        until: u32,
+    ) -> Result<([Turn; MAX_ATTEMPTS as usize], usize), Error> {
+        let placeholder = Turn {
+            guess: Code::from_index(&RULES, 0).unwrap(),
+            feedback: Feedback::SOLVED,
+        };
+        let mut history = [placeholder; MAX_ATTEMPTS as usize];
+        let mut len = 0;
+        for guess_id in 0..until {
+            let turn = Self::load_turn(env, session_id, guess_id)?;
+            if turn.proof_hash.is_none() {
+                continue;
             }
-            i += 1;
+            history[len] = Turn {
+                guess: Self::parse_guess(&turn.guess)?,
+                feedback: Feedback::new(turn.exact, turn.partial)
+                    .map_err(|_| Error::InvalidFeedback)?,
+            };
+            len += 1;
         }
-        None
+        Ok((history, len))
     }
 
-    fn validate_guess_digits(guess: &BytesN<4>) -> Result<(), Error> {
-        let d0 = guess.get(0).unwrap_or(0);
-        let d1 = guess.get(1).unwrap_or(0);
-        let d2 = guess.get(2).unwrap_or(0);
-        let d3 = guess.get(3).unwrap_or(0);
+    fn load_hint(env: &Env, session_id: u32, hint_id: u32) -> Result<HintRecord, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Hint(session_id, hint_id))
+            .ok_or(Error::InvalidHint)
+    }
 
-        for d in [d0, d1, d2, d3] {
-            if !(1..=6).contains(&d) {
-                return Err(Error::InvalidGuess);
-            }
+    fn write_hint(env: &Env, session_id: u32, hint: &HintRecord) {
+        let hint_key = DataKey::Hint(session_id, hint.hint_id);
+        env.storage().temporary().set(&hint_key, hint);
+        env.storage()
+            .temporary()
+            .extend_ttl(&hint_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
+
+    fn parse_hint(kind: HintKind, position: u32, color: u32) -> Result<Hint, Error> {
+        let position = u8::try_from(position).map_err(|_| Error::InvalidHint)?;
+        let color = u8::try_from(color).map_err(|_| Error::InvalidHint)?;
+        let hint = match kind {
+            // Unused parameters must be zero so each hint has one encoding
+            HintKind::MostFrequentColor if position == 0 && color == 0 => Hint::MostFrequentColor,
+            HintKind::MostFrequentColor => return Err(Error::InvalidHint),
+            HintKind::PositionIs => Hint::PositionIs { position, color },
+            HintKind::ColorCount if position == 0 => Hint::ColorCount { color },
+            HintKind::ColorCount => return Err(Error::InvalidHint),
+        };
+        hint.validate(&RULES).map_err(|_| Error::InvalidHint)?;
+        Ok(hint)
+    }
+
+    fn expected_hint_inputs(
+        env: &Env,
+        game: &Game,
+        session_id: u32,
+        hint_id: u32,
+        answer: u32,
+    ) -> Result<(HintRecord, Bytes), Error> {
+        let commitment = game.commitment.clone().ok_or(Error::CommitmentNotSet)?;
+        let record = Self::load_hint(env, session_id, hint_id)?;
+        let hint = Self::parse_hint(record.kind, record.position, record.color)?;
+        if !hint.is_possible_answer(&RULES, answer) {
+            return Err(Error::InvalidHint);
         }
-        Ok(())
+
+        let mut public_inputs = Bytes::new(env);
+        Self::append_u32_field(env, &mut public_inputs, session_id);
+        Self::append_u32_field(env, &mut public_inputs, hint_id);
+        public_inputs.append(&commitment.to_bytes());
+        Self::append_u32_field(env, &mut public_inputs, hint.statement_type());
+        Self::append_u32_field(env, &mut public_inputs, record.position);
+        Self::append_u32_field(env, &mut public_inputs, record.color);
+        Self::append_u32_field(env, &mut public_inputs, answer);
+        Ok((record, public_inputs))
     }
 
-    fn build_public_inputs(
+    fn reveal_preimage(env: &Env, secret: &BytesN<4>, salt: &BytesN<16>) -> Bytes {
+        let mut preimage = Bytes::new(env);
+        preimage.append(&secret.to_bytes());
+        preimage.append(&salt.to_bytes());
+        preimage
+    }
+
+    fn parse_guess(guess: &BytesN<4>) -> Result<Code, Error> {
+        Code::new(&RULES, guess.to_array()).map_err(|_| Error::InvalidGuess)
+    }
+
+    fn expected_inputs(
         env: &Env,
+        game: &Game,
         session_id: u32,
         guess_id: u32,
-        commitment: &BytesN<32>,
-        guess: &BytesN<4>,
         exact: u32,
         partial: u32,
+    ) -> Result<(TurnRecord, Bytes), Error> {
+        let commitment = game.commitment.clone().ok_or(Error::CommitmentNotSet)?;
+        let feedback = Feedback::new(exact, partial).map_err(|_| Error::InvalidFeedback)?;
+
+        let turn = Self::load_turn(env, session_id, guess_id)?;
+        let guess = Self::parse_guess(&turn.guess)?;
+        let public_inputs =
+            Self::build_public_inputs(env, session_id, guess_id, &commitment, &guess, &feedback);
+        Ok((turn, public_inputs))
+    }
+
+    fn preflight_proof(
+        env: &Env,
+        game: &Game,
+        session_id: u32,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+        proof_blob: &Bytes,
+    ) -> Result<(TurnRecord, Bytes, Bytes), Error> {
+        let pending_guess_id = game.pending_guess_id.ok_or(Error::NoPendingGuess)?;
+        if pending_guess_id != guess_id {
+            return Err(Error::InvalidGuessId);
+        }
+
+        let (turn, expected_public_inputs) =
+            Self::expected_inputs(env, game, session_id, guess_id, exact, partial)?;
+        let (public_inputs, proof_bytes) = game.verifier_backend.split_proof_blob(proof_blob)?;
+        if expected_public_inputs != public_inputs {
+            return Err(Error::InvalidPublicInputs);
+        }
+        Ok((turn, public_inputs, proof_bytes))
+    }
+
+    // Turns covered by a batch, and the batch circuit's inputs: session_id,
+    // first_guess_id, count, commitment, then guess_packed, exact and partial per slot,
+    // zero past `count`.
+    fn expected_aggregate_inputs(
+        env: &Env,
+        game: &Game,
+        session_id: u32,
+        first_guess_id: u32,
+        feedback: &Vec<FeedbackPair>,
+    ) -> Result<(Vec<TurnRecord>, Bytes), Error> {
+        let count = feedback.len();
+        if count == 0 || count > FEEDBACK_BATCH_SIZE {
+            return Err(Error::InvalidBatch);
+        }
+        let commitment = game.commitment.clone().ok_or(Error::CommitmentNotSet)?;
+
+        let mut turns = Vec::new(env);
+        let mut guesses = Bytes::new(env);
+        let mut exacts = Bytes::new(env);
+        let mut partials = Bytes::new(env);
+        for slot in 0..FEEDBACK_BATCH_SIZE {
+            let Some(pair) = feedback.get(slot) else {
+                Self::append_u32_field(env, &mut guesses, 0);
+                Self::append_u32_field(env, &mut exacts, 0);
+                Self::append_u32_field(env, &mut partials, 0);
+                continue;
+            };
+            let feedback =
+                Feedback::new(pair.exact, pair.partial).map_err(|_| Error::InvalidFeedback)?;
+            let turn = Self::load_turn(env, session_id, first_guess_id + slot)?;
+            guesses.append(&Bytes::from_array(env, &Self::parse_guess(&turn.guess)?.to_field()));
+            Self::append_u32_field(env, &mut exacts, feedback.exact());
+            Self::append_u32_field(env, &mut partials, feedback.partial());
+            turns.push_back(turn);
+        }
+
+        let mut public_inputs = Bytes::new(env);
+        Self::append_u32_field(env, &mut public_inputs, session_id);
+        Self::append_u32_field(env, &mut public_inputs, first_guess_id);
+        Self::append_u32_field(env, &mut public_inputs, count);
+        public_inputs.append(&commitment.to_bytes());
+        public_inputs.append(&guesses);
+        public_inputs.append(&exacts);
+        public_inputs.append(&partials);
+        Ok((turns, public_inputs))
+    }
+
+    fn build_public_inputs(
+        env: &Env,
+        session_id: u32,
+        guess_id: u32,
+        commitment: &BytesN<32>,
+        guess: &Code,
+        feedback: &Feedback,
     ) -> Bytes {
         let mut public_inputs = Bytes::new(env);
         Self::append_u32_field(env, &mut public_inputs, session_id);
         Self::append_u32_field(env, &mut public_inputs, guess_id);
         public_inputs.append(&commitment.to_bytes());
-        Self::append_bytes4_field(env, &mut public_inputs, guess);
-        Self::append_u32_field(env, &mut public_inputs, exact);
-        Self::append_u32_field(env, &mut public_inputs, partial);
+        public_inputs.append(&Bytes::from_array(env, &guess.to_field()));
+        Self::append_u32_field(env, &mut public_inputs, feedback.exact());
+        Self::append_u32_field(env, &mut public_inputs, feedback.partial());
         public_inputs
     }
 
@@ -428,13 +1698,60 @@ impl MyGameContract {
         out.append(&Bytes::from_array(env, &field));
     }
 
-    fn append_bytes4_field(env: &Env, out: &mut Bytes, value: &BytesN<4>) {
-        let mut field = [0u8; 32];
-        field[28] = value.get(0).unwrap();
-        field[29] = value.get(1).unwrap();
-        field[30] = value.get(2).unwrap();
-        field[31] = value.get(3).unwrap();
-        out.append(&Bytes::from_array(env, &field));
+    fn verify_proof(
+        env: &Env,
+        verifier_addr: &Address,
+        public_inputs: &Bytes,
+        proof_bytes: &Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        let proof_hash = Self::check_proof_unused(env, proof_bytes)?;
+        Self::call_verifier(env, verifier_addr, public_inputs, proof_bytes)?;
+        Self::mark_proof_used(env, proof_hash.clone());
+        Ok(proof_hash)
+    }
+
+    fn call_verifier(
+        env: &Env,
+        verifier_addr: &Address,
+        public_inputs: &Bytes,
+        proof_bytes: &Bytes,
+    ) -> Result<(), Error> {
+        let verifier = UltraHonkVerifierClient::new(env, verifier_addr);
+        let error = match verifier.try_verify_proof(public_inputs, proof_bytes) {
+            Ok(Ok(())) => return Ok(()),
+            Err(Ok(VerifierError::VerificationFailed)) => Error::InvalidProof,
+            Err(Ok(VerifierError::ProofParseError)) => Error::MalformedProof,
+            Err(Ok(VerifierError::VkParseError | VerifierError::VkNotSet)) => {
+                Error::VerifierMisconfigured
+            }
+            // Host errors: no contract at the address, a trap, or an unexpected result
+            _ => Error::VerifierUnavailable,
+        };
+        VerifierFailure {
+            verifier: verifier_addr.clone(),
+            cause: error as u32,
+        }
+        .publish(env);
+        Err(error)
+    }
+
+    // Every accepted proof is kept, across sessions and game modes, under keccak256(`key`)
+    fn check_proof_unused(env: &Env, key: &Bytes) -> Result<BytesN<32>, Error> {
+        let proof_hash: BytesN<32> = env.crypto().keccak256(key).into();
+        if Self::was_proof_used(env.clone(), proof_hash.clone()) {
+            return Err(Error::ProofAlreadyUsed);
+        }
+        Ok(proof_hash)
+    }
+
+    fn mark_proof_used(env: &Env, proof_hash: BytesN<32>) {
+        let proof_key = DataKey::UsedProof(proof_hash);
+        env.storage()
+            .persistent()
+            .set(&proof_key, &env.ledger().sequence());
+        env.storage()
+            .persistent()
+            .extend_ttl(&proof_key, PROOF_TTL_LEDGERS, PROOF_TTL_LEDGERS);
     }
 
     fn split_proof_blob(proof_blob: &Bytes) -> Result<(Bytes, Bytes), Error> {
@@ -444,11 +1761,11 @@ impl MyGameContract {
         }
 
         let rest_len = total_len - 4;
-        for proof_fields in [456u32, 440u32, 234u32] {
+        for proof_fields in PROOF_FIELDS {
             let proof_len = proof_fields * 32;
             if rest_len >= proof_len {
                 let pi_len = rest_len - proof_len;
-                if pi_len % 32 == 0 {
+                if pi_len.is_multiple_of(32) {
                     let public_inputs = proof_blob.slice(4..(4 + pi_len));
                     let proof_bytes = proof_blob.slice(4 + pi_len..total_len);
                     return Ok((public_inputs, proof_bytes));
@@ -458,7 +1775,44 @@ impl MyGameContract {
 
         Err(Error::InvalidProofBlob)
     }
+
+    // Split where the caller knows the public inputs length, for layouts whose length can
+    // be mistaken for a bare proof above
+    fn split_proof_blob_at(
+        proof_blob: &Bytes,
+        public_inputs_len: u32,
+    ) -> Result<(Bytes, Bytes), Error> {
+        let total_len = proof_blob.len();
+        if total_len < 4 + public_inputs_len {
+            return Err(Error::InvalidProofBlob);
+        }
+        let proof_len = total_len - 4 - public_inputs_len;
+        if !PROOF_FIELDS.iter().any(|proof_fields| proof_fields * 32 == proof_len) {
+            return Err(Error::InvalidProofBlob);
+        }
+        Ok((
+            proof_blob.slice(4..(4 + public_inputs_len)),
+            proof_blob.slice(4 + public_inputs_len..total_len),
+        ))
+    }
 }
 
+mod duel;
+
+pub use duel::{DuelGame, DuelSide, DuelTiePolicy, DuelTurn};
+
+mod puzzle;
+
+pub use puzzle::{LeaderboardEntry, Puzzle, PuzzleTrack};
+
+mod session_keys;
+
+pub use session_keys::SessionKey;
+
+mod verifier;
+
+pub use verifier::{Groth16VerifyingKey, VerifierBackend};
+use verifier::probe_verifier;
+
 #[cfg(test)]
 mod test;
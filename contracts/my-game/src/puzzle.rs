@@ -0,0 +1,338 @@
+//! Puzzle mode: one committed code, any number of breakers.
+//!
+//! Each breaker plays their own guess track against the puzzle's commitment, and the
+//! codemaker answers every guess with a feedback proof. Puzzles are not hub sessions, so
+//! no points are locked. Solvers are ranked by attempts, then by who solved first.
+//!
+//! Proofs reuse the main circuit. The `session_id` input carries a domain field hashed
+//! from the puzzle id and the breaker's address, so a proof for one breaker's track does
+//! not verify on another's.
+
+use mastermind_core::Feedback;
+use soroban_sdk::{contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+// The contract's client and args types are needed by `#[contractimpl]` in this module
+use crate::{
+    verifier::VerifierBackend, DataKey, Error, MyGameContract, MyGameContractArgs,
+    MyGameContractClient, TurnRecord, GAME_TTL_LEDGERS, MAX_ATTEMPTS,
+};
+
+const LEADERBOARD_SIZE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    pub maker: Address,
+    pub commitment: BytesN<32>,
+    pub max_attempts: u32,
+    pub breakers: u32, // Breakers who have guessed at least once
+    pub solvers: u32,
+    pub verifier_backend: VerifierBackend,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PuzzleTrack {
+    pub next_guess_id: u32,
+    pub pending_guess_id: Option<u32>,
+    pub attempts_used: u32,
+    pub solved_at: u32, // Attempt that cracked the code, 0 if not yet
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaderboardEntry {
+    pub breaker: Address,
+    pub attempts: u32,
+    pub ledger: u32, // Ledger the solving proof landed in
+}
+
+#[contractimpl]
+impl MyGameContract {
+    pub fn create_puzzle(
+        env: Env,
+        puzzle_id: u32,
+        maker: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), Error> {
+        maker.require_auth();
+        let puzzle_key = DataKey::Puzzle(puzzle_id);
+        if env.storage().temporary().has(&puzzle_key) {
+            return Err(Error::CommitmentAlreadySet);
+        }
+
+        let puzzle = Puzzle {
+            maker,
+            commitment,
+            max_attempts: MAX_ATTEMPTS,
+            breakers: 0,
+            solvers: 0,
+            verifier_backend: MyGameContract::get_verifier_backend(env.clone()),
+        };
+        write_puzzle(&env, puzzle_id, &puzzle);
+        Ok(())
+    }
+
+    pub fn submit_puzzle_guess(
+        env: Env,
+        puzzle_id: u32,
+        breaker: Address,
+        guess: BytesN<4>,
+    ) -> Result<u32, Error> {
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
+        if breaker == puzzle.maker {
+            panic!("Cannot break your own puzzle");
+        }
+        breaker.require_auth();
+
+        let mut track = load_track(&env, puzzle_id, &breaker);
+        if track.solved_at > 0 {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if track.attempts_used >= puzzle.max_attempts {
+            return Err(Error::AttemptsExhausted);
+        }
+        if track.pending_guess_id.is_some() {
+            return Err(Error::GuessPendingFeedback);
+        }
+        MyGameContract::parse_guess(&guess)?;
+
+        let guess_id = track.next_guess_id;
+        if guess_id == 0 {
+            puzzle.breakers += 1;
+            write_puzzle(&env, puzzle_id, &puzzle);
+        }
+        track.next_guess_id += 1;
+        track.pending_guess_id = Some(guess_id);
+
+        write_puzzle_turn(
+            &env,
+            puzzle_id,
+            &breaker,
+            &TurnRecord {
+                guess_id,
+                guess,
+                exact: 0,
+                partial: 0,
+                proof_hash: None,
+            },
+        );
+        write_track(&env, puzzle_id, &breaker, &track);
+        Ok(guess_id)
+    }
+
+    pub fn submit_puzzle_feedback(
+        env: Env,
+        puzzle_id: u32,
+        breaker: Address,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+        proof_blob: Bytes,
+    ) -> Result<(), Error> {
+        let mut puzzle = load_puzzle(&env, puzzle_id)?;
+        puzzle.maker.require_auth();
+
+        let mut track = load_track(&env, puzzle_id, &breaker);
+        let pending_guess_id = track.pending_guess_id.ok_or(Error::NoPendingGuess)?;
+        if pending_guess_id != guess_id {
+            return Err(Error::InvalidGuessId);
+        }
+
+        let (mut turn, expected_public_inputs) =
+            expected_inputs(&env, &puzzle, puzzle_id, &breaker, guess_id, exact, partial)?;
+        let (public_inputs, proof_bytes) = puzzle.verifier_backend.split_proof_blob(&proof_blob)?;
+        if expected_public_inputs != public_inputs {
+            return Err(Error::InvalidPublicInputs);
+        }
+        let proof_hash = puzzle
+            .verifier_backend
+            .verify(&env, &public_inputs, &proof_bytes)?;
+
+        turn.exact = exact;
+        turn.partial = partial;
+        turn.proof_hash = Some(proof_hash);
+        write_puzzle_turn(&env, puzzle_id, &breaker, &turn);
+
+        track.pending_guess_id = None;
+        track.attempts_used += 1;
+        if exact == 4 {
+            track.solved_at = track.attempts_used;
+            puzzle.solvers += 1;
+            write_puzzle(&env, puzzle_id, &puzzle);
+            record_solve(&env, puzzle_id, breaker.clone(), track.solved_at);
+        }
+
+        write_track(&env, puzzle_id, &breaker, &track);
+        Ok(())
+    }
+
+    pub fn expected_puzzle_public_inputs(
+        env: Env,
+        puzzle_id: u32,
+        breaker: Address,
+        guess_id: u32,
+        exact: u32,
+        partial: u32,
+    ) -> Result<Bytes, Error> {
+        let puzzle = load_puzzle(&env, puzzle_id)?;
+        let (_, public_inputs) =
+            expected_inputs(&env, &puzzle, puzzle_id, &breaker, guess_id, exact, partial)?;
+        Ok(public_inputs)
+    }
+
+    pub fn get_puzzle(env: Env, puzzle_id: u32) -> Result<Puzzle, Error> {
+        load_puzzle(&env, puzzle_id)
+    }
+
+    pub fn get_puzzle_track(
+        env: Env,
+        puzzle_id: u32,
+        breaker: Address,
+    ) -> Result<PuzzleTrack, Error> {
+        load_puzzle(&env, puzzle_id)?;
+        Ok(load_track(&env, puzzle_id, &breaker))
+    }
+
+    pub fn get_puzzle_turn(
+        env: Env,
+        puzzle_id: u32,
+        breaker: Address,
+        guess_id: u32,
+    ) -> Result<TurnRecord, Error> {
+        load_puzzle(&env, puzzle_id)?;
+        load_puzzle_turn(&env, puzzle_id, &breaker, guess_id)
+    }
+
+    pub fn get_leaderboard(env: Env, puzzle_id: u32) -> Result<Vec<LeaderboardEntry>, Error> {
+        load_puzzle(&env, puzzle_id)?;
+        Ok(env
+            .storage()
+            .temporary()
+            .get(&DataKey::Leaderboard(puzzle_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+}
+
+// Field standing in for `session_id`: be31(sha256(puzzle_id || breaker xdr))
+fn puzzle_domain(env: &Env, puzzle_id: u32, breaker: &Address) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &puzzle_id.to_be_bytes());
+    preimage.append(&breaker.clone().to_xdr(env));
+    let digest = env.crypto().sha256(&preimage).to_array();
+
+    let mut field = [0u8; 32];
+    field[1..].copy_from_slice(&digest[..31]);
+    BytesN::from_array(env, &field)
+}
+
+fn expected_inputs(
+    env: &Env,
+    puzzle: &Puzzle,
+    puzzle_id: u32,
+    breaker: &Address,
+    guess_id: u32,
+    exact: u32,
+    partial: u32,
+) -> Result<(TurnRecord, Bytes), Error> {
+    let feedback = Feedback::new(exact, partial).map_err(|_| Error::InvalidFeedback)?;
+    let turn = load_puzzle_turn(env, puzzle_id, breaker, guess_id)?;
+    let guess = MyGameContract::parse_guess(&turn.guess)?;
+
+    let mut public_inputs = Bytes::from(puzzle_domain(env, puzzle_id, breaker));
+    MyGameContract::append_u32_field(env, &mut public_inputs, guess_id);
+    public_inputs.append(&puzzle.commitment.to_bytes());
+    public_inputs.append(&Bytes::from_array(env, &guess.to_field()));
+    MyGameContract::append_u32_field(env, &mut public_inputs, feedback.exact());
+    MyGameContract::append_u32_field(env, &mut public_inputs, feedback.partial());
+    Ok((turn, public_inputs))
+}
+
+// Insert after every entry with the same or fewer attempts, so earlier solves rank first
+fn record_solve(env: &Env, puzzle_id: u32, breaker: Address, attempts: u32) {
+    let board_key = DataKey::Leaderboard(puzzle_id);
+    let mut board: Vec<LeaderboardEntry> = env
+        .storage()
+        .temporary()
+        .get(&board_key)
+        .unwrap_or(Vec::new(env));
+
+    let rank = board
+        .iter()
+        .position(|entry| entry.attempts > attempts)
+        .map(|rank| rank as u32)
+        .unwrap_or(board.len());
+    if rank >= LEADERBOARD_SIZE {
+        return;
+    }
+    board.insert(
+        rank,
+        LeaderboardEntry {
+            breaker,
+            attempts,
+            ledger: env.ledger().sequence(),
+        },
+    );
+    if board.len() > LEADERBOARD_SIZE {
+        board.pop_back();
+    }
+
+    env.storage().temporary().set(&board_key, &board);
+    env.storage()
+        .temporary()
+        .extend_ttl(&board_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+}
+
+fn load_puzzle(env: &Env, puzzle_id: u32) -> Result<Puzzle, Error> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::Puzzle(puzzle_id))
+        .ok_or(Error::GameNotFound)
+}
+
+fn write_puzzle(env: &Env, puzzle_id: u32, puzzle: &Puzzle) {
+    let puzzle_key = DataKey::Puzzle(puzzle_id);
+    env.storage().temporary().set(&puzzle_key, puzzle);
+    env.storage()
+        .temporary()
+        .extend_ttl(&puzzle_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+}
+
+fn load_track(env: &Env, puzzle_id: u32, breaker: &Address) -> PuzzleTrack {
+    env.storage()
+        .temporary()
+        .get(&DataKey::PuzzleTrack(puzzle_id, breaker.clone()))
+        .unwrap_or(PuzzleTrack {
+            next_guess_id: 0,
+            pending_guess_id: None,
+            attempts_used: 0,
+            solved_at: 0,
+        })
+}
+
+fn write_track(env: &Env, puzzle_id: u32, breaker: &Address, track: &PuzzleTrack) {
+    let track_key = DataKey::PuzzleTrack(puzzle_id, breaker.clone());
+    env.storage().temporary().set(&track_key, track);
+    env.storage()
+        .temporary()
+        .extend_ttl(&track_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+}
+
+fn load_puzzle_turn(
+    env: &Env,
+    puzzle_id: u32,
+    breaker: &Address,
+    guess_id: u32,
+) -> Result<TurnRecord, Error> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::PuzzleTurn(puzzle_id, breaker.clone(), guess_id))
+        .ok_or(Error::InvalidGuessId)
+}
+
+fn write_puzzle_turn(env: &Env, puzzle_id: u32, breaker: &Address, turn: &TurnRecord) {
+    let turn_key = DataKey::PuzzleTurn(puzzle_id, breaker.clone(), turn.guess_id);
+    env.storage().temporary().set(&turn_key, turn);
+    env.storage()
+        .temporary()
+        .extend_ttl(&turn_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+}
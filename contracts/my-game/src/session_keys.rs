@@ -0,0 +1,176 @@
+//! Session keys: a player delegates their moves in one session to another address.
+//!
+//! A key is scoped to a session, a set of move functions and an expiry ledger. The move
+//! functions are `submit_guess` for the codebreaker, and `submit_feedback_proof`,
+//! `submit_feedback_batch`, `submit_feedback_aggregate` and `answer_hint` for the
+//! codemaker. Their `_as` variants take the signer explicitly, so either the player or
+//! their live key can authorize the call. The player can revoke the key at any time.
+//!
+//! A key can be registered when the game starts (`start_game_with_session_keys`), under
+//! the auth the player already gives for their points, or later with
+//! `register_session_key`.
+
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+// The contract's client and args types are needed by `#[contractimpl]` in this module
+use crate::{
+    DataKey, Error, Game, MyGameContract, MyGameContractArgs, MyGameContractClient,
+    GAME_TTL_LEDGERS,
+};
+
+const DELEGABLE_FUNCTIONS: [&str; 5] = [
+    "submit_guess",
+    "submit_feedback_proof",
+    "submit_feedback_batch",
+    "submit_feedback_aggregate",
+    "answer_hint",
+];
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKey {
+    pub key: Address,
+    pub functions: Vec<Symbol>,
+    pub expires_at: u32, // Last ledger the key is valid in
+}
+
+#[contractimpl]
+impl MyGameContract {
+    /// Delegate `player`'s moves in a session to `key`
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session
+    /// * `player` - Player delegating their moves
+    /// * `key` - Address allowed to sign the delegated moves
+    /// * `functions` - Move functions the key may call, by name
+    /// * `expires_at` - Last ledger the key is valid in
+    pub fn register_session_key(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        key: Address,
+        functions: Vec<Symbol>,
+        expires_at: u32,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        let game = MyGameContract::load_game(&env, session_id)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        MyGameContract::set_session_key(
+            &env,
+            session_id,
+            &game,
+            &player,
+            SessionKey {
+                key,
+                functions,
+                expires_at,
+            },
+        )
+    }
+
+    /// Remove `player`'s session key before it expires
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session
+    /// * `player` - Player whose key is revoked
+    pub fn revoke_session_key(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        let game = MyGameContract::load_game(&env, session_id)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if !is_player(&game, &player) {
+            return Err(Error::NotPlayer);
+        }
+        env.storage()
+            .temporary()
+            .remove(&DataKey::SessionKey(session_id, player));
+        Ok(())
+    }
+
+    /// Get `player`'s session key, expired or not
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session
+    /// * `player` - Player who registered the key
+    pub fn get_session_key(env: Env, session_id: u32, player: Address) -> Option<SessionKey> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::SessionKey(session_id, player))
+    }
+}
+
+impl MyGameContract {
+    pub(crate) fn set_session_key(
+        env: &Env,
+        session_id: u32,
+        game: &Game,
+        player: &Address,
+        session_key: SessionKey,
+    ) -> Result<(), Error> {
+        if !is_player(game, player) {
+            return Err(Error::NotPlayer);
+        }
+        if session_key.key == *player
+            || session_key.expires_at <= env.ledger().sequence()
+            || session_key.functions.is_empty()
+        {
+            return Err(Error::InvalidConfig);
+        }
+        for function in session_key.functions.iter() {
+            if !DELEGABLE_FUNCTIONS
+                .iter()
+                .any(|name| function == Symbol::new(env, name))
+            {
+                return Err(Error::InvalidConfig);
+            }
+        }
+
+        let key_slot = DataKey::SessionKey(session_id, player.clone());
+        env.storage().temporary().set(&key_slot, &session_key);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key_slot, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        Ok(())
+    }
+
+    // No signer, or the player themselves, falls back to the player's own auth
+    pub(crate) fn authorize_move(
+        env: &Env,
+        session_id: u32,
+        player: &Address,
+        signer: Option<Address>,
+        function: &str,
+    ) -> Result<(), Error> {
+        let signer = match signer {
+            Some(signer) if signer != *player => signer,
+            _ => {
+                player.require_auth();
+                return Ok(());
+            }
+        };
+
+        let session_key: SessionKey = env
+            .storage()
+            .temporary()
+            .get(&DataKey::SessionKey(session_id, player.clone()))
+            .ok_or(Error::SessionKeyInvalid)?;
+        if session_key.key != signer {
+            return Err(Error::SessionKeyInvalid);
+        }
+        if env.ledger().sequence() > session_key.expires_at {
+            return Err(Error::SessionKeyExpired);
+        }
+        if !session_key.functions.contains(Symbol::new(env, function)) {
+            return Err(Error::SessionKeyInvalid);
+        }
+        signer.require_auth();
+        Ok(())
+    }
+}
+
+fn is_player(game: &Game, player: &Address) -> bool {
+    game.player1 == *player || game.player2 == *player
+}
@@ -1,8 +1,24 @@
 #![cfg(test)]
 
-use crate::{Error, MyGameContract, MyGameContractClient, VerifierError};
-use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, BytesN, Env};
+extern crate std;
+
+use crate::{
+    BondConfig, CommitmentScheme, DataKey, DuelTiePolicy, Error, FeedbackAnswer, FeedbackPair,
+    Groth16VerifyingKey, HintConfig, HintKind, MyGameContract, MyGameContractClient, SessionKey,
+    TurnRecord, VerifierBackend, VerifierError, VerifierFailure, VerifierFaultReported,
+    PROOF_TTL_LEDGERS,
+};
+use mastermind_core::{Code, Feedback, Rules, Turn};
+use mastermind_solver::{Report, Solver, Strategy};
+use soroban_sdk::crypto::bn254::{Bn254G1Affine, Fr};
+use soroban_sdk::testutils::{
+    storage::Persistent as _, Address as _, AuthorizedFunction, Events as _, Ledger as _,
+};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, Event, IntoVal, Symbol,
+    Vec, U256,
+};
 
 #[contract]
 pub struct MockGameHub;
@@ -12,6 +28,8 @@ pub struct MockGameHub;
 pub enum HubDataKey {
     EndCount(u32),
     LastOutcome(u32),
+    AddedPoints(u32),
+    LastWinners(u32),
 }
 
 #[contractimpl]
@@ -36,8 +54,36 @@ impl MockGameHub {
             .set(&HubDataKey::LastOutcome(session_id), &player1_won);
     }
 
+    pub fn add_points(env: Env, session_id: u32, _player: Address, points: i128) {
+        let key = HubDataKey::AddedPoints(session_id);
+        let added: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(added + points));
+    }
+
+    pub fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>) {
+        let count_key = HubDataKey::EndCount(session_id);
+        let count: u32 = env.storage().instance().get(&count_key).unwrap_or(0);
+        env.storage().instance().set(&count_key, &(count + 1));
+        env.storage()
+            .instance()
+            .set(&HubDataKey::LastWinners(session_id), &winners);
+    }
+
     pub fn add_game(_env: Env, _game_address: Address) {}
 
+    pub fn get_last_winners(env: Env, session_id: u32) -> Option<Vec<Address>> {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::LastWinners(session_id))
+    }
+
+    pub fn get_added_points(env: Env, session_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::AddedPoints(session_id))
+            .unwrap_or(0)
+    }
+
     pub fn get_end_count(env: Env, session_id: u32) -> u32 {
         env.storage()
             .instance()
@@ -46,7 +92,35 @@ impl MockGameHub {
     }
 
     pub fn get_last_outcome(env: Env, session_id: u32) -> Option<bool> {
-        env.storage().instance().get(&HubDataKey::LastOutcome(session_id))
+        env.storage()
+            .instance()
+            .get(&HubDataKey::LastOutcome(session_id))
+    }
+}
+
+// Only the standard Game Hub entry points, without `end_game_multi`
+#[contract]
+pub struct StandardGameHub;
+
+#[contractimpl]
+impl StandardGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        env.storage().instance().set(&session_id, &player1_won);
+    }
+
+    pub fn get_player1_won(env: Env, session_id: u32) -> Option<bool> {
+        env.storage().instance().get(&session_id)
     }
 }
 
@@ -60,7 +134,7 @@ impl MockUltraHonkVerifier {
         public_inputs: Bytes,
         proof_bytes: Bytes,
     ) -> Result<(), VerifierError> {
-        if public_inputs.len() == 0 {
+        if public_inputs.is_empty() {
             return Err(VerifierError::ProofParseError);
         }
         let len = proof_bytes.len();
@@ -75,6 +149,45 @@ impl MockUltraHonkVerifier {
     }
 }
 
+// Fails every call with the stored cause; 0 traps like a host error
+#[contract]
+pub struct MockFaultyVerifier;
+
+#[contractimpl]
+impl MockFaultyVerifier {
+    pub fn set_cause(env: Env, cause: u32) {
+        env.storage().instance().set(&0u32, &cause);
+    }
+
+    pub fn verify_proof(
+        env: Env,
+        _public_inputs: Bytes,
+        _proof_bytes: Bytes,
+    ) -> Result<(), VerifierError> {
+        match env.storage().instance().get(&0u32).unwrap_or(0u32) {
+            1 => Err(VerifierError::VkParseError),
+            2 => Err(VerifierError::ProofParseError),
+            3 => Err(VerifierError::VerificationFailed),
+            4 => Err(VerifierError::VkNotSet),
+            _ => panic!("verifier trapped"),
+        }
+    }
+}
+
+#[contract]
+pub struct MockRejectingVerifier;
+
+#[contractimpl]
+impl MockRejectingVerifier {
+    pub fn verify_proof(
+        _env: Env,
+        _public_inputs: Bytes,
+        _proof_bytes: Bytes,
+    ) -> Result<(), VerifierError> {
+        Err(VerifierError::VerificationFailed)
+    }
+}
+
 fn setup_test() -> (
     Env,
     MyGameContractClient<'static>,
@@ -107,6 +220,9 @@ fn setup_test() -> (
 
     game_hub.add_game(&contract_id);
 
+    // The mock implements `end_game_multi`
+    client.set_hub_extensions(&true);
+
     let player1 = Address::generate(&env);
     let player2 = Address::generate(&env);
 
@@ -183,7 +299,9 @@ fn append_guess_field(env: &Env, out: &mut Bytes, guess: &BytesN<4>) {
 }
 
 fn commitment_from_4bytes(env: &Env, seed: [u8; 4]) -> BytesN<32> {
-    env.crypto().keccak256(&Bytes::from_array(env, &seed)).into()
+    env.crypto()
+        .keccak256(&Bytes::from_array(env, &seed))
+        .into()
 }
 
 fn build_proof_blob(env: &Env, public_inputs: &Bytes, valid: bool) -> Bytes {
@@ -195,7 +313,9 @@ fn build_proof_blob(env: &Env, public_inputs: &Bytes, valid: bool) -> Bytes {
     blob.append(&Bytes::from_array(env, &total_fields.to_be_bytes()));
     blob.append(public_inputs);
 
-    let mut i = 0u32;
+    // Like a real proof, the bytes depend on the public inputs
+    blob.append(&env.crypto().keccak256(public_inputs).into());
+    let mut i = 32u32;
     let proof_bytes = proof_fields * 32;
     while i < proof_fields * 32 {
         let is_last = i == proof_bytes - 1;
@@ -265,7 +385,8 @@ fn test_invalid_public_inputs_rejected() {
     client.commit_code(&session_id, &commitment);
     let guess_id = client.submit_guess(&session_id, &guess);
 
-    let wrong_public_inputs = build_public_inputs(&env, session_id, guess_id, &commitment, &guess, 2, 1);
+    let wrong_public_inputs =
+        build_public_inputs(&env, session_id, guess_id, &commitment, &guess, 2, 1);
     let proof_blob = build_proof_blob(&env, &wrong_public_inputs, true);
     let result = client.try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
     assert_game_error(&result, Error::InvalidPublicInputs);
@@ -333,7 +454,8 @@ fn test_attempt_cap_player1_wins_on_twelfth_feedback() {
         let guess = BytesN::<4>::from_array(&env, raw_guess);
         let guess_id = client.submit_guess(&session_id, &guess);
         assert_eq!(guess_id, idx as u32);
-        let public_inputs = build_public_inputs(&env, session_id, guess_id, &commitment, &guess, 1, 1);
+        let public_inputs =
+            build_public_inputs(&env, session_id, guess_id, &commitment, &guess, 1, 1);
         let proof_blob = build_proof_blob(&env, &public_inputs, true);
         client.submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
     }
@@ -359,17 +481,11 @@ fn test_reject_wrong_guess_id() {
     let guess_id = client.submit_guess(&session_id, &guess);
 
     let wrong_guess_id = guess_id + 1;
-    let public_inputs = build_public_inputs(
-        &env,
-        session_id,
-        wrong_guess_id,
-        &commitment,
-        &guess,
-        2,
-        1,
-    );
+    let public_inputs =
+        build_public_inputs(&env, session_id, wrong_guess_id, &commitment, &guess, 2, 1);
     let proof_blob = build_proof_blob(&env, &public_inputs, true);
-    let result = client.try_submit_feedback_proof(&session_id, &wrong_guess_id, &2, &1, &proof_blob);
+    let result =
+        client.try_submit_feedback_proof(&session_id, &wrong_guess_id, &2, &1, &proof_blob);
     assert_game_error(&result, Error::InvalidGuessId);
 }
 
@@ -404,6 +520,3029 @@ fn test_cannot_commit_twice() {
     assert_game_error(&result, Error::CommitmentAlreadySet);
 }
 
+#[test]
+fn test_turns_stored_per_guess() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 9u32;
+    let commitment = commitment_from_4bytes(&env, [6, 5, 4, 3]);
+    let guess = BytesN::<4>::from_array(&env, &[1, 2, 3, 4]);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment);
+    let guess_id = client.submit_guess(&session_id, &guess);
+
+    let turn = client.get_turn(&session_id, &guess_id);
+    assert_eq!(turn.guess, guess);
+    assert_eq!(turn.proof_hash, None);
+
+    let public_inputs = build_public_inputs(&env, session_id, guess_id, &commitment, &guess, 1, 2);
+    let proof_blob = build_proof_blob(&env, &public_inputs, true);
+    client.submit_feedback_proof(&session_id, &guess_id, &1, &2, &proof_blob);
+
+    let turn = client.get_turn(&session_id, &guess_id);
+    assert_eq!((turn.exact, turn.partial), (1, 2));
+    assert_eq!(
+        turn.proof_hash,
+        Some(BytesN::from(
+            env.crypto()
+                .keccak256(&proof_part(&proof_blob, &public_inputs))
+        ))
+    );
+
+    let result = client.try_get_turn(&session_id, &(guess_id + 1));
+    assert_game_error(&result, Error::InvalidGuessId);
+    let result = client.try_get_turn(&99, &0);
+    assert_game_error(&result, Error::GameNotFound);
+}
+
+#[test]
+fn test_get_turns_pages_through_board() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 10u32;
+    let commitment = commitment_from_4bytes(&env, [6, 6, 6, 6]);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment);
+    for digit in 1..=5u8 {
+        let guess = BytesN::<4>::from_array(&env, &[digit, 1, 1, 1]);
+        let guess_id = client.submit_guess(&session_id, &guess);
+        let public_inputs =
+            build_public_inputs(&env, session_id, guess_id, &commitment, &guess, 0, 0);
+        let proof_blob = build_proof_blob(&env, &public_inputs, true);
+        client.submit_feedback_proof(&session_id, &guess_id, &0, &0, &proof_blob);
+    }
+
+    let page = client.get_turns(&session_id, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(1).unwrap().guess_id, 1);
+
+    let page = client.get_turns(&session_id, &3, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(
+        page.get(0).unwrap().guess,
+        BytesN::from_array(&env, &[4, 1, 1, 1])
+    );
+    assert!(page.iter().all(|turn| turn.proof_hash.is_some()));
+
+    assert!(client.get_turns(&session_id, &5, &10).is_empty());
+    assert!(client.get_turns(&session_id, &0, &0).is_empty());
+}
+
+#[test]
+fn test_expected_public_inputs_match_prover_encoding() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 11u32;
+    let commitment = commitment_from_4bytes(&env, [2, 4, 6, 1]);
+    let guess = BytesN::<4>::from_array(&env, &[2, 4, 1, 6]);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment);
+    let guess_id = client.submit_guess(&session_id, &guess);
+
+    let expected = build_public_inputs(&env, session_id, guess_id, &commitment, &guess, 2, 2);
+    assert_eq!(
+        client.expected_public_inputs(&session_id, &guess_id, &2, &2),
+        expected
+    );
+
+    let result = client.try_expected_public_inputs(&session_id, &guess_id, &3, &2);
+    assert_game_error(&result, Error::InvalidFeedback);
+    let result = client.try_expected_public_inputs(&session_id, &(guess_id + 1), &2, &2);
+    assert_game_error(&result, Error::InvalidGuessId);
+}
+
+#[test]
+fn test_validate_proof_blob_preflights_without_verifier() {
+    let (env, client, player1, player2) = setup_test_without_verifier();
+    let session_id = 12u32;
+    let commitment = commitment_from_4bytes(&env, [3, 3, 1, 1]);
+    let guess = BytesN::<4>::from_array(&env, &[1, 1, 3, 3]);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment);
+
+    let public_inputs = build_public_inputs(&env, session_id, 0, &commitment, &guess, 0, 4);
+    let proof_blob = build_proof_blob(&env, &public_inputs, true);
+    let result = client.try_validate_proof_blob(&session_id, &0, &0, &4, &proof_blob);
+    assert_game_error(&result, Error::NoPendingGuess);
+
+    let guess_id = client.submit_guess(&session_id, &guess);
+    client.validate_proof_blob(&session_id, &guess_id, &0, &4, &proof_blob);
+
+    let result = client.try_validate_proof_blob(&session_id, &guess_id, &1, &3, &proof_blob);
+    assert_game_error(&result, Error::InvalidPublicInputs);
+    let result = client.try_validate_proof_blob(&session_id, &guess_id, &0, &4, &public_inputs);
+    assert_game_error(&result, Error::InvalidProofBlob);
+
+    // The real submission still needs a verifier.
+    let result = client.try_submit_feedback_proof(&session_id, &guess_id, &0, &4, &proof_blob);
+    assert_game_error(&result, Error::VerifierNotSet);
+}
+
+#[test]
+fn test_scored_game_matches_core_rules() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 13u32;
+    let secret = Code::new(&Rules::CLASSIC, [3, 6, 2, 6]).unwrap();
+    let commitment = commitment_from_4bytes(&env, secret.pegs());
+    let guesses = [
+        [1, 1, 2, 2],
+        [3, 3, 4, 4],
+        [5, 5, 6, 6],
+        [3, 6, 1, 5],
+        [3, 6, 2, 6],
+    ];
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment);
+    for raw_guess in guesses {
+        let code = Code::new(&Rules::CLASSIC, raw_guess).unwrap();
+        let feedback = secret.score(&code);
+        let guess = BytesN::<4>::from_array(&env, &raw_guess);
+        let guess_id = client.submit_guess(&session_id, &guess);
+
+        let result = client.try_expected_public_inputs(&session_id, &guess_id, &3, &1);
+        assert_game_error(&result, Error::InvalidFeedback);
+
+        let public_inputs = client.expected_public_inputs(
+            &session_id,
+            &guess_id,
+            &feedback.exact(),
+            &feedback.partial(),
+        );
+        let proof_blob = build_proof_blob(&env, &public_inputs, true);
+        client.submit_feedback_proof(
+            &session_id,
+            &guess_id,
+            &feedback.exact(),
+            &feedback.partial(),
+            &proof_blob,
+        );
+    }
+
+    let game = client.get_game(&session_id);
+    assert!(game.solved);
+    assert_eq!(game.attempts_used, 5);
+    assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+}
+
+fn turn_from_record(record: &TurnRecord) -> Turn {
+    Turn {
+        guess: Code::new(&Rules::CLASSIC, record.guess.to_array()).unwrap(),
+        feedback: Feedback::new(record.exact, record.partial).unwrap(),
+    }
+}
+
+// Plays a whole session on-chain, the solver as codebreaker and `secret` as the code.
+// Returns the attempts used if the solver cracked the code.
+fn play_solver(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    solver: &Solver,
+    secret: &Code,
+) -> Option<u32> {
+    client.start_game(&session_id, player1, player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(env, secret.pegs()));
+
+    let mut history = std::vec::Vec::new();
+    loop {
+        let guess = solver.next_guess(&history).unwrap();
+        let guess_id = client.submit_guess(&session_id, &BytesN::from_array(env, &guess.pegs()));
+        let feedback = secret.score(&guess);
+        let public_inputs = client.expected_public_inputs(
+            &session_id,
+            &guess_id,
+            &feedback.exact(),
+            &feedback.partial(),
+        );
+        let proof_blob = build_proof_blob(env, &public_inputs, true);
+        client.submit_feedback_proof(
+            &session_id,
+            &guess_id,
+            &feedback.exact(),
+            &feedback.partial(),
+            &proof_blob,
+        );
+
+        let game = client.get_game(&session_id);
+        if game.ended {
+            return if game.solved {
+                Some(game.attempts_used)
+            } else {
+                None
+            };
+        }
+        history = client
+            .get_turns(&session_id, &0, &game.next_guess_id)
+            .iter()
+            .map(|record| turn_from_record(&record))
+            .collect();
+    }
+}
+
+#[test]
+fn test_solver_bot_cracks_codes_on_chain() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let solver = Solver::new(Rules::CLASSIC, Strategy::Knuth);
+    let secrets = [[1, 1, 1, 1], [6, 5, 4, 3], [2, 6, 2, 5]];
+
+    for (session_id, pegs) in secrets.iter().enumerate() {
+        let session_id = 100 + session_id as u32;
+        let secret = Code::new(&Rules::CLASSIC, *pegs).unwrap();
+        let attempts = play_solver(
+            &env, &client, session_id, &player1, &player2, &solver, &secret,
+        );
+        assert!(attempts.unwrap() <= 5);
+        assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+    }
+}
+
+// Plays every code of the classic rules on-chain with both strategies and reports the
+// worst-case and average attempts. Takes several minutes; run with `cargo test --release -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn test_solver_harness_all_codes() {
+    for strategy in [Strategy::Knuth, Strategy::Entropy] {
+        let solver = Solver::new(Rules::CLASSIC, strategy);
+        let mut report = Report::default();
+        for secret in Rules::CLASSIC.codes() {
+            // A fresh environment per game keeps memory flat over the whole run.
+            let (env, client, _hub, player1, player2) = setup_test();
+            report.record(play_solver(
+                &env, &client, 1, &player1, &player2, &solver, &secret,
+            ));
+        }
+
+        std::println!(
+            "{:?}: {}/{} solved, worst {} attempts, average {:.4}",
+            strategy,
+            report.solved,
+            report.games,
+            report.worst_attempts,
+            report.average_attempts()
+        );
+        assert_eq!(report.solved, report.games);
+        assert!(report.worst_attempts <= crate::MAX_ATTEMPTS);
+    }
+}
+
+fn answer(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    raw_guess: [u8; 4],
+    exact: u32,
+    partial: u32,
+) -> Result<(), Error> {
+    let guess_id = client.submit_guess(&session_id, &BytesN::from_array(env, &raw_guess));
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &exact, &partial);
+    let proof_blob = build_proof_blob(env, &public_inputs, true);
+    match client.try_submit_feedback_proof(&session_id, &guess_id, &exact, &partial, &proof_blob) {
+        Ok(Ok(())) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("unexpected submit_feedback_proof result"),
+    }
+}
+
+#[test]
+fn test_consistency_check_rejects_contradictory_feedback() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 14u32;
+    assert!(!client.get_consistency_check());
+    client.set_consistency_check(&true);
+    assert!(client.get_consistency_check());
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    assert_eq!(
+        answer(&env, &client, session_id, [1, 2, 5, 5], 2, 0),
+        Ok(())
+    );
+
+    // No code scores 1122 with 2 exact and also 1156 with nothing.
+    assert_eq!(
+        answer(&env, &client, session_id, [1, 2, 5, 6], 0, 0),
+        Err(Error::InconsistentFeedback)
+    );
+    let game = client.get_game(&session_id);
+    assert_eq!(game.attempts_used, 1);
+    assert_eq!(game.pending_guess_id, Some(1));
+    assert_eq!(client.get_turn(&session_id, &1).proof_hash, None);
+}
+
+#[test]
+fn test_audit_game_reports_contradiction() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 15u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    let report = client.audit_game(&session_id);
+    assert_eq!(report.turns, 0);
+    assert_eq!(report.consistent_codes, 1296);
+    assert_eq!(report.first_contradiction, None);
+
+    answer(&env, &client, session_id, [1, 2, 5, 5], 2, 0).unwrap();
+    answer(&env, &client, session_id, [1, 3, 6, 6], 1, 0).unwrap();
+    let report = client.audit_game(&session_id);
+    assert_eq!(report.turns, 2);
+    assert!(report.consistent_codes > 0);
+    assert_eq!(report.first_contradiction, None);
+
+    // With the check off, contradictory feedback is accepted but shows up in the audit.
+    answer(&env, &client, session_id, [1, 2, 5, 5], 0, 0).unwrap();
+    answer(&env, &client, session_id, [3, 4, 3, 4], 0, 0).unwrap();
+    let report = client.audit_game(&session_id);
+    assert_eq!(report.turns, 4);
+    assert_eq!(report.consistent_codes, 0);
+    assert_eq!(report.first_contradiction, Some(2));
+
+    let result = client.try_audit_game(&99);
+    assert_game_error(&result, Error::GameNotFound);
+}
+
+// The consistency check and `audit_game` in the compiled contract, over a full 12-turn
+// history, each call under the default budget. Native builds are not metered like the
+// wasm, so this needs `stellar contract build` first; run it with
+// `cargo test -p my-game -- --ignored test_wasm_consistency_check`.
+#[test]
+#[ignore]
+fn test_wasm_consistency_check_and_audit_fit_default_budget() {
+    let wasm = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../target/wasm32v1-none/release/my_game.wasm"
+    ))
+    .expect("build the contract with `stellar contract build` first");
+    let (env, _native, hub, player1, player2) = setup_test();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(wasm.as_slice(), (&admin, &hub.address));
+    let client = MyGameContractClient::new(&env, &contract_id);
+    hub.add_game(&contract_id);
+    client.set_hub_extensions(&true);
+    client.set_verifier(&env.register(MockUltraHonkVerifier, ()));
+    client.set_consistency_check(&true);
+    let session_id = 60u32;
+
+    // Every guess misses 6666, the last code, so only the check's full scan finds it
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [6, 6, 6, 6]));
+    let guesses = Rules::CLASSIC
+        .codes()
+        .filter(|code| !code.pegs().contains(&6))
+        .step_by(50)
+        .take(12);
+    for guess in guesses {
+        env.cost_estimate().budget().reset_default();
+        let guess_id = client.submit_guess(&session_id, &BytesN::from_array(&env, &guess.pegs()));
+        // Building the mock proof byte by byte is itself over budget
+        env.cost_estimate().budget().reset_unlimited();
+        let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &0, &0);
+        let proof_blob = build_proof_blob(&env, &public_inputs, true);
+        env.cost_estimate().budget().reset_default();
+        client.submit_feedback_proof(&session_id, &guess_id, &0, &0, &proof_blob);
+    }
+    assert!(client.get_game(&session_id).ended);
+
+    env.cost_estimate().budget().reset_default();
+    let report = client.audit_game(&session_id);
+    assert_eq!(report.turns, 12);
+    assert_eq!(report.consistent_codes, 1);
+    assert_eq!(report.first_contradiction, None);
+}
+
+#[test]
+fn test_reveal_secret_audits_blake2s_commitment() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 16u32;
+    let secret = Code::new(&Rules::CLASSIC, [2, 5, 5, 1]).unwrap();
+    let salt = [7u8; 16];
+    let commitment = BytesN::from_array(&env, &secret.commitment(&salt));
+    let salt = BytesN::from_array(&env, &salt);
+    let secret_bytes = BytesN::from_array(&env, &secret.pegs());
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment);
+    let result = client.try_reveal_secret(&session_id, &secret_bytes, &salt);
+    assert_game_error(&result, Error::GameNotEnded);
+
+    answer(&env, &client, session_id, [1, 1, 2, 2], 0, 2).unwrap();
+    answer(&env, &client, session_id, [2, 5, 5, 1], 4, 0).unwrap();
+
+    let wrong_salt = BytesN::from_array(&env, &[8u8; 16]);
+    let result = client.try_reveal_secret(&session_id, &secret_bytes, &wrong_salt);
+    assert_game_error(&result, Error::RevealMismatch);
+
+    assert!(client.reveal_secret(&session_id, &secret_bytes, &salt));
+    let game = client.get_game(&session_id);
+    assert!(game.audited);
+    assert_eq!(game.revealed_secret, Some(secret_bytes.clone()));
+
+    let result = client.try_reveal_secret(&session_id, &secret_bytes, &salt);
+    assert_game_error(&result, Error::AlreadyRevealed);
+}
+
+#[test]
+fn test_reveal_secret_with_host_hash_exposes_wrong_feedback() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 17u32;
+    let secret_bytes = BytesN::<4>::from_array(&env, &[3, 3, 4, 4]);
+    let salt = BytesN::<16>::from_array(&env, &[1u8; 16]);
+    let mut preimage = Bytes::from_array(&env, &secret_bytes.to_array());
+    preimage.append(&salt.clone().into());
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code_with_scheme(&session_id, &commitment, &CommitmentScheme::Sha256);
+    assert_eq!(
+        client.get_game(&session_id).commitment_scheme,
+        CommitmentScheme::Sha256
+    );
+
+    // The codemaker lies on the first guess (the true score is 0 exact, 2 partial), then concedes.
+    answer(&env, &client, session_id, [4, 4, 1, 1], 0, 0).unwrap();
+    answer(&env, &client, session_id, [3, 3, 4, 4], 4, 0).unwrap();
+
+    assert!(!client.reveal_secret(&session_id, &secret_bytes, &salt));
+    let game = client.get_game(&session_id);
+    assert!(!game.audited);
+    assert_eq!(game.revealed_secret, Some(secret_bytes));
+}
+
+const BOND: i128 = 50_0000000;
+const FEEDBACK_LEDGERS: u32 = 100;
+
+fn setup_bond(env: &Env, client: &MyGameContractClient, player1: &Address) -> TokenClient<'static> {
+    let token_admin = Address::generate(env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    StellarAssetClient::new(env, &token_addr).mint(player1, &BOND);
+    client.set_bond_config(&Some(BondConfig {
+        token: token_addr.clone(),
+        amount: BOND,
+        feedback_ledgers: FEEDBACK_LEDGERS,
+    }));
+    TokenClient::new(env, &token_addr)
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_bond_config_validation() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let token = Address::generate(&env);
+    assert_eq!(client.get_bond_config(), None);
+
+    let result = client.try_set_bond_config(&Some(BondConfig {
+        token: token.clone(),
+        amount: -1,
+        feedback_ledgers: 10,
+    }));
+    assert_game_error(&result, Error::InvalidConfig);
+    let result = client.try_set_bond_config(&Some(BondConfig {
+        token: token.clone(),
+        amount: BOND,
+        feedback_ledgers: 0,
+    }));
+    assert_game_error(&result, Error::InvalidConfig);
+
+    let config = BondConfig {
+        token,
+        amount: BOND,
+        feedback_ledgers: 10,
+    };
+    client.set_bond_config(&Some(config.clone()));
+    assert_eq!(client.get_bond_config(), Some(config));
+    client.set_bond_config(&None);
+    assert_eq!(client.get_bond_config(), None);
+}
+
+fn blake2s_commitment(env: &Env, pegs: [u8; 4]) -> BytesN<32> {
+    BytesN::from_array(
+        env,
+        &Code::new(&Rules::CLASSIC, pegs)
+            .unwrap()
+            .commitment(&[7u8; 16]),
+    )
+}
+
+#[test]
+fn test_bond_returned_when_code_is_cracked() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    let session_id = 18u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &blake2s_commitment(&env, [1, 2, 3, 4]));
+    assert_eq!(token.balance(&player1), 0);
+    assert_eq!(token.balance(&client.address), BOND);
+
+    // The bond is held until the codemaker shows the code behind the answers
+    answer(&env, &client, session_id, [1, 2, 3, 4], 4, 0).unwrap();
+    assert_eq!(token.balance(&client.address), BOND);
+    assert_eq!(
+        client.get_game(&session_id).reveal_deadline,
+        100 + FEEDBACK_LEDGERS
+    );
+    assert_game_error(
+        &client.try_claim_timeout(&session_id),
+        Error::RevealNotExpired,
+    );
+
+    assert!(client.reveal_secret(
+        &session_id,
+        &BytesN::from_array(&env, &[1, 2, 3, 4]),
+        &BytesN::from_array(&env, &[7u8; 16])
+    ));
+    assert_eq!(token.balance(&player1), BOND);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.get_game(&session_id).bond, 0);
+    advance_ledgers(&env, FEEDBACK_LEDGERS + 1);
+    assert_game_error(
+        &client.try_claim_timeout(&session_id),
+        Error::GameAlreadyEnded,
+    );
+}
+
+#[test]
+fn test_bond_returned_when_attempts_run_out() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    let session_id = 19u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &blake2s_commitment(&env, [6, 6, 6, 6]));
+    for _ in 0..12 {
+        answer(&env, &client, session_id, [1, 1, 1, 1], 0, 0).unwrap();
+    }
+
+    assert_eq!(client.get_game(&session_id).winner, Some(player1.clone()));
+    assert_eq!(hub.get_last_outcome(&session_id), Some(true));
+    client.reveal_secret(
+        &session_id,
+        &BytesN::from_array(&env, &[6, 6, 6, 6]),
+        &BytesN::from_array(&env, &[7u8; 16]),
+    );
+    assert_eq!(token.balance(&player1), BOND);
+    assert_eq!(token.balance(&player2), 0);
+}
+
+#[test]
+fn test_bond_slashed_when_reveal_fails() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    let session_id = 58u32;
+    let salt = BytesN::from_array(&env, &[7u8; 16]);
+
+    // A reveal that contradicts an answer hands the bond to the codebreaker
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &blake2s_commitment(&env, [3, 3, 4, 4]));
+    answer(&env, &client, session_id, [4, 4, 1, 1], 0, 0).unwrap();
+    answer(&env, &client, session_id, [3, 3, 4, 4], 4, 0).unwrap();
+    assert!(!client.reveal_secret(&session_id, &BytesN::from_array(&env, &[3, 3, 4, 4]), &salt));
+    assert_eq!(token.balance(&player2), BOND);
+    assert_eq!(token.balance(&client.address), 0);
+
+    // So does never revealing at all
+    StellarAssetClient::new(&env, &token.address).mint(&player1, &BOND);
+    client.start_game(
+        &(session_id + 1),
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+    );
+    client.commit_code(&(session_id + 1), &blake2s_commitment(&env, [1, 2, 3, 4]));
+    answer(&env, &client, session_id + 1, [1, 2, 3, 4], 4, 0).unwrap();
+    advance_ledgers(&env, FEEDBACK_LEDGERS);
+    assert_game_error(
+        &client.try_claim_timeout(&(session_id + 1)),
+        Error::RevealNotExpired,
+    );
+    advance_ledgers(&env, 1);
+    client.claim_timeout(&(session_id + 1));
+    assert_eq!(token.balance(&player2), 2 * BOND);
+    let result = client.try_reveal_secret(
+        &(session_id + 1),
+        &BytesN::from_array(&env, &[1, 2, 3, 4]),
+        &salt,
+    );
+    assert_eq!(result, Ok(Ok(true)));
+    assert_eq!(token.balance(&player1), 0);
+}
+
+#[test]
+fn test_bond_slashed_when_feedback_deadline_lapses() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    let session_id = 20u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    let result = client.try_claim_timeout(&session_id);
+    assert_game_error(&result, Error::NoPendingGuess);
+
+    client.submit_guess(&session_id, &BytesN::from_array(&env, &[5, 5, 5, 5]));
+    assert_eq!(
+        client.get_game(&session_id).feedback_deadline,
+        100 + FEEDBACK_LEDGERS
+    );
+
+    advance_ledgers(&env, FEEDBACK_LEDGERS);
+    let result = client.try_claim_timeout(&session_id);
+    assert_game_error(&result, Error::FeedbackNotExpired);
+
+    advance_ledgers(&env, 1);
+    client.claim_timeout(&session_id);
+    let game = client.get_game(&session_id);
+    assert!(game.ended);
+    assert!(!game.solved);
+    assert_eq!(game.winner, Some(player2.clone()));
+    assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+    assert_eq!(token.balance(&player2), BOND);
+    assert_eq!(token.balance(&player1), 0);
+
+    let result = client.try_claim_timeout(&session_id);
+    assert_game_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_no_timeout_without_bond_config() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 21u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    client.submit_guess(&session_id, &BytesN::from_array(&env, &[5, 5, 5, 5]));
+    advance_ledgers(&env, 10_000);
+
+    let result = client.try_claim_timeout(&session_id);
+    assert_game_error(&result, Error::FeedbackNotExpired);
+    assert_eq!(client.get_game(&session_id).bond_token, None);
+}
+
+const HINT_PRICE: i128 = 5_0000000;
+
+fn setup_hints(client: &MyGameContractClient) {
+    let verifier = client.get_verifier().unwrap();
+    for kind in [
+        HintKind::MostFrequentColor,
+        HintKind::PositionIs,
+        HintKind::ColorCount,
+    ] {
+        client.set_hint_config(
+            &kind,
+            &Some(HintConfig {
+                price: HINT_PRICE,
+                verifier: verifier.clone(),
+            }),
+        );
+    }
+}
+
+fn answer_hint(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    hint_id: u32,
+    answer: u32,
+) -> Result<(), Error> {
+    let public_inputs = match client.try_expected_hint_public_inputs(&session_id, &hint_id, &answer)
+    {
+        Ok(Ok(public_inputs)) => public_inputs,
+        Err(Ok(error)) => return Err(error),
+        _ => panic!("unexpected expected_hint_public_inputs result"),
+    };
+    let proof_blob = build_proof_blob(env, &public_inputs, true);
+    match client.try_answer_hint(&session_id, &hint_id, &answer, &proof_blob) {
+        Ok(Ok(())) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("unexpected answer_hint result"),
+    }
+}
+
+#[test]
+fn test_paid_hints_need_hub_extensions() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub_addr = env.register(StandardGameHub, ());
+    client.set_hub(&hub_addr);
+    client.set_hub_extensions(&false);
+    setup_hints(&client);
+    let session_id = 53u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+    let result = client.try_request_hint(&session_id, &HintKind::ColorCount, &0, &3);
+    assert_game_error(&result, Error::HubExtensionsRequired);
+
+    // Free hints never touch the hub
+    let verifier = client.get_verifier().unwrap();
+    client.set_hint_config(
+        &HintKind::ColorCount,
+        &Some(HintConfig { price: 0, verifier }),
+    );
+    client.request_hint(&session_id, &HintKind::ColorCount, &0, &3);
+    assert_eq!(client.get_game(&session_id).player2_points, 100_0000000);
+}
+
+#[test]
+fn test_hint_price_overflow_rejected() {
+    let (env, client, hub, player1, player2) = setup_test();
+    setup_hints(&client);
+    let session_id = 54u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &i128::MAX);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+    let result = client.try_request_hint(&session_id, &HintKind::ColorCount, &0, &3);
+    assert_game_error(&result, Error::InvalidConfig);
+    assert_eq!(hub.get_added_points(&session_id), 0);
+}
+
+#[test]
+fn test_hint_purchase_and_answer() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 22u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+    let result = client.try_request_hint(&session_id, &HintKind::ColorCount, &0, &3);
+    assert_game_error(&result, Error::HintUnavailable);
+
+    setup_hints(&client);
+    let hint_id = client.request_hint(&session_id, &HintKind::ColorCount, &0, &3);
+    assert_eq!(hub.get_added_points(&session_id), HINT_PRICE);
+    assert_eq!(
+        client.get_game(&session_id).player2_points,
+        100_0000000 + HINT_PRICE
+    );
+    let result = client.try_request_hint(&session_id, &HintKind::MostFrequentColor, &0, &0);
+    assert_game_error(&result, Error::HintPending);
+
+    // Guessing carries on while a hint is pending
+    client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 1, 1]));
+
+    let mut public_inputs = Bytes::new(&env);
+    for field in [session_id, hint_id] {
+        append_u32_field(&env, &mut public_inputs, field);
+    }
+    public_inputs.append(&commitment_from_4bytes(&env, [3, 5, 3, 1]).to_bytes());
+    for field in [3, 0, 3, 2] {
+        append_u32_field(&env, &mut public_inputs, field);
+    }
+    assert_eq!(
+        client.expected_hint_public_inputs(&session_id, &hint_id, &2),
+        public_inputs
+    );
+
+    // A feedback-shaped proof does not pass for the hint statement
+    let guess = BytesN::from_array(&env, &[1, 1, 1, 1]);
+    let feedback_inputs = build_public_inputs(
+        &env,
+        session_id,
+        hint_id,
+        &commitment_from_4bytes(&env, [3, 5, 3, 1]),
+        &guess,
+        1,
+        0,
+    );
+    let result = client.try_answer_hint(
+        &session_id,
+        &hint_id,
+        &2,
+        &build_proof_blob(&env, &feedback_inputs, true),
+    );
+    assert_game_error(&result, Error::InvalidPublicInputs);
+    assert_eq!(
+        answer_hint(&env, &client, session_id, hint_id, 5),
+        Err(Error::InvalidHint)
+    );
+
+    answer_hint(&env, &client, session_id, hint_id, 2).unwrap();
+    let record = client.get_hint(&session_id, &hint_id);
+    assert_eq!(record.kind, HintKind::ColorCount);
+    assert_eq!(record.answer, 2);
+    assert_eq!(record.price, HINT_PRICE);
+    assert!(record.proof_hash.is_some());
+    assert_eq!(client.get_game(&session_id).pending_hint_id, None);
+    assert_eq!(
+        answer_hint(&env, &client, session_id, hint_id, 2),
+        Err(Error::NoPendingHint)
+    );
+}
+
+#[test]
+fn test_hint_requests_are_validated() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 23u32;
+    setup_hints(&client);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let result = client.try_request_hint(&session_id, &HintKind::MostFrequentColor, &0, &0);
+    assert_game_error(&result, Error::CommitmentNotSet);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+
+    for (kind, position, color) in [
+        (HintKind::PositionIs, 4, 1),
+        (HintKind::PositionIs, 0, 7),
+        (HintKind::ColorCount, 1, 2),
+        (HintKind::MostFrequentColor, 0, 1),
+        (HintKind::ColorCount, 0, 256),
+    ] {
+        let result = client.try_request_hint(&session_id, &kind, &position, &color);
+        assert_game_error(&result, Error::InvalidHint);
+    }
+    assert_eq!(client.get_game(&session_id).next_hint_id, 0);
+
+    let result = client.try_set_hint_config(
+        &HintKind::ColorCount,
+        &Some(HintConfig {
+            price: -1,
+            verifier: client.get_verifier().unwrap(),
+        }),
+    );
+    assert_game_error(&result, Error::InvalidConfig);
+}
+
+#[test]
+fn test_hint_proofs_route_to_kind_verifier() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 24u32;
+    setup_hints(&client);
+    let rejecting = env.register(MockRejectingVerifier, ());
+    client.set_hint_config(
+        &HintKind::PositionIs,
+        &Some(HintConfig {
+            price: 0,
+            verifier: rejecting,
+        }),
+    );
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+
+    let hint_id = client.request_hint(&session_id, &HintKind::PositionIs, &1, &5);
+    assert_eq!(
+        answer_hint(&env, &client, session_id, hint_id, 1),
+        Err(Error::InvalidProof)
+    );
+
+    // Removing the kind's config leaves the pending hint unanswerable until it is restored
+    client.set_hint_config(&HintKind::PositionIs, &None);
+    assert_eq!(
+        answer_hint(&env, &client, session_id, hint_id, 1),
+        Err(Error::HintUnavailable)
+    );
+    let verifier = client.get_verifier().unwrap();
+    client.set_hint_config(
+        &HintKind::PositionIs,
+        &Some(HintConfig { price: 0, verifier }),
+    );
+    answer_hint(&env, &client, session_id, hint_id, 1).unwrap();
+
+    let hint_id = client.request_hint(&session_id, &HintKind::MostFrequentColor, &0, &0);
+    answer_hint(&env, &client, session_id, hint_id, 3).unwrap();
+}
+
+#[test]
+fn test_reveal_audits_hint_answers() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 25u32;
+    setup_hints(&client);
+    let secret = Code::new(&Rules::CLASSIC, [3, 5, 3, 1]).unwrap();
+    let salt = [9u8; 16];
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(
+        &session_id,
+        &BytesN::from_array(&env, &secret.commitment(&salt)),
+    );
+
+    // The codemaker lies: 5 appears once, not twice
+    let hint_id = client.request_hint(&session_id, &HintKind::ColorCount, &0, &5);
+    answer_hint(&env, &client, session_id, hint_id, 2).unwrap();
+    answer(&env, &client, session_id, [3, 5, 3, 1], 4, 0).unwrap();
+
+    let revealed = client.reveal_secret(
+        &session_id,
+        &BytesN::from_array(&env, &secret.pegs()),
+        &BytesN::from_array(&env, &salt),
+    );
+    assert!(!revealed);
+    assert!(!client.get_game(&session_id).audited);
+}
+
+#[test]
+fn test_unanswered_hint_can_be_claimed() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    setup_hints(&client);
+    let session_id = 26u32;
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+    client.request_hint(&session_id, &HintKind::MostFrequentColor, &0, &0);
+
+    advance_ledgers(&env, FEEDBACK_LEDGERS);
+    let result = client.try_claim_timeout(&session_id);
+    assert_game_error(&result, Error::FeedbackNotExpired);
+
+    advance_ledgers(&env, 1);
+    client.claim_timeout(&session_id);
+    assert_eq!(client.get_game(&session_id).winner, Some(player2.clone()));
+    assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+    assert_eq!(token.balance(&player2), BOND);
+}
+
+fn queued_answer(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    guess_id: u32,
+    exact: u32,
+    partial: u32,
+) -> FeedbackAnswer {
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &exact, &partial);
+    FeedbackAnswer {
+        guess_id,
+        exact,
+        partial,
+        proof_blob: build_proof_blob(env, &public_inputs, true),
+    }
+}
+
+#[test]
+fn test_queue_depth_config() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    assert_eq!(client.get_queue_depth(), 1);
+    assert_game_error(&client.try_set_queue_depth(&0), Error::InvalidConfig);
+    assert_game_error(&client.try_set_queue_depth(&13), Error::InvalidConfig);
+
+    // Depth is fixed when the game starts; queued guesses still use up attempts
+    client.set_queue_depth(&12);
+    client.start_game(&23, &player1, &player2, &100_0000000, &100_0000000);
+    client.set_queue_depth(&2);
+    assert_eq!(client.get_game(&23).queue_depth, 12);
+    client.commit_code(&23, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    for guess_id in 0..12u32 {
+        assert_eq!(
+            client.submit_guess(&23, &BytesN::from_array(&env, &[5, 5, 6, 6])),
+            guess_id
+        );
+    }
+    let result = client.try_submit_guess(&23, &BytesN::from_array(&env, &[5, 5, 6, 6]));
+    assert_game_error(&result, Error::AttemptsExhausted);
+    assert_eq!(client.get_game(&23).pending_guess_id, Some(0));
+}
+
+#[test]
+fn test_queued_guesses_are_answered_in_order() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 24u32;
+    client.set_queue_depth(&3);
+    setup_bond(&env, &client, &player1);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+
+    for raw_guess in [[5, 5, 6, 6], [1, 1, 2, 2], [4, 3, 2, 1]] {
+        client.submit_guess(&session_id, &BytesN::from_array(&env, &raw_guess));
+    }
+    let result = client.try_submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 1, 1]));
+    assert_game_error(&result, Error::GuessPendingFeedback);
+    let game = client.get_game(&session_id);
+    assert_eq!((game.pending_guess_id, game.next_guess_id), (Some(0), 3));
+    let first_deadline = game.feedback_deadline;
+
+    let out_of_order = queued_answer(&env, &client, session_id, 1, 1, 1);
+    let result =
+        client.try_submit_feedback_proof(&session_id, &1, &1, &1, &out_of_order.proof_blob);
+    assert_game_error(&result, Error::InvalidGuessId);
+
+    // Answering the oldest guess moves the deadline on to the next one
+    advance_ledgers(&env, 10);
+    let first = queued_answer(&env, &client, session_id, 0, 0, 0);
+    client.submit_feedback_proof(&session_id, &0, &0, &0, &first.proof_blob);
+    let game = client.get_game(&session_id);
+    assert_eq!((game.pending_guess_id, game.attempts_used), (Some(1), 1));
+    assert_eq!(game.feedback_deadline, first_deadline + 10);
+    assert_eq!(
+        client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 1, 1])),
+        3
+    );
+}
+
+#[test]
+fn test_feedback_batch_ends_game_with_guesses_still_queued() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 25u32;
+    client.set_queue_depth(&3);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    for raw_guess in [[5, 5, 6, 6], [1, 2, 3, 4], [4, 3, 2, 1]] {
+        client.submit_guess(&session_id, &BytesN::from_array(&env, &raw_guess));
+    }
+
+    // Answering past the solve fails the whole batch
+    let answers = vec![
+        &env,
+        queued_answer(&env, &client, session_id, 0, 0, 0),
+        queued_answer(&env, &client, session_id, 1, 4, 0),
+        queued_answer(&env, &client, session_id, 2, 0, 4),
+    ];
+    let result = client.try_submit_feedback_batch(&session_id, &answers);
+    assert_game_error(&result, Error::GameAlreadyEnded);
+    assert_eq!(client.get_game(&session_id).attempts_used, 0);
+
+    let wrong_order = vec![&env, answers.get(1).unwrap(), answers.get(0).unwrap()];
+    let result = client.try_submit_feedback_batch(&session_id, &wrong_order);
+    assert_game_error(&result, Error::InvalidGuessId);
+
+    client.submit_feedback_batch(
+        &session_id,
+        &vec![&env, answers.get(0).unwrap(), answers.get(1).unwrap()],
+    );
+    let game = client.get_game(&session_id);
+    assert!(game.ended && game.solved);
+    assert_eq!((game.attempts_used, game.pending_guess_id), (2, None));
+    assert_eq!(game.winner, Some(player2));
+    assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+    assert!(client.get_turn(&session_id, &2).proof_hash.is_none());
+    let result = client.try_submit_feedback_proof(
+        &session_id,
+        &2,
+        &0,
+        &4,
+        &answers.get(2).unwrap().proof_blob,
+    );
+    assert_game_error(&result, Error::GameAlreadyEnded);
+}
+
+fn aggregate_blob(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    first_guess_id: u32,
+    feedback: &Vec<FeedbackPair>,
+) -> Bytes {
+    let public_inputs =
+        client.expected_aggregate_public_inputs(&session_id, &first_guess_id, feedback);
+    build_proof_blob(env, &public_inputs, true)
+}
+
+#[test]
+fn test_aggregate_proof_answers_several_guesses() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 26u32;
+    client.set_queue_depth(&4);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    for raw_guess in [[5, 5, 6, 6], [1, 1, 2, 2], [4, 3, 2, 1]] {
+        client.submit_guess(&session_id, &BytesN::from_array(&env, &raw_guess));
+    }
+
+    let feedback = vec![
+        &env,
+        FeedbackPair {
+            exact: 0,
+            partial: 0,
+        },
+        FeedbackPair {
+            exact: 1,
+            partial: 1,
+        },
+        FeedbackPair {
+            exact: 0,
+            partial: 4,
+        },
+    ];
+    let proof_blob = aggregate_blob(&env, &client, session_id, 0, &feedback);
+    let result = client.try_submit_feedback_aggregate(&session_id, &0, &feedback, &proof_blob);
+    assert_game_error(&result, Error::VerifierNotSet);
+    client.set_batch_verifier(&client.get_verifier());
+
+    // Session, first guess, count, commitment, then guesses, exacts and partials over 4 slots
+    let public_inputs = client.expected_aggregate_public_inputs(&session_id, &0, &feedback);
+    assert_eq!(public_inputs.len(), 16 * 32);
+    assert_eq!(public_inputs.slice(8 * 32..16 * 32), {
+        let mut tail = Bytes::new(&env);
+        for value in [0u32, 1, 0, 0, 0, 1, 4, 0] {
+            let mut field = [0u8; 32];
+            field[28..].copy_from_slice(&value.to_be_bytes());
+            tail.append(&Bytes::from_array(&env, &field));
+        }
+        tail
+    });
+
+    let forged = build_proof_blob(&env, &public_inputs, false);
+    let result = client.try_submit_feedback_aggregate(&session_id, &0, &feedback, &forged);
+    assert_game_error(&result, Error::InvalidProof);
+    client.submit_feedback_aggregate(&session_id, &0, &feedback, &proof_blob);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(
+        (game.attempts_used, game.pending_guess_id, game.ended),
+        (3, None, false)
+    );
+    let turns = client.get_turns(&session_id, &0, &3);
+    let proof_hash = turns.get(0).unwrap().proof_hash.unwrap();
+    assert!(turns
+        .iter()
+        .all(|turn| turn.proof_hash == Some(proof_hash.clone())));
+    assert!(client.was_proof_used(&proof_hash));
+    assert_eq!(
+        (turns.get(2).unwrap().exact, turns.get(2).unwrap().partial),
+        (0, 4)
+    );
+}
+
+#[test]
+fn test_aggregate_proof_batch_bounds_and_solves() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 32u32;
+    client.set_queue_depth(&4);
+    client.set_batch_verifier(&client.get_verifier());
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    for raw_guess in [[5, 5, 6, 6], [1, 2, 3, 4], [4, 3, 2, 1]] {
+        client.submit_guess(&session_id, &BytesN::from_array(&env, &raw_guess));
+    }
+
+    let miss = FeedbackPair {
+        exact: 0,
+        partial: 0,
+    };
+    let solve = FeedbackPair {
+        exact: 4,
+        partial: 0,
+    };
+    let result = client.try_expected_aggregate_public_inputs(&session_id, &0, &Vec::new(&env));
+    assert_game_error(&result, Error::InvalidBatch);
+    let result = client.try_expected_aggregate_public_inputs(
+        &session_id,
+        &0,
+        &vec![&env, miss, miss, miss, miss, miss],
+    );
+    assert_game_error(&result, Error::InvalidBatch);
+    // Only three guesses are queued
+    let result = client.try_expected_aggregate_public_inputs(
+        &session_id,
+        &0,
+        &vec![&env, miss, solve, miss, miss],
+    );
+    assert_game_error(&result, Error::InvalidGuessId);
+
+    let feedback = vec![&env, solve];
+    let result = client.try_submit_feedback_aggregate(
+        &session_id,
+        &1,
+        &feedback,
+        &aggregate_blob(&env, &client, session_id, 1, &feedback),
+    );
+    assert_game_error(&result, Error::InvalidGuessId);
+
+    // A solve with more of the batch behind it is rejected
+    let feedback = vec![
+        &env,
+        miss,
+        solve,
+        FeedbackPair {
+            exact: 0,
+            partial: 4,
+        },
+    ];
+    let result = client.try_submit_feedback_aggregate(
+        &session_id,
+        &0,
+        &feedback,
+        &aggregate_blob(&env, &client, session_id, 0, &feedback),
+    );
+    assert_game_error(&result, Error::GameAlreadyEnded);
+
+    let feedback = vec![&env, miss, solve];
+    client.submit_feedback_aggregate(
+        &session_id,
+        &0,
+        &feedback,
+        &aggregate_blob(&env, &client, session_id, 0, &feedback),
+    );
+    let game = client.get_game(&session_id);
+    assert!(game.ended && game.solved);
+    assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+    assert!(client.get_turn(&session_id, &2).proof_hash.is_none());
+}
+
+const BN254_G2_GENERATOR: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+
+fn g1_times(env: &Env, scalar: u32) -> Bn254G1Affine {
+    let mut generator = [0u8; 64];
+    generator[31] = 1;
+    generator[63] = 2;
+    let generator = Bn254G1Affine::from_array(env, &generator);
+    env.crypto()
+        .bn254()
+        .g1_mul(&generator, &Fr::from_u256(U256::from_u32(env, scalar)))
+}
+
+fn from_hex<const N: usize>(env: &Env, hex: &str) -> BytesN<N> {
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+    }
+    BytesN::from_array(env, &bytes)
+}
+
+fn g2_generator(env: &Env) -> BytesN<128> {
+    from_hex(env, BN254_G2_GENERATOR)
+}
+
+// Trapdoor test vector: every VK point is a known multiple of the generators, with
+// beta = gamma = delta = G2, so A = alpha + vk_x + C and B = G2 satisfy the pairing check.
+fn groth16_vk(env: &Env, inputs: u32) -> Groth16VerifyingKey {
+    let mut ic = Vec::new(env);
+    for i in 0..=inputs {
+        ic.push_back(g1_times(env, 7 + i).to_bytes());
+    }
+    Groth16VerifyingKey {
+        alpha: g1_times(env, 5).to_bytes(),
+        beta: g2_generator(env),
+        gamma: g2_generator(env),
+        delta: g2_generator(env),
+        ic,
+    }
+}
+
+fn groth16_prove(env: &Env, vk: &Groth16VerifyingKey, public_inputs: &Bytes) -> Bytes {
+    groth16_prove_with(env, vk, public_inputs, 3)
+}
+
+// Any C gives a valid proof under the test VK; changing it re-randomizes the proof
+fn groth16_prove_with(
+    env: &Env,
+    vk: &Groth16VerifyingKey,
+    public_inputs: &Bytes,
+    c_scalar: u32,
+) -> Bytes {
+    let bn254 = env.crypto().bn254();
+    let mut vk_x = Bn254G1Affine::from_bytes(vk.ic.get(0).unwrap());
+    for i in 0..public_inputs.len() / 32 {
+        let input: BytesN<32> = public_inputs
+            .slice(i * 32..(i + 1) * 32)
+            .try_into()
+            .unwrap();
+        vk_x = bn254.g1_add(
+            &vk_x,
+            &bn254.g1_mul(
+                &Bn254G1Affine::from_bytes(vk.ic.get(i + 1).unwrap()),
+                &Fr::from_bytes(input),
+            ),
+        );
+    }
+    let c = g1_times(env, c_scalar);
+    let a = bn254.g1_add(
+        &bn254.g1_add(&Bn254G1Affine::from_bytes(vk.alpha.clone()), &vk_x),
+        &c,
+    );
+
+    let mut proof = Bytes::new(env);
+    proof.append(&a.to_bytes().into());
+    proof.append(&vk.beta.clone().into());
+    proof.append(&c.to_bytes().into());
+    proof
+}
+
+fn groth16_blob(env: &Env, public_inputs: &Bytes, proof: &Bytes) -> Bytes {
+    let mut blob = Bytes::from_array(env, &(public_inputs.len() / 32 + 8).to_be_bytes());
+    blob.append(public_inputs);
+    blob.append(proof);
+    blob
+}
+
+#[test]
+fn test_groth16_backend_is_chosen_per_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    assert_eq!(client.get_verifier_backend(), VerifierBackend::UltraHonk);
+    client.start_game(&33, &player1, &player2, &100_0000000, &100_0000000);
+    let vk = groth16_vk(&env, 6);
+    client.set_groth16_vk(&Some(vk.clone()));
+    client.set_verifier_backend(&VerifierBackend::Groth16);
+    client.start_game(&34, &player1, &player2, &100_0000000, &100_0000000);
+    assert_eq!(
+        client.get_game(&33).verifier_backend,
+        VerifierBackend::UltraHonk
+    );
+    assert_eq!(
+        client.get_game(&34).verifier_backend,
+        VerifierBackend::Groth16
+    );
+
+    // The game started earlier keeps verifying through the UltraHonk contract
+    client.commit_code(&33, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    assert_eq!(answer(&env, &client, 33, [1, 1, 2, 2], 1, 1), Ok(()));
+
+    client.commit_code(&34, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    let guess_id = client.submit_guess(&34, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    let public_inputs = client.expected_public_inputs(&34, &guess_id, &1, &1);
+    let proof_blob = groth16_blob(
+        &env,
+        &public_inputs,
+        &groth16_prove(&env, &vk, &public_inputs),
+    );
+    client.validate_proof_blob(&34, &guess_id, &1, &1, &proof_blob);
+    client.submit_feedback_proof(&34, &guess_id, &1, &1, &proof_blob);
+    let game = client.get_game(&34);
+    assert_eq!((game.attempts_used, game.pending_guess_id), (1, None));
+}
+
+#[test]
+fn test_groth16_backend_rejects_bad_proofs() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 35u32;
+    client.set_verifier_backend(&VerifierBackend::Groth16);
+    let empty = Groth16VerifyingKey {
+        ic: Vec::new(&env),
+        ..groth16_vk(&env, 0)
+    };
+    assert_game_error(
+        &client.try_set_groth16_vk(&Some(empty)),
+        Error::InvalidConfig,
+    );
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    let guess_id = client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    let vk = groth16_vk(&env, 6);
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &1, &1);
+    let proof = groth16_prove(&env, &vk, &public_inputs);
+    let proof_blob = groth16_blob(&env, &public_inputs, &proof);
+
+    let result = client.try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
+    assert_game_error(&result, Error::VerifierNotSet);
+    client.set_groth16_vk(&Some(groth16_vk(&env, 5)));
+    let result = client.try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
+    assert_game_error(&result, Error::InvalidPublicInputs);
+    client.set_groth16_vk(&Some(vk.clone()));
+
+    // A proof of other feedback does not verify against the claimed inputs
+    let other_inputs = client.expected_public_inputs(&session_id, &guess_id, &2, &0);
+    let forged = groth16_blob(
+        &env,
+        &public_inputs,
+        &groth16_prove(&env, &vk, &other_inputs),
+    );
+    let result = client.try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &forged);
+    assert_game_error(&result, Error::InvalidProof);
+    let truncated = proof_blob.slice(0..proof_blob.len() - 32);
+    let result = client.try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &truncated);
+    assert_game_error(&result, Error::InvalidPublicInputs);
+    let result = client.try_submit_feedback_proof(
+        &session_id,
+        &guess_id,
+        &1,
+        &1,
+        &Bytes::from_array(&env, &[0u8; 64]),
+    );
+    assert_game_error(&result, Error::InvalidProofBlob);
+
+    client.submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
+    assert_eq!(client.get_game(&session_id).attempts_used, 1);
+    let turn = client.get_turn(&session_id, &guess_id);
+    assert_eq!(
+        turn.proof_hash,
+        Some(BytesN::from(env.crypto().keccak256(&public_inputs)))
+    );
+
+    // A re-randomized proof of the same statement still verifies, but is a replay
+    let rerandomized = groth16_prove_with(&env, &vk, &public_inputs, 4);
+    assert_ne!(rerandomized, proof);
+    let result = env.as_contract(&client.address, || {
+        VerifierBackend::Groth16.verify(&env, &public_inputs, &rerandomized)
+    });
+    assert_eq!(result, Err(Error::ProofAlreadyUsed));
+    let other_inputs = client.expected_public_inputs(&session_id, &guess_id, &2, &0);
+    let fresh = groth16_prove_with(&env, &vk, &other_inputs, 4);
+    assert!(env
+        .as_contract(&client.address, || VerifierBackend::Groth16.verify(
+            &env,
+            &other_inputs,
+            &fresh
+        ))
+        .is_ok());
+}
+
+// Produced by arkworks (ark-groth16 0.5 on BN254, setup and proof from StdRng seed 2026) for
+// a circuit over the six feedback inputs of session 55: code [1, 2, 3, 4], guess [1, 1, 2, 2]
+// as guess 0, feedback (1, 1). G2 points are in the host encoding, c1 before c0.
+const ARKWORKS_ALPHA: &str = "1a41092f972004cb72b32eedf48fc3c4d8b55d79764e60826a42edc9b5cba5d415af253ac3d4d46fb8416e45f6237610c39d39534c4ac88a62efa0aa264ca30f";
+const ARKWORKS_BETA: &str = "1fa51ee70183f0977001e5d049fff2d642c930f07b14488e04b66ef2c677880b12d554462ab41f228a7dc56184e09890922d2f9ed33fe0f143b904af367c5d9c013ec661eb120f2e5c5815346c1bdcc85c14aaf32e782c245769e9032b5b3440073a075534715b545fa68ad2c09d4ba751eb43d86b71c2ba7efdbcedf1baa8ef";
+const ARKWORKS_GAMMA: &str = "11a91e6e47a0aca2a07eee7b388b45e0162f4d9f182c7304bb1298621c89836708c6ccb67ff5744fdd4c2e7e6b21a91d0ce6bca3734e349c675cceb9a5f02c2b21c3b6ceb8e722444f7e393ee2521d6fcdedc181b1895f2b33faf4b349badf652fd5eb68a5b4792bac2bbc1cb14cc13aaa047c23693b162001b4ab306e6a324f";
+const ARKWORKS_DELTA: &str = "0d3b9c45d4541206df3df70b3ce363cee110c28ff4ecca0cf86f1539bb08ded409dccd09781faef20fcc9996ccca9ef7af79102c3f92a5997dc7dcdc4ec5ac26069bcc450c39bc9a233fe58ecca82fdcd6dd2dbd36d0662b8dbdd49cbdcbd0de1474a70046320894b96051cef1062de3abda15c4267512522fd2de3e086579fc";
+const ARKWORKS_IC: [&str; 7] = [
+    "1eda446f839d461faa4e5bd1433577a97cbe17123f84c34787d9c90b58bdc2c422b5b82e2fcf9b7ca568c6ebc2b76f28588a97929f47a3f0825daaca7854f3ab",
+    "1096c5ba009330991ba01431d11bf304c48667d34bce5cccba905e1262717bc017ebfc00ac287ca4a854f7affc923d8825c4c451040a43f560021a7e8c827b3b",
+    "2cbbc5e360746f88cc1b05193c6ac599aa49d75a11cefe4a58c9b5efc67798ab24b61ca46f5cd2c0c86205c8e7bd1fe4cf3071465f548c904632a273b3400453",
+    "05ed0e46bbd6c3533f2b1f3ce94bd0eebafe7d17cfe69f0c25cedfa0f3ca747c03fc3ddf9686036130f46779ee5f066998bdc371acb746f92b46bc940f0da4ef",
+    "0def86aabfcc099198799fc61c29dc2734d2928439de37d457138338f0ab640311d4fb22aa441b67166b92dc7ba9e0d9880a0116d3f99067c7b888b2c243c435",
+    "2ddbe8c9341e5e8c92de87804ed2ddaf315fd001554d45b127c3ed576b3ff92b2d992437841371dba35c57f6adb6557f6131e211a13609f1fa2fe09949b7dd19",
+    "18f9707d345a74f87b136147ce66775a91d4d9ceaa9866c62cf414c1313e8e97234d2bbe255654b02cfbb19fb3db789aae0fb86b8008281c78bece2f53243649",
+];
+const ARKWORKS_PROOF: &str = "0f518aaf82683e752fc1e3ee8c6df4c2ee7dedc6965566b48c96f01bce634c73220c89405dbdac06c018f95804cc5e6ae36f34a68e25e52756c744362f2d6aa5112da2926a99777414a0b7da9f3b6232795f6ccbd9855d6f03bb54df9e8d577011ec8baa3c8cfb98f03cacb063fff02bd8c03e1dcac617b67fae5a35fc32028b246316f27b9b044253a2628b8030bd9c1ba4738f7d477fe0876c50cc0fdadd80017ab3ef9fe93170449be46ddccf840c194337e43868466e88d28364094e210d07186a7114713ee50a1fb2f620715dbce66d27c66f93d48f658703afa9e13ef427f1c65b3ff0ad50ebf53df981541e56339e06194402fc02a5ed366814a37596";
+
+fn arkworks_vk(env: &Env) -> Groth16VerifyingKey {
+    let mut ic = Vec::new(env);
+    for point in ARKWORKS_IC {
+        ic.push_back(from_hex(env, point));
+    }
+    Groth16VerifyingKey {
+        alpha: from_hex(env, ARKWORKS_ALPHA),
+        beta: from_hex(env, ARKWORKS_BETA),
+        gamma: from_hex(env, ARKWORKS_GAMMA),
+        delta: from_hex(env, ARKWORKS_DELTA),
+        ic,
+    }
+}
+
+#[test]
+fn test_groth16_accepts_arkworks_proof() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 55u32;
+    client.set_groth16_vk(&Some(arkworks_vk(&env)));
+    client.set_verifier_backend(&VerifierBackend::Groth16);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    let guess_id = client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &1, &1);
+    let proof: BytesN<256> = from_hex(&env, ARKWORKS_PROOF);
+    let proof = Bytes::from(proof);
+
+    // B with the halves of each coordinate swapped is not the prover's point
+    let mut swapped = proof.slice(0..64);
+    for half in [96u32, 64, 160, 128] {
+        swapped.append(&proof.slice(half..half + 32));
+    }
+    swapped.append(&proof.slice(192..256));
+    let swapped = groth16_blob(&env, &public_inputs, &swapped);
+    assert!(client
+        .try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &swapped)
+        .is_err());
+
+    client.submit_feedback_proof(
+        &session_id,
+        &guess_id,
+        &1,
+        &1,
+        &groth16_blob(&env, &public_inputs, &proof),
+    );
+    let game = client.get_game(&session_id);
+    assert_eq!((game.attempts_used, game.pending_guess_id), (1, None));
+}
+
+#[test]
+fn test_groth16_backend_covers_duels_and_puzzles() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 56u32;
+    let puzzle_id = 11u32;
+    let vk = groth16_vk(&env, 6);
+    client.set_groth16_vk(&Some(vk.clone()));
+    client.set_verifier_backend(&VerifierBackend::Groth16);
+
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+    assert_eq!(
+        client.get_duel(&session_id).verifier_backend,
+        VerifierBackend::Groth16
+    );
+    let guess = BytesN::from_array(&env, &[1, 1, 1, 1]);
+    let guess_id = client.submit_duel_guess(&session_id, &player1, &guess);
+    let public_inputs = build_public_inputs(
+        &env,
+        session_id,
+        guess_id,
+        &commitment_from_4bytes(&env, [6, 6, 5, 5]),
+        &guess,
+        0,
+        0,
+    );
+    let ultra_honk_blob = build_proof_blob(&env, &public_inputs, true);
+    assert!(client
+        .try_submit_duel_feedback(&session_id, &player2, &guess_id, &0, &0, &ultra_honk_blob)
+        .is_err());
+    let proof_blob = groth16_blob(
+        &env,
+        &public_inputs,
+        &groth16_prove(&env, &vk, &public_inputs),
+    );
+    client.submit_duel_feedback(&session_id, &player2, &guess_id, &0, &0, &proof_blob);
+    assert_eq!(
+        client
+            .get_duel(&session_id)
+            .sides
+            .get(0)
+            .unwrap()
+            .attempts_used,
+        1
+    );
+
+    client.create_puzzle(
+        &puzzle_id,
+        &player1,
+        &commitment_from_4bytes(&env, [3, 1, 4, 1]),
+    );
+    let guess_id = client.submit_puzzle_guess(
+        &puzzle_id,
+        &player2,
+        &BytesN::from_array(&env, &[1, 1, 2, 2]),
+    );
+    let public_inputs =
+        client.expected_puzzle_public_inputs(&puzzle_id, &player2, &guess_id, &1, &1);
+    let proof_blob = groth16_blob(
+        &env,
+        &public_inputs,
+        &groth16_prove(&env, &vk, &public_inputs),
+    );
+    client.submit_puzzle_feedback(&puzzle_id, &player2, &guess_id, &1, &1, &proof_blob);
+    assert_eq!(
+        client.get_puzzle_track(&puzzle_id, &player2).attempts_used,
+        1
+    );
+}
+
+#[test]
+fn test_verifier_failures_map_to_distinct_errors() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 36u32;
+    let faulty = env.register(MockFaultyVerifier, ());
+    let faulty_client = MockFaultyVerifierClient::new(&env, &faulty);
+    client.set_verifier(&faulty);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    let guess_id = client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &1, &1);
+    let proof_blob = build_proof_blob(&env, &public_inputs, true);
+
+    for (cause, expected) in [
+        (1, Error::VerifierMisconfigured),
+        (2, Error::MalformedProof),
+        (3, Error::InvalidProof),
+        (4, Error::VerifierMisconfigured),
+        (0, Error::VerifierUnavailable),
+    ] {
+        faulty_client.set_cause(&cause);
+        let result = client.try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
+        assert_game_error(&result, expected);
+    }
+
+    // No contract behind the verifier address at all
+    client.set_verifier(&Address::generate(&env));
+    let result = client.try_submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
+    assert_game_error(&result, Error::VerifierUnavailable);
+}
+
+#[test]
+fn test_verifier_fault_restarts_feedback_window() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 37u32;
+    let healthy = client.get_verifier().unwrap();
+    let faulty = env.register(MockFaultyVerifier, ());
+    let faulty_client = MockFaultyVerifierClient::new(&env, &faulty);
+    setup_bond(&env, &client, &player1);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    assert_game_error(
+        &client.try_report_verifier_fault(&session_id),
+        Error::NoPendingGuess,
+    );
+    client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    let first_deadline = client.get_game(&session_id).feedback_deadline;
+
+    // Faults are only shown against a known-good proof, which a working verifier accepts
+    assert_game_error(
+        &client.try_report_verifier_fault(&session_id),
+        Error::VerifierProbeNotSet,
+    );
+    let probe_inputs = client.expected_public_inputs(&session_id, &0, &1, &1);
+    let probe = build_proof_blob(&env, &probe_inputs, true);
+    let result = client.try_set_verifier_probe(&healthy, &Some(Bytes::from_array(&env, &[0, 0])));
+    assert_game_error(&result, Error::InvalidConfig);
+    client.set_verifier_probe(&healthy, &Some(probe.clone()));
+    client.set_verifier_probe(&faulty, &Some(probe.clone()));
+    assert_eq!(client.get_verifier_probe(&faulty), Some(probe));
+    assert_game_error(
+        &client.try_report_verifier_fault(&session_id),
+        Error::NoVerifierFault,
+    );
+    client.set_verifier(&faulty);
+
+    advance_ledgers(&env, FEEDBACK_LEDGERS - 1);
+    faulty_client.set_cause(&4);
+    client.report_verifier_fault(&session_id);
+    let events = env.events().all().filter_by_contract(&client.address);
+    let game = client.get_game(&session_id);
+    assert_eq!(
+        game.feedback_deadline,
+        first_deadline + FEEDBACK_LEDGERS - 1
+    );
+    assert_eq!(
+        events,
+        [
+            VerifierFailure {
+                verifier: faulty.clone(),
+                cause: Error::VerifierMisconfigured as u32
+            }
+            .to_xdr(&env, &client.address),
+            VerifierFaultReported {
+                session_id,
+                cause: Error::VerifierMisconfigured as u32,
+                feedback_deadline: game.feedback_deadline,
+                hint_deadline: 0
+            }
+            .to_xdr(&env, &client.address),
+        ]
+    );
+
+    advance_ledgers(&env, 2);
+    assert_game_error(
+        &client.try_claim_timeout(&session_id),
+        Error::FeedbackNotExpired,
+    );
+    client.set_verifier(&healthy);
+    assert_eq!(answer_pending(&env, &client, session_id, 1, 1), Ok(()));
+}
+
+#[test]
+fn test_verifier_fault_restarts_hint_window() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 47u32;
+    let faulty = env.register(MockFaultyVerifier, ());
+    MockFaultyVerifierClient::new(&env, &faulty).set_cause(&1);
+    setup_bond(&env, &client, &player1);
+    setup_hints(&client);
+    client.set_hint_config(
+        &HintKind::PositionIs,
+        &Some(HintConfig {
+            price: HINT_PRICE,
+            verifier: faulty.clone(),
+        }),
+    );
+    let probe = build_proof_blob(&env, &Bytes::from_array(&env, &[1; 32]), true);
+    client.set_verifier_probe(&client.get_verifier().unwrap(), &Some(probe.clone()));
+    client.set_verifier_probe(&faulty, &Some(probe));
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+    client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    let hint_id = client.request_hint(&session_id, &HintKind::PositionIs, &0, &3);
+    let first = client.get_game(&session_id);
+
+    // Only the hint's verifier is broken, so only the hint window restarts
+    advance_ledgers(&env, FEEDBACK_LEDGERS - 1);
+    client.report_verifier_fault(&session_id);
+    let game = client.get_game(&session_id);
+    assert_eq!(game.feedback_deadline, first.feedback_deadline);
+    assert_eq!(
+        game.hint_deadline,
+        first.hint_deadline + FEEDBACK_LEDGERS - 1
+    );
+
+    answer_pending(&env, &client, session_id, 0, 0).unwrap();
+    advance_ledgers(&env, 2);
+    assert_game_error(
+        &client.try_claim_timeout(&session_id),
+        Error::FeedbackNotExpired,
+    );
+    assert_eq!(client.get_hint(&session_id, &hint_id).answer, 0);
+}
+
+fn answer_pending(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    exact: u32,
+    partial: u32,
+) -> Result<(), Error> {
+    let guess_id = client.get_game(&session_id).pending_guess_id.unwrap();
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &exact, &partial);
+    match client.try_submit_feedback_proof(
+        &session_id,
+        &guess_id,
+        &exact,
+        &partial,
+        &build_proof_blob(env, &public_inputs, true),
+    ) {
+        Ok(Ok(())) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("unexpected submit_feedback_proof result"),
+    }
+}
+
+fn proof_part(blob: &Bytes, public_inputs: &Bytes) -> Bytes {
+    blob.slice(4 + public_inputs.len()..blob.len())
+}
+
+#[test]
+fn test_accepted_proofs_cannot_be_replayed() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let commitment = commitment_from_4bytes(&env, [1, 2, 3, 4]);
+    for session_id in [38u32, 39] {
+        client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+        client.commit_code(&session_id, &commitment);
+        client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+    }
+
+    let public_inputs = client.expected_public_inputs(&38, &0, &1, &1);
+    let proof_blob = build_proof_blob(&env, &public_inputs, true);
+    let proof = proof_part(&proof_blob, &public_inputs);
+    let proof_hash: BytesN<32> = env.crypto().keccak256(&proof).into();
+    assert!(!client.was_proof_used(&proof_hash));
+    client.submit_feedback_proof(&38, &0, &1, &1, &proof_blob);
+    assert!(client.was_proof_used(&proof_hash));
+    let ttl = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::UsedProof(proof_hash.clone()))
+    });
+    assert!(ttl >= PROOF_TTL_LEDGERS);
+
+    // As if session 39's inputs matched the proof: the verifier mock accepts it, the registry doesn't
+    let other_inputs = client.expected_public_inputs(&39, &0, &1, &1);
+    let mut replayed = Bytes::from_array(&env, &(other_inputs.len() / 32 + 440).to_be_bytes());
+    replayed.append(&other_inputs);
+    replayed.append(&proof);
+    let result = client.try_submit_feedback_proof(&39, &0, &1, &1, &replayed);
+    assert_game_error(&result, Error::ProofAlreadyUsed);
+
+    // Re-encoding the header changes the blob hash but not the proof
+    let mut reheaded = Bytes::from_array(&env, &0u32.to_be_bytes());
+    reheaded.append(&other_inputs);
+    reheaded.append(&proof);
+    let result = client.try_submit_feedback_proof(&39, &0, &1, &1, &reheaded);
+    assert_game_error(&result, Error::ProofAlreadyUsed);
+
+    // Other game modes share the registry
+    let puzzle_breaker = Address::generate(&env);
+    client.create_puzzle(&10, &player1, &commitment);
+    client.submit_puzzle_guess(
+        &10,
+        &puzzle_breaker,
+        &BytesN::from_array(&env, &[1, 1, 2, 2]),
+    );
+    let puzzle_inputs = client.expected_puzzle_public_inputs(&10, &puzzle_breaker, &0, &1, &1);
+    let mut replayed = Bytes::from_array(&env, &(puzzle_inputs.len() / 32 + 440).to_be_bytes());
+    replayed.append(&puzzle_inputs);
+    replayed.append(&proof);
+    let result = client.try_submit_puzzle_feedback(&10, &puzzle_breaker, &0, &1, &1, &replayed);
+    assert_game_error(&result, Error::ProofAlreadyUsed);
+
+    client.submit_feedback_proof(
+        &39,
+        &0,
+        &1,
+        &1,
+        &build_proof_blob(&env, &other_inputs, true),
+    );
+}
+
+#[test]
+fn test_recorded_proof_hashes_are_registry_keys() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    setup_hints(&client);
+    let session_id = 48u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [3, 5, 3, 1]));
+    answer(&env, &client, session_id, [1, 1, 2, 2], 0, 1).unwrap();
+    let hint_id = client.request_hint(&session_id, &HintKind::ColorCount, &0, &3);
+    answer_hint(&env, &client, session_id, hint_id, 2).unwrap();
+
+    let turn_hash = client.get_turn(&session_id, &0).proof_hash.unwrap();
+    let hint_hash = client.get_hint(&session_id, &hint_id).proof_hash.unwrap();
+    assert!(client.was_proof_used(&turn_hash));
+    assert!(client.was_proof_used(&hint_hash));
+
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id + 1,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id + 1,
+        &player1,
+        &player2,
+        &codes[1],
+        [1, 1, 1, 1],
+    );
+    let duel_hash = client
+        .get_duel_turn(&(session_id + 1), &0)
+        .turn
+        .proof_hash
+        .unwrap();
+    assert!(client.was_proof_used(&duel_hash));
+}
+
+fn duel_code(pegs: [u8; 4]) -> Code {
+    Code::new(&Rules::CLASSIC, pegs).unwrap()
+}
+
+fn start_duel(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    tie_policy: DuelTiePolicy,
+    codes: [Code; 2],
+) {
+    client.start_duel(
+        &session_id,
+        player1,
+        player2,
+        &100_0000000,
+        &100_0000000,
+        &tie_policy,
+    );
+    client.commit_duel_code(
+        &session_id,
+        player1,
+        &commitment_from_4bytes(env, codes[0].pegs()),
+    );
+    client.commit_duel_code(
+        &session_id,
+        player2,
+        &commitment_from_4bytes(env, codes[1].pegs()),
+    );
+}
+
+// `breaker` guesses, then `maker` proves the true feedback against its own code
+fn duel_round(
+    env: &Env,
+    client: &MyGameContractClient,
+    session_id: u32,
+    breaker: &Address,
+    maker: &Address,
+    maker_code: &Code,
+    raw_guess: [u8; 4],
+) {
+    let guess = duel_code(raw_guess);
+    let guess_id =
+        client.submit_duel_guess(&session_id, breaker, &BytesN::from_array(env, &raw_guess));
+    let feedback = maker_code.score(&guess);
+    let public_inputs = build_public_inputs(
+        env,
+        session_id,
+        guess_id,
+        &commitment_from_4bytes(env, maker_code.pegs()),
+        &BytesN::from_array(env, &raw_guess),
+        feedback.exact(),
+        feedback.partial(),
+    );
+    let proof_blob = build_proof_blob(env, &public_inputs, true);
+    client.submit_duel_feedback(
+        &session_id,
+        maker,
+        &guess_id,
+        &feedback.exact(),
+        &feedback.partial(),
+        &proof_blob,
+    );
+}
+
+#[test]
+fn test_duel_first_solver_wins_after_opponent_matches_attempts() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 27u32;
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [1, 1, 1, 1],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [2, 2, 2, 2],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [6, 6, 5, 5],
+    );
+
+    // Player 1 solved on attempt 2; player 2 still gets attempt 2
+    let duel = client.get_duel(&session_id);
+    assert_eq!(duel.sides.get(0).unwrap().solved_at, 2);
+    assert!(!duel.ended);
+    let result = client.try_submit_duel_guess(
+        &session_id,
+        &player1,
+        &BytesN::from_array(&env, &[1, 1, 1, 1]),
+    );
+    assert_game_error(&result, Error::AttemptsExhausted);
+
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [1, 2, 4, 3],
+    );
+    let duel = client.get_duel(&session_id);
+    assert!(duel.ended);
+    assert_eq!(duel.winners, vec![&env, player1.clone()]);
+    assert_eq!(hub.get_last_outcome(&session_id), Some(true));
+    assert_eq!(hub.get_end_count(&session_id), 1);
+
+    let turn = client.get_duel_turn(&session_id, &3);
+    assert_eq!(turn.side, 1);
+    assert_eq!((turn.turn.exact, turn.turn.partial), (2, 2));
+}
+
+#[test]
+fn test_duel_turns_and_proof_obligations() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 28u32;
+    let outsider = Address::generate(&env);
+    let guess = BytesN::from_array(&env, &[1, 1, 2, 2]);
+
+    client.start_duel(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &DuelTiePolicy::Draw,
+    );
+    client.commit_duel_code(
+        &session_id,
+        &player1,
+        &commitment_from_4bytes(&env, [1, 2, 3, 4]),
+    );
+    let result = client.try_submit_duel_guess(&session_id, &player1, &guess);
+    assert_game_error(&result, Error::CommitmentNotSet);
+    let result = client.try_commit_duel_code(
+        &session_id,
+        &player1,
+        &commitment_from_4bytes(&env, [1, 2, 3, 4]),
+    );
+    assert_game_error(&result, Error::CommitmentAlreadySet);
+    let result = client.try_commit_duel_code(
+        &session_id,
+        &outsider,
+        &commitment_from_4bytes(&env, [1, 2, 3, 4]),
+    );
+    assert_game_error(&result, Error::NotPlayer);
+    client.commit_duel_code(
+        &session_id,
+        &player2,
+        &commitment_from_4bytes(&env, [5, 5, 6, 6]),
+    );
+
+    let result = client.try_submit_duel_guess(&session_id, &player2, &guess);
+    assert_game_error(&result, Error::NotYourTurn);
+    let first = client.submit_duel_guess(&session_id, &player1, &guess);
+    let result = client.try_submit_duel_guess(&session_id, &player1, &guess);
+    assert_game_error(&result, Error::GuessPendingFeedback);
+
+    // Player 2 may guess before answering; both obligations are then open
+    let second = client.submit_duel_guess(&session_id, &player2, &guess);
+    assert_eq!((first, second), (0, 1));
+
+    // Each player answers only the opponent's guess, against their own commitment
+    let public_inputs = build_public_inputs(
+        &env,
+        session_id,
+        first,
+        &commitment_from_4bytes(&env, [1, 2, 3, 4]),
+        &guess,
+        1,
+        1,
+    );
+    let proof_blob = build_proof_blob(&env, &public_inputs, true);
+    let result =
+        client.try_submit_duel_feedback(&session_id, &player2, &first, &1, &1, &proof_blob);
+    assert_game_error(&result, Error::InvalidPublicInputs);
+    let result =
+        client.try_submit_duel_feedback(&session_id, &player1, &first, &1, &1, &proof_blob);
+    assert_game_error(&result, Error::InvalidGuessId);
+    client.submit_duel_feedback(
+        &session_id,
+        &player1,
+        &second,
+        &1,
+        &1,
+        &build_proof_blob(
+            &env,
+            &build_public_inputs(
+                &env,
+                session_id,
+                second,
+                &commitment_from_4bytes(&env, [1, 2, 3, 4]),
+                &guess,
+                1,
+                1,
+            ),
+            true,
+        ),
+    );
+
+    let duel = client.get_duel(&session_id);
+    assert_eq!(duel.sides.get(1).unwrap().attempts_used, 1);
+    assert_eq!(duel.sides.get(0).unwrap().pending_guess_id, Some(first));
+}
+
+#[test]
+fn test_duel_equal_solves_draw() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 29u32;
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [6, 6, 5, 5],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [1, 2, 3, 4],
+    );
+
+    let duel = client.get_duel(&session_id);
+    assert!(duel.ended);
+    assert_eq!(duel.winners, vec![&env, player1.clone(), player2.clone()]);
+    assert_eq!(
+        hub.get_last_winners(&session_id),
+        Some(vec![&env, player1, player2])
+    );
+    assert_eq!(hub.get_last_outcome(&session_id), None);
+}
+
+#[test]
+fn test_duel_tiebreak_on_exact_pegs() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 30u32;
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::MostExact,
+        codes,
+    );
+
+    // Both solve on attempt 2, but player 2 had 2 exact pegs on attempt 1 against player 1's 0
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [5, 5, 6, 6],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [1, 2, 4, 3],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [6, 6, 5, 5],
+    );
+    assert!(!client.get_duel(&session_id).ended);
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [1, 2, 3, 4],
+    );
+
+    let duel = client.get_duel(&session_id);
+    assert_eq!(duel.winners, vec![&env, player2.clone()]);
+    assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+}
+
+#[test]
+fn test_duel_later_solver_wins_when_first_cannot_match() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 31u32;
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [1, 1, 1, 1],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [1, 2, 3, 4],
+    );
+
+    // Player 1 already used attempt 1 without solving
+    let duel = client.get_duel(&session_id);
+    assert!(duel.ended);
+    assert_eq!(duel.winners, vec![&env, player2]);
+    assert_eq!(hub.get_last_outcome(&session_id), Some(false));
+}
+
+#[test]
+fn test_duel_stalled_opponent_forfeits_bonds() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    StellarAssetClient::new(&env, &token.address).mint(&player2, &BOND);
+    let session_id = 44u32;
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+    assert_eq!(token.balance(&client.address), 2 * BOND);
+    let result = client.try_claim_duel_timeout(&session_id, &player1);
+    assert_game_error(&result, Error::NoPendingGuess);
+
+    // Player 2 answers once, then leaves player 1's next guess unanswered
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [1, 1, 1, 1],
+    );
+    client.submit_duel_guess(
+        &session_id,
+        &player2,
+        &BytesN::from_array(&env, &[2, 2, 2, 2]),
+    );
+    client.submit_duel_feedback(
+        &session_id,
+        &player1,
+        &1,
+        &1,
+        &0,
+        &build_proof_blob(
+            &env,
+            &build_public_inputs(
+                &env,
+                session_id,
+                1,
+                &commitment_from_4bytes(&env, codes[0].pegs()),
+                &BytesN::from_array(&env, &[2, 2, 2, 2]),
+                1,
+                0,
+            ),
+            true,
+        ),
+    );
+    client.submit_duel_guess(
+        &session_id,
+        &player1,
+        &BytesN::from_array(&env, &[5, 5, 5, 5]),
+    );
+    assert_eq!(
+        client
+            .get_duel(&session_id)
+            .sides
+            .get(0)
+            .unwrap()
+            .feedback_deadline,
+        100 + FEEDBACK_LEDGERS
+    );
+
+    advance_ledgers(&env, FEEDBACK_LEDGERS);
+    let result = client.try_claim_duel_timeout(&session_id, &player1);
+    assert_game_error(&result, Error::FeedbackNotExpired);
+    let result = client.try_claim_duel_timeout(&session_id, &player2);
+    assert_game_error(&result, Error::NoPendingGuess);
+
+    advance_ledgers(&env, 1);
+    client.claim_duel_timeout(&session_id, &player1);
+    let duel = client.get_duel(&session_id);
+    assert!(duel.ended);
+    assert_eq!(duel.winners, vec![&env, player1.clone()]);
+    assert_eq!(hub.get_last_outcome(&session_id), Some(true));
+    assert_eq!(token.balance(&player1), 2 * BOND);
+    assert_eq!(token.balance(&player2), 0);
+
+    let result = client.try_claim_duel_timeout(&session_id, &player1);
+    assert_game_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_duel_timeout_with_both_sides_overdue_goes_to_earlier_guess() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    StellarAssetClient::new(&env, &token.address).mint(&player2, &BOND);
+    let session_id = 51u32;
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+
+    // Neither player answers the other's guess; player 1's was due first
+    client.submit_duel_guess(
+        &session_id,
+        &player1,
+        &BytesN::from_array(&env, &[1, 1, 1, 1]),
+    );
+    advance_ledgers(&env, 1);
+    client.submit_duel_guess(
+        &session_id,
+        &player2,
+        &BytesN::from_array(&env, &[2, 2, 2, 2]),
+    );
+
+    advance_ledgers(&env, FEEDBACK_LEDGERS + 1);
+    let result = client.try_claim_duel_timeout(&session_id, &player2);
+    assert_game_error(&result, Error::FeedbackOverdue);
+
+    client.claim_duel_timeout(&session_id, &player1);
+    assert_eq!(
+        client.get_duel(&session_id).winners,
+        vec![&env, player1.clone()]
+    );
+    assert_eq!(hub.get_last_outcome(&session_id), Some(true));
+    assert_eq!(token.balance(&player1), 2 * BOND);
+    assert_eq!(token.balance(&player2), 0);
+}
+
+#[test]
+fn test_duel_verifier_fault_restarts_feedback_windows() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    StellarAssetClient::new(&env, &token.address).mint(&player2, &BOND);
+    let session_id = 52u32;
+    let faulty = env.register(MockFaultyVerifier, ());
+    let probe = build_proof_blob(&env, &Bytes::from_array(&env, &[1; 32]), true);
+    client.set_verifier_probe(&client.get_verifier().unwrap(), &Some(probe.clone()));
+    client.set_verifier_probe(&faulty, &Some(probe));
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+    assert_game_error(
+        &client.try_report_verifier_fault(&session_id),
+        Error::NoPendingGuess,
+    );
+
+    client.submit_duel_guess(
+        &session_id,
+        &player1,
+        &BytesN::from_array(&env, &[1, 1, 1, 1]),
+    );
+    client.submit_duel_guess(
+        &session_id,
+        &player2,
+        &BytesN::from_array(&env, &[2, 2, 2, 2]),
+    );
+    assert_game_error(
+        &client.try_report_verifier_fault(&session_id),
+        Error::NoVerifierFault,
+    );
+
+    // Player 2 can't answer while the verifier is down, so the window restarts
+    client.set_verifier(&faulty);
+    advance_ledgers(&env, FEEDBACK_LEDGERS - 1);
+    client.report_verifier_fault(&session_id);
+    let duel = client.get_duel(&session_id);
+    assert_eq!(
+        duel.sides.get(0).unwrap().feedback_deadline,
+        100 + 2 * FEEDBACK_LEDGERS - 1
+    );
+    assert_eq!(
+        duel.sides.get(1).unwrap().feedback_deadline,
+        100 + 2 * FEEDBACK_LEDGERS - 1
+    );
+
+    advance_ledgers(&env, 2);
+    assert_game_error(
+        &client.try_claim_duel_timeout(&session_id, &player1),
+        Error::FeedbackNotExpired,
+    );
+    assert_eq!(token.balance(&client.address), 2 * BOND);
+}
+
+#[test]
+fn test_duel_opponent_that_never_commits_forfeits() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    let session_id = 45u32;
+
+    client.start_duel(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &DuelTiePolicy::Draw,
+    );
+    let result = client.try_claim_duel_timeout(&session_id, &player1);
+    assert_game_error(&result, Error::NoPendingGuess);
+    client.commit_duel_code(
+        &session_id,
+        &player1,
+        &commitment_from_4bytes(&env, [1, 2, 3, 4]),
+    );
+    let result = client.try_claim_duel_timeout(&session_id, &player1);
+    assert_game_error(&result, Error::FeedbackNotExpired);
+
+    advance_ledgers(&env, FEEDBACK_LEDGERS + 1);
+    client.claim_duel_timeout(&session_id, &player1);
+    assert_eq!(
+        client.get_duel(&session_id).winners,
+        vec![&env, player1.clone()]
+    );
+    assert_eq!(hub.get_last_outcome(&session_id), Some(true));
+    assert_eq!(token.balance(&player1), BOND);
+}
+
+#[test]
+fn test_duel_draw_returns_each_bond() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let token = setup_bond(&env, &client, &player1);
+    StellarAssetClient::new(&env, &token.address).mint(&player2, &BOND);
+    let session_id = 46u32;
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Draw,
+        codes,
+    );
+
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [6, 6, 5, 5],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [1, 2, 3, 4],
+    );
+    assert_eq!(client.get_duel(&session_id).winners.len(), 2);
+    assert_eq!(token.balance(&player1), BOND);
+    assert_eq!(token.balance(&player2), BOND);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_duel_that_can_draw_needs_hub_extensions() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub_addr = env.register(StandardGameHub, ());
+    let hub = StandardGameHubClient::new(&env, &hub_addr);
+    client.set_hub(&hub_addr);
+    client.set_hub_extensions(&false);
+    let session_id = 50u32;
+
+    for tie_policy in [DuelTiePolicy::Draw, DuelTiePolicy::MostExact] {
+        let result = client.try_start_duel(
+            &session_id,
+            &player1,
+            &player2,
+            &100_0000000,
+            &100_0000000,
+            &tie_policy,
+        );
+        assert_game_error(&result, Error::HubExtensionsRequired);
+    }
+
+    // Equal solves go to player 1 and end through the standard `end_game`
+    let codes = [duel_code([1, 2, 3, 4]), duel_code([6, 6, 5, 5])];
+    start_duel(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        DuelTiePolicy::Player1Wins,
+        codes,
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player1,
+        &player2,
+        &codes[1],
+        [6, 6, 5, 5],
+    );
+    duel_round(
+        &env,
+        &client,
+        session_id,
+        &player2,
+        &player1,
+        &codes[0],
+        [1, 2, 3, 4],
+    );
+
+    let duel = client.get_duel(&session_id);
+    assert!(duel.ended);
+    assert_eq!(duel.winners, vec![&env, player1]);
+    assert_eq!(hub.get_player1_won(&session_id), Some(true));
+}
+
+fn puzzle_round(
+    env: &Env,
+    client: &MyGameContractClient,
+    puzzle_id: u32,
+    breaker: &Address,
+    secret: &Code,
+    raw_guess: [u8; 4],
+) {
+    let guess_id =
+        client.submit_puzzle_guess(&puzzle_id, breaker, &BytesN::from_array(env, &raw_guess));
+    let feedback = secret.score(&duel_code(raw_guess));
+    let public_inputs = client.expected_puzzle_public_inputs(
+        &puzzle_id,
+        breaker,
+        &guess_id,
+        &feedback.exact(),
+        &feedback.partial(),
+    );
+    let proof_blob = build_proof_blob(env, &public_inputs, true);
+    client.submit_puzzle_feedback(
+        &puzzle_id,
+        breaker,
+        &guess_id,
+        &feedback.exact(),
+        &feedback.partial(),
+        &proof_blob,
+    );
+}
+
+#[test]
+fn test_puzzle_leaderboard_ranks_by_attempts_then_solve_order() {
+    let (env, client, hub, maker, _player2) = setup_test();
+    let puzzle_id = 7u32;
+    let secret = duel_code([3, 1, 4, 1]);
+    let (first, second, third, fourth) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    client.create_puzzle(
+        &puzzle_id,
+        &maker,
+        &commitment_from_4bytes(&env, secret.pegs()),
+    );
+
+    puzzle_round(&env, &client, puzzle_id, &first, &secret, [1, 1, 2, 2]);
+    puzzle_round(&env, &client, puzzle_id, &first, &secret, [3, 1, 4, 1]);
+    puzzle_round(&env, &client, puzzle_id, &second, &secret, [3, 1, 4, 1]);
+    puzzle_round(&env, &client, puzzle_id, &third, &secret, [6, 6, 6, 6]);
+    puzzle_round(&env, &client, puzzle_id, &fourth, &secret, [5, 5, 5, 5]);
+    puzzle_round(&env, &client, puzzle_id, &third, &secret, [3, 1, 4, 1]);
+
+    let board = client.get_leaderboard(&puzzle_id);
+    let ranked: std::vec::Vec<(Address, u32)> = board
+        .iter()
+        .map(|entry| (entry.breaker, entry.attempts))
+        .collect();
+    assert_eq!(
+        ranked,
+        std::vec![(second.clone(), 1), (first.clone(), 2), (third.clone(), 2)]
+    );
+
+    let puzzle = client.get_puzzle(&puzzle_id);
+    assert_eq!((puzzle.breakers, puzzle.solvers), (4, 3));
+    let track = client.get_puzzle_track(&puzzle_id, &first);
+    assert_eq!(
+        (track.attempts_used, track.solved_at, track.pending_guess_id),
+        (2, 2, None)
+    );
+    let turn = client.get_puzzle_turn(&puzzle_id, &fourth, &0);
+    assert_eq!((turn.exact, turn.partial), (0, 0));
+    assert!(turn.proof_hash.is_some());
+
+    let result = client.try_submit_puzzle_guess(
+        &puzzle_id,
+        &first,
+        &BytesN::from_array(&env, &[1, 1, 1, 1]),
+    );
+    assert_game_error(&result, Error::GameAlreadyEnded);
+    // Puzzles never touch the hub
+    assert_eq!(hub.get_end_count(&puzzle_id), 0);
+}
+
+#[test]
+fn test_puzzle_proofs_are_bound_to_breaker() {
+    let (env, client, _hub, maker, _player2) = setup_test();
+    let puzzle_id = 8u32;
+    let commitment = commitment_from_4bytes(&env, [3, 1, 4, 1]);
+    let guess = BytesN::from_array(&env, &[1, 1, 2, 2]);
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    let result = client.try_submit_puzzle_guess(&puzzle_id, &alice, &guess);
+    assert_game_error(&result, Error::GameNotFound);
+    client.create_puzzle(&puzzle_id, &maker, &commitment);
+    let result = client.try_create_puzzle(&puzzle_id, &maker, &commitment);
+    assert_game_error(&result, Error::CommitmentAlreadySet);
+
+    assert_eq!(client.submit_puzzle_guess(&puzzle_id, &alice, &guess), 0);
+    assert_eq!(client.submit_puzzle_guess(&puzzle_id, &bob, &guess), 0);
+    let result = client.try_submit_puzzle_guess(&puzzle_id, &bob, &guess);
+    assert_game_error(&result, Error::GuessPendingFeedback);
+
+    // Same puzzle, guess id, guess and feedback, yet the inputs differ per breaker
+    let alice_inputs = client.expected_puzzle_public_inputs(&puzzle_id, &alice, &0, &1, &0);
+    let bob_inputs = client.expected_puzzle_public_inputs(&puzzle_id, &bob, &0, &1, &0);
+    assert_ne!(alice_inputs, bob_inputs);
+    assert_eq!(alice_inputs.slice(32..), bob_inputs.slice(32..));
+
+    let alice_proof = build_proof_blob(&env, &alice_inputs, true);
+    let result = client.try_submit_puzzle_feedback(&puzzle_id, &bob, &0, &1, &0, &alice_proof);
+    assert_game_error(&result, Error::InvalidPublicInputs);
+    let session_inputs = build_public_inputs(&env, puzzle_id, 0, &commitment, &guess, 1, 0);
+    let result = client.try_submit_puzzle_feedback(
+        &puzzle_id,
+        &alice,
+        &0,
+        &1,
+        &0,
+        &build_proof_blob(&env, &session_inputs, true),
+    );
+    assert_game_error(&result, Error::InvalidPublicInputs);
+    let result = client.try_submit_puzzle_feedback(
+        &puzzle_id,
+        &alice,
+        &0,
+        &1,
+        &0,
+        &build_proof_blob(&env, &alice_inputs, false),
+    );
+    assert_game_error(&result, Error::InvalidProof);
+
+    client.submit_puzzle_feedback(&puzzle_id, &alice, &0, &1, &0, &alice_proof);
+    let result = client.try_submit_puzzle_feedback(&puzzle_id, &alice, &0, &1, &0, &alice_proof);
+    assert_game_error(&result, Error::NoPendingGuess);
+    assert_eq!(
+        client.get_puzzle_track(&puzzle_id, &bob).pending_guess_id,
+        Some(0)
+    );
+    assert_eq!(client.get_leaderboard(&puzzle_id).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Cannot break your own puzzle")]
+fn test_puzzle_maker_cannot_break() {
+    let (env, client, _hub, maker, _player2) = setup_test();
+    client.create_puzzle(&9, &maker, &commitment_from_4bytes(&env, [3, 1, 4, 1]));
+    client.submit_puzzle_guess(&9, &maker, &BytesN::from_array(&env, &[1, 1, 2, 2]));
+}
+
+fn move_scope(env: &Env) -> Vec<Symbol> {
+    vec![
+        env,
+        Symbol::new(env, "submit_guess"),
+        Symbol::new(env, "submit_feedback_proof"),
+    ]
+}
+
+#[test]
+fn test_session_keys_play_moves_for_players() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 40u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+
+    let breaker_key = Address::generate(&env);
+    let maker_key = Address::generate(&env);
+    client.register_session_key(&session_id, &player2, &breaker_key, &move_scope(&env), &200);
+    client.register_session_key(&session_id, &player1, &maker_key, &move_scope(&env), &200);
+    assert_eq!(
+        client.get_session_key(&session_id, &player2),
+        Some(SessionKey {
+            key: breaker_key.clone(),
+            functions: move_scope(&env),
+            expires_at: 200
+        })
+    );
+
+    let guess_id = client.submit_guess_as(
+        &session_id,
+        &breaker_key,
+        &BytesN::from_array(&env, &[1, 1, 2, 2]),
+    );
+    assert_eq!(env.auths()[0].0, breaker_key);
+
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &1, &1);
+    client.submit_feedback_proof_as(
+        &session_id,
+        &maker_key,
+        &guess_id,
+        &1,
+        &1,
+        &build_proof_blob(&env, &public_inputs, true),
+    );
+    assert_eq!(env.auths()[0].0, maker_key);
+
+    // The player can still move with their own account, directly or as the signer
+    client.submit_guess_as(
+        &session_id,
+        &player2,
+        &BytesN::from_array(&env, &[1, 2, 3, 5]),
+    );
+    assert_eq!(env.auths()[0].0, player2);
+    assert_eq!(answer_pending(&env, &client, session_id, 3, 0), Ok(()));
+    client.submit_guess(&session_id, &BytesN::from_array(&env, &[1, 2, 3, 6]));
+    assert_eq!(env.auths()[0].0, player2);
+}
+
+#[test]
+fn test_session_keys_are_scoped_expire_and_revoke() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 41u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    let guess = BytesN::from_array(&env, &[1, 1, 2, 2]);
+
+    // Keys only act for the player and session they were registered for
+    let breaker_key = Address::generate(&env);
+    client.register_session_key(
+        &session_id,
+        &player2,
+        &breaker_key,
+        &vec![&env, Symbol::new(&env, "submit_guess")],
+        &150,
+    );
+    let stranger = Address::generate(&env);
+    assert_game_error(
+        &client.try_submit_guess_as(&session_id, &stranger, &guess),
+        Error::SessionKeyInvalid,
+    );
+    client.start_game(&42, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&42, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    assert_game_error(
+        &client.try_submit_guess_as(&42, &breaker_key, &guess),
+        Error::SessionKeyInvalid,
+    );
+
+    let guess_id = client.submit_guess_as(&session_id, &breaker_key, &guess);
+    let public_inputs = client.expected_public_inputs(&session_id, &guess_id, &1, &1);
+    let proof_blob = build_proof_blob(&env, &public_inputs, true);
+    assert_game_error(
+        &client.try_submit_feedback_proof_as(
+            &session_id,
+            &breaker_key,
+            &guess_id,
+            &1,
+            &1,
+            &proof_blob,
+        ),
+        Error::SessionKeyInvalid,
+    );
+
+    // Out-of-scope: the breaker's key cannot answer for the codemaker either
+    let maker_key = Address::generate(&env);
+    client.register_session_key(
+        &session_id,
+        &player1,
+        &maker_key,
+        &vec![&env, Symbol::new(&env, "submit_guess")],
+        &150,
+    );
+    assert_game_error(
+        &client.try_submit_feedback_proof_as(
+            &session_id,
+            &maker_key,
+            &guess_id,
+            &1,
+            &1,
+            &proof_blob,
+        ),
+        Error::SessionKeyInvalid,
+    );
+    client.submit_feedback_proof(&session_id, &guess_id, &1, &1, &proof_blob);
+
+    advance_ledgers(&env, 51);
+    assert_game_error(
+        &client.try_submit_guess_as(&session_id, &breaker_key, &guess),
+        Error::SessionKeyExpired,
+    );
+
+    client.register_session_key(&session_id, &player2, &breaker_key, &move_scope(&env), &300);
+    client.revoke_session_key(&session_id, &player2);
+    assert_eq!(client.get_session_key(&session_id, &player2), None);
+    assert_game_error(
+        &client.try_submit_guess_as(&session_id, &breaker_key, &guess),
+        Error::SessionKeyInvalid,
+    );
+}
+
+#[test]
+fn test_session_keys_answer_batches_aggregates_and_hints() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 57u32;
+    client.set_queue_depth(&3);
+    client.set_batch_verifier(&client.get_verifier());
+    setup_hints(&client);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    for raw_guess in [[5, 5, 6, 6], [1, 1, 2, 2]] {
+        client.submit_guess(&session_id, &BytesN::from_array(&env, &raw_guess));
+    }
+
+    let maker_key = Address::generate(&env);
+    let scope = vec![
+        &env,
+        Symbol::new(&env, "submit_feedback_batch"),
+        Symbol::new(&env, "submit_feedback_aggregate"),
+        Symbol::new(&env, "answer_hint"),
+    ];
+    client.register_session_key(&session_id, &player1, &maker_key, &scope, &200);
+    let breaker_key = Address::generate(&env);
+    client.register_session_key(&session_id, &player2, &breaker_key, &move_scope(&env), &200);
+
+    client.submit_feedback_batch_as(
+        &session_id,
+        &maker_key,
+        &vec![&env, queued_answer(&env, &client, session_id, 0, 0, 0)],
+    );
+    assert_eq!(env.auths()[0].0, maker_key);
+    let feedback = vec![
+        &env,
+        FeedbackPair {
+            exact: 1,
+            partial: 1,
+        },
+    ];
+    client.submit_feedback_aggregate_as(
+        &session_id,
+        &maker_key,
+        &1,
+        &feedback,
+        &aggregate_blob(&env, &client, session_id, 1, &feedback),
+    );
+    assert_eq!(env.auths()[0].0, maker_key);
+    assert_eq!(client.get_game(&session_id).attempts_used, 2);
+
+    let hint_id = client.request_hint(&session_id, &HintKind::ColorCount, &0, &3);
+    let public_inputs = client.expected_hint_public_inputs(&session_id, &hint_id, &1);
+    let proof_blob = build_proof_blob(&env, &public_inputs, true);
+    let result = client.try_answer_hint_as(&session_id, &breaker_key, &hint_id, &1, &proof_blob);
+    assert_game_error(&result, Error::SessionKeyInvalid);
+    client.answer_hint_as(&session_id, &maker_key, &hint_id, &1, &proof_blob);
+    assert_eq!(env.auths()[0].0, maker_key);
+    assert_eq!(client.get_hint(&session_id, &hint_id).answer, 1);
+}
+
+#[test]
+fn test_session_key_registration_is_validated() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 43u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let key = Address::generate(&env);
+
+    let outsider = Address::generate(&env);
+    assert_game_error(
+        &client.try_register_session_key(&session_id, &outsider, &key, &move_scope(&env), &200),
+        Error::NotPlayer,
+    );
+    assert_game_error(
+        &client.try_register_session_key(&session_id, &player2, &player2, &move_scope(&env), &200),
+        Error::InvalidConfig,
+    );
+    assert_game_error(
+        &client.try_register_session_key(&session_id, &player2, &key, &move_scope(&env), &100),
+        Error::InvalidConfig,
+    );
+    assert_game_error(
+        &client.try_register_session_key(&session_id, &player2, &key, &Vec::new(&env), &200),
+        Error::InvalidConfig,
+    );
+    let scope = vec![
+        &env,
+        Symbol::new(&env, "submit_guess"),
+        Symbol::new(&env, "reveal_secret"),
+    ];
+    assert_game_error(
+        &client.try_register_session_key(&session_id, &player2, &key, &scope, &200),
+        Error::InvalidConfig,
+    );
+    assert_game_error(
+        &client.try_register_session_key(&99, &player2, &key, &move_scope(&env), &200),
+        Error::GameNotFound,
+    );
+    assert_eq!(client.get_session_key(&session_id, &player2), None);
+
+    // Revoking needs a live session the caller plays in
+    assert_game_error(
+        &client.try_revoke_session_key(&99, &player2),
+        Error::GameNotFound,
+    );
+    assert_game_error(
+        &client.try_revoke_session_key(&session_id, &outsider),
+        Error::NotPlayer,
+    );
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    answer(&env, &client, session_id, [1, 2, 3, 4], 4, 0).unwrap();
+    assert_game_error(
+        &client.try_revoke_session_key(&session_id, &player2),
+        Error::GameAlreadyEnded,
+    );
+}
+
+#[test]
+fn test_session_keys_registered_at_start() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 49u32;
+    let breaker_key = SessionKey {
+        key: Address::generate(&env),
+        functions: move_scope(&env),
+        expires_at: 200,
+    };
+
+    client.start_game_with_session_keys(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+        &Some(breaker_key.clone()),
+    );
+    // Player 2 signs for the key together with their points; player 1 signs as for start_game
+    let auths = env.auths();
+    let args_of = |player: &Address| match &auths
+        .iter()
+        .find(|(address, _)| address == player)
+        .unwrap()
+        .1
+        .function
+    {
+        AuthorizedFunction::Contract((_, _, args)) => args.clone(),
+        _ => panic!("unexpected auth"),
+    };
+    assert_eq!(
+        args_of(&player1),
+        vec![
+            &env,
+            session_id.into_val(&env),
+            100_0000000i128.into_val(&env)
+        ]
+    );
+    assert_eq!(
+        args_of(&player2),
+        vec![
+            &env,
+            session_id.into_val(&env),
+            100_0000000i128.into_val(&env),
+            breaker_key.clone().into_val(&env)
+        ]
+    );
+    assert_eq!(
+        client.get_session_key(&session_id, &player2),
+        Some(breaker_key.clone())
+    );
+    assert_eq!(client.get_session_key(&session_id, &player1), None);
+
+    client.commit_code(&session_id, &commitment_from_4bytes(&env, [1, 2, 3, 4]));
+    client.submit_guess_as(
+        &session_id,
+        &breaker_key.key,
+        &BytesN::from_array(&env, &[1, 1, 2, 2]),
+    );
+    assert_eq!(env.auths()[0].0, breaker_key.key);
+
+    // A bad key fails the whole start
+    let bad_key = SessionKey {
+        expires_at: 0,
+        ..breaker_key
+    };
+    let result = client.try_start_game_with_session_keys(
+        &(session_id + 1),
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &Some(bad_key),
+        &None,
+    );
+    assert_game_error(&result, Error::InvalidConfig);
+    assert_game_error(&client.try_get_game(&(session_id + 1)), Error::GameNotFound);
+}
+
 #[test]
 fn test_upgrade_function_exists() {
     let env = Env::default();
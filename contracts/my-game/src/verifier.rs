@@ -0,0 +1,230 @@
+//! Proof verification backends for feedback proofs.
+//!
+//! A game, duel or puzzle verifies with the backend configured when it started:
+//! - `UltraHonk` - cross-contract call to the `UltraHonkVerifier` at `VerifierAddress`.
+//! - `Groth16` - pairing check on the BN254 host functions against the VK in instance
+//!   storage. The proof is `A (G1) || B (G2) || C (G1)` in the host's uncompressed
+//!   encoding, 256 bytes after the public inputs.
+//!
+//! Groth16 proofs are malleable: anyone holding one can derive other valid proofs of the
+//! same statement. So Groth16 proofs go into the proof registry under the hash of their
+//! public inputs, and UltraHonk proofs under the hash of the proof bytes.
+
+use soroban_sdk::{
+    contracttype,
+    crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr},
+    vec, Address, Bytes, BytesN, Env, Vec,
+};
+
+use crate::{DataKey, Error, MyGameContract};
+
+const GROTH16_PROOF_LEN: u32 = 256;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifierBackend {
+    UltraHonk,
+    Groth16,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Groth16VerifyingKey {
+    pub alpha: BytesN<64>,
+    pub beta: BytesN<128>,
+    pub gamma: BytesN<128>,
+    pub delta: BytesN<128>,
+    pub ic: Vec<BytesN<64>>, // One point per public input, plus the constant term first
+}
+
+pub(crate) trait ProofBackend: Sized {
+    fn load(env: &Env) -> Result<Self, Error>;
+    fn split_proof_blob(proof_blob: &Bytes) -> Result<(Bytes, Bytes), Error>;
+    // The proof's registry hash once accepted
+    fn verify(
+        &self,
+        env: &Env,
+        public_inputs: &Bytes,
+        proof_bytes: &Bytes,
+    ) -> Result<BytesN<32>, Error>;
+    // The fault a known-good proof runs into, `None` if the backend is healthy
+    fn probe(env: &Env) -> Result<Option<Error>, Error>;
+}
+
+pub(crate) struct UltraHonk {
+    verifier: Address,
+}
+
+impl ProofBackend for UltraHonk {
+    fn load(env: &Env) -> Result<Self, Error> {
+        let verifier = env
+            .storage()
+            .instance()
+            .get(&DataKey::VerifierAddress)
+            .ok_or(Error::VerifierNotSet)?;
+        Ok(UltraHonk { verifier })
+    }
+
+    fn split_proof_blob(proof_blob: &Bytes) -> Result<(Bytes, Bytes), Error> {
+        MyGameContract::split_proof_blob(proof_blob)
+    }
+
+    fn verify(
+        &self,
+        env: &Env,
+        public_inputs: &Bytes,
+        proof_bytes: &Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        MyGameContract::verify_proof(env, &self.verifier, public_inputs, proof_bytes)
+    }
+
+    fn probe(env: &Env) -> Result<Option<Error>, Error> {
+        match Self::load(env) {
+            Ok(backend) => probe_verifier(env, &backend.verifier),
+            Err(error) => Ok(Some(error)),
+        }
+    }
+}
+
+pub(crate) struct Groth16 {
+    vk: Groth16VerifyingKey,
+}
+
+impl ProofBackend for Groth16 {
+    fn load(env: &Env) -> Result<Self, Error> {
+        let vk = env
+            .storage()
+            .instance()
+            .get(&DataKey::Groth16Vk)
+            .ok_or(Error::VerifierNotSet)?;
+        Ok(Groth16 { vk })
+    }
+
+    // Same 4-byte header as UltraHonk blobs, then public inputs, then the fixed-size proof
+    fn split_proof_blob(proof_blob: &Bytes) -> Result<(Bytes, Bytes), Error> {
+        let total_len = proof_blob.len();
+        if total_len < 4 + GROTH16_PROOF_LEN {
+            return Err(Error::InvalidProofBlob);
+        }
+        let pi_end = total_len - GROTH16_PROOF_LEN;
+        if !(pi_end - 4).is_multiple_of(32) {
+            return Err(Error::InvalidProofBlob);
+        }
+        Ok((
+            proof_blob.slice(4..pi_end),
+            proof_blob.slice(pi_end..total_len),
+        ))
+    }
+
+    // e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+    fn verify(
+        &self,
+        env: &Env,
+        public_inputs: &Bytes,
+        proof_bytes: &Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        let inputs = public_inputs.len() / 32;
+        if inputs + 1 != self.vk.ic.len() {
+            return Err(Error::InvalidPublicInputs);
+        }
+        if proof_bytes.len() != GROTH16_PROOF_LEN {
+            return Err(Error::InvalidProofBlob);
+        }
+
+        let proof_hash = MyGameContract::check_proof_unused(env, public_inputs)?;
+        let bn254 = env.crypto().bn254();
+        let mut vk_x = Bn254G1Affine::from_bytes(self.vk.ic.get(0).unwrap());
+        for i in 0..inputs {
+            let input: BytesN<32> = public_inputs
+                .slice(i * 32..(i + 1) * 32)
+                .try_into()
+                .map_err(|_| Error::InvalidPublicInputs)?;
+            let point = Bn254G1Affine::from_bytes(self.vk.ic.get(i + 1).unwrap());
+            vk_x = bn254.g1_add(&vk_x, &bn254.g1_mul(&point, &Fr::from_bytes(input)));
+        }
+
+        let a = g1_at(proof_bytes, 0)?;
+        let b = Bn254G2Affine::from_bytes(
+            proof_bytes
+                .slice(64..192)
+                .try_into()
+                .map_err(|_| Error::InvalidProofBlob)?,
+        );
+        let c = g1_at(proof_bytes, 192)?;
+
+        let g1 = vec![
+            env,
+            -a,
+            Bn254G1Affine::from_bytes(self.vk.alpha.clone()),
+            vk_x,
+            c,
+        ];
+        let g2 = vec![
+            env,
+            b,
+            Bn254G2Affine::from_bytes(self.vk.beta.clone()),
+            Bn254G2Affine::from_bytes(self.vk.gamma.clone()),
+            Bn254G2Affine::from_bytes(self.vk.delta.clone()),
+        ];
+        if bn254.pairing_check(g1, g2) {
+            MyGameContract::mark_proof_used(env, proof_hash.clone());
+            Ok(proof_hash)
+        } else {
+            Err(Error::InvalidProof)
+        }
+    }
+
+    fn probe(env: &Env) -> Result<Option<Error>, Error> {
+        Ok(Self::load(env).err())
+    }
+}
+
+// Any error on the proof registered with `set_verifier_probe` is the verifier's fault.
+// The probe goes around the proof registry, so it can be run again.
+pub(crate) fn probe_verifier(env: &Env, verifier: &Address) -> Result<Option<Error>, Error> {
+    let probe: Bytes = env
+        .storage()
+        .instance()
+        .get(&DataKey::VerifierProbe(verifier.clone()))
+        .ok_or(Error::VerifierProbeNotSet)?;
+    let (public_inputs, proof_bytes) = MyGameContract::split_proof_blob(&probe)?;
+    Ok(MyGameContract::call_verifier(env, verifier, &public_inputs, &proof_bytes).err())
+}
+
+fn g1_at(proof_bytes: &Bytes, offset: u32) -> Result<Bn254G1Affine, Error> {
+    let point: BytesN<64> = proof_bytes
+        .slice(offset..offset + 64)
+        .try_into()
+        .map_err(|_| Error::InvalidProofBlob)?;
+    Ok(Bn254G1Affine::from_bytes(point))
+}
+
+impl VerifierBackend {
+    pub(crate) fn split_proof_blob(self, proof_blob: &Bytes) -> Result<(Bytes, Bytes), Error> {
+        match self {
+            VerifierBackend::UltraHonk => UltraHonk::split_proof_blob(proof_blob),
+            VerifierBackend::Groth16 => Groth16::split_proof_blob(proof_blob),
+        }
+    }
+
+    pub(crate) fn probe(self, env: &Env) -> Result<Option<Error>, Error> {
+        match self {
+            VerifierBackend::UltraHonk => UltraHonk::probe(env),
+            VerifierBackend::Groth16 => Groth16::probe(env),
+        }
+    }
+
+    pub(crate) fn verify(
+        self,
+        env: &Env,
+        public_inputs: &Bytes,
+        proof_bytes: &Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        match self {
+            VerifierBackend::UltraHonk => {
+                UltraHonk::load(env)?.verify(env, public_inputs, proof_bytes)
+            }
+            VerifierBackend::Groth16 => Groth16::load(env)?.verify(env, public_inputs, proof_bytes),
+        }
+    }
+}
@@ -2,15 +2,34 @@
 
 //! # Number Guess Game
 //!
-//! A simple two-player guessing game where players guess a number between 1 and 10.
-//! The player whose guess is closest to the randomly generated number wins.
+//! A simple guessing game for 2-6 players where players guess a number in a range
+//! (1 to 10 unless configured otherwise). The player whose guess is closest to the
+//! randomly generated number wins.
+//!
+//! **Game Config:**
+//! A `GuessConfig` given at start sets the range and what happens when several guesses
+//! are equally close: the tied players share the win (`Draw`), or the earliest tied seat
+//! takes it (`Player1Wins`). Without a config the classic game is played: 1 to 10, where a
+//! two-player tie goes to player 1 and a tie at a larger table is shared.
+//!
+//! **Randomness:**
+//! Without a beacon, the winning number is seeded from the session ID, the guesses and the
+//! player addresses, which anyone can work out as soon as the last guess is in. When the admin
+//! configures a randomness beacon, the game instead requests a seed from the beacon once every
+//! player has guessed and `reveal_winner` waits for that seed to be finalized.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
+//! Two-seat games with a single winner use the Game Hub's two-player `start_game`/`end_game`
+//! session, which is all the Game Hub interface defines. Tables of 3-6 seats and shared
+//! wins (`start_game_multi`/`end_game_multi`) are extensions only some hubs implement, such
+//! as `mock-game-hub`. They are off until the admin confirms the configured hub supports
+//! them with `set_hub_extensions`; until then only two-seat games whose ties go to player 1
+//! can be started.
 
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl, contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, Vec, contract, contractclient, contracterror, contractimpl, contracttype, vec
 };
 
 // Import GameHub contract interface
@@ -27,11 +46,31 @@ pub trait GameHub {
         player2_points: i128,
     );
 
+    // Extensions, only called when `set_hub_extensions` is on
+    fn start_game_multi(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    );
+
     fn end_game(
         env: Env,
         session_id: u32,
         player1_won: bool
     );
+
+    fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>);
+}
+
+// Import RandomnessBeacon contract interface
+// Seeds are requested and read per (this contract, session ID)
+#[contractclient(name = "BeaconClient")]
+pub trait RandomnessBeacon {
+    fn request_seed(env: Env, requester: Address, request_id: u32);
+
+    fn get_seed(env: Env, requester: Address, request_id: u32) -> Option<BytesN<32>>;
 }
 
 // ============================================================================
@@ -47,23 +86,57 @@ pub enum Error {
     AlreadyGuessed = 3,
     BothPlayersNotGuessed = 4,
     GameAlreadyEnded = 5,
+    SelfPlay = 6,
+    InvalidTableSize = 7,
+    InvalidRange = 8,
+    GuessOutOfRange = 9,
+    SeedNotReady = 10,
+    ChannelNotFound = 11,
+    ChannelExists = 12,
+    ChannelOpen = 13,
+    InvalidChannelState = 14,
+    StaleChannelState = 15,
+    ChallengeClosed = 16,
+    ChallengeOpen = 17,
+    NoChannelState = 18,
+    HubExtensionsRequired = 19,
 }
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TiePolicy {
+    Draw,        // Every equally close player wins and the payout is shared
+    Player1Wins, // The earliest equally close seat wins
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuessConfig {
+    pub min: u32, // Lowest number that can be guessed or drawn
+    pub max: u32, // Highest number that can be guessed or drawn
+    pub tie_policy: TiePolicy,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Seat {
+    pub player: Address,
+    pub points: i128,
+    pub guess: Option<u32>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
-    pub player1: Address,
-    pub player2: Address,
-    pub player1_points: i128,
-    pub player2_points: i128,
-    pub player1_guess: Option<u32>,
-    pub player2_guess: Option<u32>,
+    pub seats: Vec<Seat>,
+    pub config: GuessConfig,
     pub winning_number: Option<u32>,
-    pub winner: Option<Address>,
+    pub winners: Vec<Address>,   // Empty while the game is running
+    pub beacon: Option<Address>, // Randomness beacon in force when the game started
 }
 
 #[contracttype]
@@ -71,6 +144,8 @@ pub struct Game {
 pub enum DataKey {
     Game(u32),
     GameHubAddress,
+    BeaconAddress,
+    HubExtensions,
     Admin,
 }
 
@@ -84,6 +159,28 @@ pub enum DataKey {
 /// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
 const GAME_TTL_LEDGERS: u32 = 518_400;
 
+/// Smallest and largest table a game can be started with
+const MIN_SEATS: u32 = 2;
+const MAX_SEATS: u32 = 6;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// The classic game: guess a number from 1 to 10.
+/// Two-player ties go to player 1; ties at larger tables are shared.
+fn classic_config(seat_count: u32) -> GuessConfig {
+    GuessConfig {
+        min: 1,
+        max: 10,
+        tie_policy: if seat_count == 2 {
+            TiePolicy::Player1Wins
+        } else {
+            TiePolicy::Draw
+        },
+    }
+}
+
 // ============================================================================
 // Contract Definition
 // ============================================================================
@@ -118,6 +215,7 @@ impl NumberGuessContract {
     /// * `player2` - Address of second player
     /// * `player1_points` - Points amount committed by player 1
     /// * `player2_points` - Points amount committed by player 2
+    /// * `config` - Number range and tie policy, or `None` for the classic game
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -125,47 +223,57 @@ impl NumberGuessContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
+        config: Option<GuessConfig>,
     ) -> Result<(), Error> {
-        // Prevent self-play: Player 1 and Player 2 must be different
-        if player1 == player2 {
-            panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
-        }
-
-        // Require authentication from both players (they consent to committing points)
-        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
-        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
-
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        let players = vec![&env, player1, player2];
+        let points = vec![&env, player1_points, player2_points];
+        Self::start_table(env, session_id, players, points, config)
+    }
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+    /// Start a new game at a table of 2-6 players with per-seat points.
+    /// This creates a session in the Game Hub and locks points before starting the game.
+    ///
+    /// **CRITICAL:** This method requires authorization from THIS contract (not players).
+    /// The Game Hub will call `game_id.require_auth()` which checks this contract's address.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `players` - Addresses of the players, in seat order
+    /// * `points` - Points amount committed by each player, in seat order
+    /// * `config` - Number range and tie policy, or `None` for the classic game
+    pub fn start_table(
+        env: Env,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+        config: Option<GuessConfig>,
+    ) -> Result<(), Error> {
+        let config = config.unwrap_or_else(|| classic_config(players.len()));
+        if config.min >= config.max {
+            return Err(Error::InvalidRange);
+        }
+        // A shared win needs the hub extensions
+        if config.tie_policy == TiePolicy::Draw && !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
 
-        // Call Game Hub to start the session and lock points
-        // This requires THIS contract's authorization (env.current_contract_address())
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+        Self::open_session(&env, session_id, &players, &points)?;
 
         // Create game (winning_number not set yet - will be generated in reveal_winner)
+        let mut seats = Vec::new(&env);
+        for i in 0..players.len() {
+            seats.push_back(Seat {
+                player: players.get_unchecked(i),
+                points: points.get_unchecked(i),
+                guess: None,
+            });
+        }
         let game = Game {
-            player1: player1.clone(),
-            player2: player2.clone(),
-            player1_points,
-            player2_points,
-            player1_guess: None,
-            player2_guess: None,
+            seats,
+            config,
             winning_number: None,
-            winner: None,
+            winners: Vec::new(&env),
+            beacon: Self::get_beacon(env.clone()),
         };
 
         // Store game in temporary storage with 30-day TTL
@@ -177,26 +285,20 @@ impl NumberGuessContract {
             .temporary()
             .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
 
-        // Event emitted by the Game Hub contract (GameStarted)
-
         Ok(())
     }
 
     /// Make a guess for the current game.
-    /// Players can guess a number between 1 and 10.
+    /// Players can guess a number within the game's range (1-10 by default).
+    /// When the game uses a randomness beacon, the last guess requests the seed.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     /// * `player` - Address of the player making the guess
-    /// * `guess` - The guessed number (1-10)
+    /// * `guess` - The guessed number
     pub fn make_guess(env: Env, session_id: u32, player: Address, guess: u32) -> Result<(), Error> {
         player.require_auth();
 
-        // Validate guess is in range
-        if guess < 1 || guess > 10 {
-            panic!("Guess must be between 1 and 10");
-        }
-
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -205,44 +307,58 @@ impl NumberGuessContract {
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
-        // Check game is still active (no winner yet)
-        if game.winner.is_some() {
+        // Check game is still active (no winners yet)
+        if !game.winners.is_empty() {
             return Err(Error::GameAlreadyEnded);
         }
+        channel::ensure_no_channel(&env, session_id)?;
 
-        // Update guess for the appropriate player
-        if player == game.player1 {
-            if game.player1_guess.is_some() {
-                return Err(Error::AlreadyGuessed);
-            }
-            game.player1_guess = Some(guess);
-        } else if player == game.player2 {
-            if game.player2_guess.is_some() {
-                return Err(Error::AlreadyGuessed);
-            }
-            game.player2_guess = Some(guess);
-        } else {
-            return Err(Error::NotPlayer);
+        // Validate guess is in range
+        if guess < game.config.min || guess > game.config.max {
+            return Err(Error::GuessOutOfRange);
         }
 
+        // Update guess for the player's seat
+        let seat_index = game
+            .seats
+            .iter()
+            .position(|seat| seat.player == player)
+            .ok_or(Error::NotPlayer)? as u32;
+        let mut seat = game.seats.get_unchecked(seat_index);
+        if seat.guess.is_some() {
+            return Err(Error::AlreadyGuessed);
+        }
+        seat.guess = Some(guess);
+        game.seats.set(seat_index, seat);
+
         // Store updated game in temporary storage
         env.storage().temporary().set(&key, &game);
 
+        // Every guess is locked in, so the seed can no longer be played around
+        if let Some(beacon) = &game.beacon {
+            if game.seats.iter().all(|seat| seat.guess.is_some()) {
+                BeaconClient::new(&env, beacon)
+                    .request_seed(&env.current_contract_address(), &session_id);
+            }
+        }
+
         // No event emitted - game state can be queried via get_game()
 
         Ok(())
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
-    /// Can only be called after both players have made their guesses.
-    /// This generates the winning number, determines the winner, and ends the session.
+    /// Reveal the winners of the game and submit outcome to GameHub.
+    /// Can only be called after every player has made their guess.
+    /// This generates the winning number, determines the winners, and ends the session.
+    /// The closest guess wins; equally close guesses are settled by the game's tie policy.
+    /// Games using a randomness beacon can only be revealed once the beacon seed is finalized.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `Vec<Address>` - Addresses of the winning players
+    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Vec<Address>, Error> {
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -251,21 +367,25 @@ impl NumberGuessContract {
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
-        // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+        // Check if game already ended (has winners)
+        if !game.winners.is_empty() {
+            return Ok(game.winners);
         }
+        // Channel games are settled with settle_channel
+        channel::ensure_no_channel(&env, session_id)?;
 
-        // Check both players have guessed
-        let guess1 = game.player1_guess.ok_or(Error::BothPlayersNotGuessed)?;
-        let guess2 = game.player2_guess.ok_or(Error::BothPlayersNotGuessed)?;
+        // Check every player has guessed
+        let mut guesses = Vec::new(&env);
+        for seat in game.seats.iter() {
+            guesses.push_back(seat.guess.ok_or(Error::BothPlayersNotGuessed)?);
+        }
 
-        // Generate random winning number between 1 and 10 using seeded PRNG
-        // This is done AFTER both players have committed their guesses
+        // Generate random winning number in the game's range using seeded PRNG
+        // This is done AFTER every player has committed their guess
         //
         // Seed components (all deterministic and identical between sim/submit):
         // 1. Session ID - unique per game, same between simulation and submission
-        // 2. Player addresses - both players contribute, same between sim/submit
+        // 2. Player addresses - every player contributes, same between sim/submit
         // 3. Guesses - committed before reveal, same between sim/submit
         //
         // Note: We do NOT include ledger sequence or timestamp because those differ
@@ -273,66 +393,52 @@ impl NumberGuessContract {
         //
         // This ensures:
         // - Same result between simulation and submission (fully deterministic)
-        // - Cannot be easily gamed (both players contribute to randomness)
-
-        // Build seed more efficiently using native arrays where possible
-        // Total: 12 bytes of fixed data (session_id + 2 guesses)
-        let mut fixed_data = [0u8; 12];
-        fixed_data[0..4].copy_from_slice(&session_id.to_be_bytes());
-        fixed_data[4..8].copy_from_slice(&guess1.to_be_bytes());
-        fixed_data[8..12].copy_from_slice(&guess2.to_be_bytes());
-
-        // Only use Bytes for the final concatenation with player addresses
-        let mut seed_bytes = Bytes::from_array(&env, &fixed_data);
-        seed_bytes.append(&game.player1.to_string().to_bytes());
-        seed_bytes.append(&game.player2.to_string().to_bytes());
-
-        let seed = env.crypto().keccak256(&seed_bytes);
+        // - Cannot be easily gamed (every player contributes to randomness)
+        //
+        // With a randomness beacon the beacon's seed is used as is.
+        let seed: BytesN<32> = match &game.beacon {
+            Some(beacon) => BeaconClient::new(&env, beacon)
+                .get_seed(&env.current_contract_address(), &session_id)
+                .ok_or(Error::SeedNotReady)?,
+            None => {
+                let mut seed_bytes = Bytes::from_array(&env, &session_id.to_be_bytes());
+                for guess in guesses.iter() {
+                    seed_bytes.append(&Bytes::from_array(&env, &guess.to_be_bytes()));
+                }
+                for seat in game.seats.iter() {
+                    seed_bytes.append(&seat.player.to_string().to_bytes());
+                }
+                env.crypto().keccak256(&seed_bytes).into()
+            }
+        };
         env.prng().seed(seed.into());
-        let winning_number = env.prng().gen_range::<u64>(1..=10) as u32;
+        let winning_number =
+            env.prng().gen_range::<u64>(game.config.min as u64..=game.config.max as u64) as u32;
         game.winning_number = Some(winning_number);
 
-        // Calculate distances
-        let distance1 = if guess1 > winning_number {
-            guess1 - winning_number
-        } else {
-            winning_number - guess1
-        };
-
-        let distance2 = if guess2 > winning_number {
-            guess2 - winning_number
-        } else {
-            winning_number - guess2
-        };
-
-        // Determine winner (if equal distance, player1 wins)
-        let winner = if distance1 <= distance2 {
-            game.player1.clone()
-        } else {
-            game.player2.clone()
-        };
+        // Find the closest guesses
+        let mut best = u32::MAX;
+        for guess in guesses.iter() {
+            best = best.min(guess.abs_diff(winning_number));
+        }
+        let mut winners = Vec::new(&env);
+        for i in 0..game.seats.len() {
+            if guesses.get_unchecked(i).abs_diff(winning_number) == best {
+                winners.push_back(game.seats.get_unchecked(i).player);
+                if game.config.tie_policy == TiePolicy::Player1Wins {
+                    break;
+                }
+            }
+        }
 
-        // Update game with winner (this marks the game as ended)
-        game.winner = Some(winner.clone());
+        // Update game with winners (this marks the game as ended)
+        game.winners = winners.clone();
         env.storage().temporary().set(&key, &game);
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        let player1 = game.seats.get_unchecked(0).player;
+        Self::close_session(&env, session_id, game.seats.len(), &player1, &winners);
 
-        // Call GameHub to end the session
-        // This unlocks points and updates standings
-        // Event emitted by the Game Hub contract (GameEnded)
-        let player1_won = winner == game.player1; // true if player1 won, false if player2 won
-        game_hub.end_game(&session_id, &player1_won);
-
-        Ok(winner)
+        Ok(winners)
     }
 
     /// Get game information.
@@ -350,6 +456,97 @@ impl NumberGuessContract {
             .ok_or(Error::GameNotFound)
     }
 
+    // ========================================================================
+    // Internal Helper Functions
+    // ========================================================================
+
+    /// Get a GameHub client for the configured hub
+    fn game_hub(env: &Env) -> GameHubClient<'_> {
+        let game_hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub address not set");
+        GameHubClient::new(env, &game_hub_addr)
+    }
+
+    /// Validate a table, collect every player's consent and open the Game Hub session.
+    /// Two-seat tables use the two-player session, larger tables the N-player session,
+    /// which needs the hub extensions.
+    fn open_session(
+        env: &Env,
+        session_id: u32,
+        players: &Vec<Address>,
+        points: &Vec<i128>,
+    ) -> Result<(), Error> {
+        if players.len() < MIN_SEATS || players.len() > MAX_SEATS || points.len() != players.len() {
+            return Err(Error::InvalidTableSize);
+        }
+        if players.len() > 2 && !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
+
+        // Prevent self-play: every seat must be taken by a different player
+        for i in 0..players.len() {
+            for j in (i + 1)..players.len() {
+                if players.get_unchecked(i) == players.get_unchecked(j) {
+                    return Err(Error::SelfPlay);
+                }
+            }
+        }
+
+        // Require authentication from every player (they consent to committing points)
+        for i in 0..players.len() {
+            players.get_unchecked(i).require_auth_for_args(vec![
+                env,
+                session_id.into_val(env),
+                points.get_unchecked(i).into_val(env),
+            ]);
+        }
+
+        // Call Game Hub to start the session and lock points
+        // This requires THIS contract's authorization (env.current_contract_address())
+        let game_hub = Self::game_hub(env);
+        if players.len() == 2 {
+            game_hub.start_game(
+                &env.current_contract_address(),
+                &session_id,
+                &players.get_unchecked(0),
+                &players.get_unchecked(1),
+                &points.get_unchecked(0),
+                &points.get_unchecked(1),
+            );
+        } else {
+            game_hub.start_game_multi(&env.current_contract_address(), &session_id, players, points);
+        }
+
+        // Event emitted by the Game Hub contract (GameStarted)
+
+        Ok(())
+    }
+
+    /// End a Game Hub session with the given winners.
+    /// A single winner at a two-seat table settles through the two-player session;
+    /// everything else (including a two-player draw) shares the payout through the N-player call.
+    fn close_session(
+        env: &Env,
+        session_id: u32,
+        seat_count: u32,
+        player1: &Address,
+        winners: &Vec<Address>,
+    ) {
+        // Call GameHub to end the session
+        // This unlocks points and updates standings
+        // Event emitted by the Game Hub contract (GameEnded)
+        let game_hub = Self::game_hub(env);
+        if seat_count == 2 && winners.len() == 1 {
+            let player1_won = winners.get_unchecked(0) == *player1;
+            game_hub.end_game(&session_id, &player1_won);
+        } else {
+            game_hub.end_game_multi(&session_id, winners);
+        }
+    }
+
     // ========================================================================
     // Admin Functions
     // ========================================================================
@@ -408,6 +605,63 @@ impl NumberGuessContract {
             .set(&DataKey::GameHubAddress, &new_hub);
     }
 
+    /// Get the randomness beacon used by newly started games
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The beacon contract address, or `None` if games seed themselves
+    pub fn get_beacon(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BeaconAddress)
+    }
+
+    /// Set the randomness beacon used by newly started games.
+    /// Games already in progress keep the beacon (or lack of one) they started with.
+    ///
+    /// # Arguments
+    /// * `beacon` - The beacon contract address, or `None` to go back to self-seeded games
+    pub fn set_beacon(env: Env, beacon: Option<Address>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        match beacon {
+            Some(beacon) => env.storage().instance().set(&DataKey::BeaconAddress, &beacon),
+            None => env.storage().instance().remove(&DataKey::BeaconAddress),
+        }
+    }
+
+    /// Whether the configured Game Hub supports the multi-seat extensions
+    ///
+    /// # Returns
+    /// * `bool` - True once the admin has turned the extensions on (off by default)
+    pub fn get_hub_extensions(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::HubExtensions)
+            .unwrap_or(false)
+    }
+
+    /// Turn the Game Hub extensions on or off.
+    /// Only turn them on for a hub that implements `start_game_multi` and `end_game_multi`;
+    /// the standard Game Hub only has `start_game` and `end_game`.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether tables of 3+ seats and shared wins are allowed
+    pub fn set_hub_extensions(env: Env, enabled: bool) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::HubExtensions, &enabled);
+    }
+
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
@@ -424,6 +678,14 @@ impl NumberGuessContract {
     }
 }
 
+// ============================================================================
+// Channels
+// ============================================================================
+
+mod channel;
+
+pub use state_channel::{Channel, ChannelState};
+
 // ============================================================================
 // Tests
 // ============================================================================
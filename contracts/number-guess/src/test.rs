@@ -6,14 +6,24 @@
 // Note: These tests use a minimal mock for isolation and speed.
 // For full integration tests with the real Game Hub contract, see the platform repo.
 
-use crate::{Error, NumberGuessContract, NumberGuessContractClient};
+use crate::{
+    ChannelState, Error, Game, GuessConfig, NumberGuessContract, NumberGuessContractClient, Seat,
+    TiePolicy,
+};
+use ed25519_dalek::{Signer as _, SigningKey};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, BytesN, Env, Vec};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
 // ============================================================================
 
+#[contracttype]
+pub enum HubDataKey {
+    Player1Won(u32),
+    Winners(u32),
+}
+
 #[contract]
 pub struct MockGameHub;
 
@@ -31,15 +41,119 @@ impl MockGameHub {
         // Mock implementation - does nothing
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+    pub fn start_game_multi(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _players: Vec<Address>,
+        _points: Vec<i128>,
+    ) {
         // Mock implementation - does nothing
     }
 
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        // Track the result so tests can check which session call was used
+        env.storage()
+            .instance()
+            .set(&HubDataKey::Player1Won(session_id), &player1_won);
+    }
+
+    pub fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>) {
+        // Track winners so tests can check multi-winner payouts
+        env.storage()
+            .instance()
+            .set(&HubDataKey::Winners(session_id), &winners);
+    }
+
+    pub fn get_player1_won(env: Env, session_id: u32) -> Option<bool> {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::Player1Won(session_id))
+    }
+
+    pub fn get_winners(env: Env, session_id: u32) -> Option<Vec<Address>> {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::Winners(session_id))
+    }
+
     pub fn add_game(_env: Env, _game_address: Address) {
         // Mock implementation - does nothing
     }
 }
 
+// ============================================================================
+// Standard GameHub for Unit Testing
+// ============================================================================
+
+// Only the two-player Game Hub interface, without any of the extensions
+#[contract]
+pub struct StandardGameHub;
+
+#[contractimpl]
+impl StandardGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        env.storage().instance().set(&session_id, &player1_won);
+    }
+
+    pub fn get_player1_won(env: Env, session_id: u32) -> Option<bool> {
+        env.storage().instance().get(&session_id)
+    }
+}
+
+// ============================================================================
+// Mock RandomnessBeacon for Unit Testing
+// ============================================================================
+
+#[contracttype]
+pub enum BeaconDataKey {
+    Requested(Address, u32),
+    Seed(Address, u32),
+}
+
+#[contract]
+pub struct MockBeacon;
+
+#[contractimpl]
+impl MockBeacon {
+    pub fn request_seed(env: Env, requester: Address, request_id: u32) {
+        // Track requests so tests can check when the seed was asked for
+        env.storage()
+            .instance()
+            .set(&BeaconDataKey::Requested(requester, request_id), &true);
+    }
+
+    pub fn get_seed(env: Env, requester: Address, request_id: u32) -> Option<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&BeaconDataKey::Seed(requester, request_id))
+    }
+
+    pub fn was_requested(env: Env, requester: Address, request_id: u32) -> bool {
+        env.storage()
+            .instance()
+            .has(&BeaconDataKey::Requested(requester, request_id))
+    }
+
+    /// Stand-in for a finalized commit-reveal round
+    pub fn finalize(env: Env, requester: Address, request_id: u32, seed: BytesN<32>) {
+        env.storage()
+            .instance()
+            .set(&BeaconDataKey::Seed(requester, request_id), &seed);
+    }
+}
+
 // ============================================================================
 // Test Helpers
 // ============================================================================
@@ -80,12 +194,29 @@ fn setup_test() -> (
     // Register number-guess as a whitelisted game (mock does nothing)
     game_hub.add_game(&contract_id);
 
+    // The mock implements the multi-seat extensions
+    client.set_hub_extensions(&true);
+
     let player1 = Address::generate(&env);
     let player2 = Address::generate(&env);
 
     (env, client, game_hub, player1, player2)
 }
 
+/// Get a seat of a game by index
+fn seat(game: &Game, index: u32) -> Seat {
+    game.seats.get(index).unwrap()
+}
+
+fn three_players(env: &Env) -> Vec<Address> {
+    vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ]
+}
+
 /// Assert that a Result contains a specific number_guess error
 ///
 /// This helper provides type-safe error assertions following Stellar/Soroban best practices.
@@ -151,32 +282,36 @@ fn test_complete_game() {
     let points = 100_0000000;
 
     // Start game
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None);
 
     // Get game to verify state
     let game = client.get_game(&session_id);
     assert!(game.winning_number.is_none()); // Winning number not set yet
-    assert!(game.winner.is_none()); // Game is still active
-    assert_eq!(game.player1, player1);
-    assert_eq!(game.player2, player2);
-    assert_eq!(game.player1_points, points);
-    assert_eq!(game.player2_points, points);
+    assert!(game.winners.is_empty()); // Game is still active
+    assert_eq!(seat(&game, 0).player, player1);
+    assert_eq!(seat(&game, 1).player, player2);
+    assert_eq!(seat(&game, 0).points, points);
+    assert_eq!(seat(&game, 1).points, points);
+    assert_eq!(game.config.min, 1);
+    assert_eq!(game.config.max, 10);
+    assert_eq!(game.config.tie_policy, TiePolicy::Player1Wins);
 
     // Make guesses
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &7);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    let winners = client.reveal_winner(&session_id);
+    assert_eq!(winners.len(), 1);
+    let winner = winners.get(0).unwrap();
     assert!(winner == player1 || winner == player2);
 
     // Verify game is ended and winning number is now set
     let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some()); // Game has ended
-    assert_eq!(final_game.winner.unwrap(), winner);
+    assert_eq!(final_game.winners, winners); // Game has ended
     assert!(final_game.winning_number.is_some());
     let winning_number = final_game.winning_number.unwrap();
-    assert!(winning_number >= 1 && winning_number <= 10);
+    assert!((1..=10).contains(&winning_number));
 }
 
 #[test]
@@ -184,7 +319,14 @@ fn test_winning_number_in_range() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 2u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Make guesses and reveal winner to generate winning number
     client.make_guess(&session_id, &player1, &5);
@@ -196,7 +338,7 @@ fn test_winning_number_in_range() {
         .winning_number
         .expect("Winning number should be set after reveal");
     assert!(
-        winning_number >= 1 && winning_number <= 10,
+        (1..=10).contains(&winning_number),
         "Winning number should be between 1 and 10"
     );
 }
@@ -210,15 +352,29 @@ fn test_multiple_sessions() {
     let session1 = 3u32;
     let session2 = 4u32;
 
-    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000);
-    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000);
+    client.start_game(
+        &session1,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    client.start_game(
+        &session2,
+        &player3,
+        &player4,
+        &50_0000000,
+        &50_0000000,
+        &None,
+    );
 
     // Verify both games exist and are independent
     let game1 = client.get_game(&session1);
     let game2 = client.get_game(&session2);
 
-    assert_eq!(game1.player1, player1);
-    assert_eq!(game2.player1, player3);
+    assert_eq!(seat(&game1, 0).player, player1);
+    assert_eq!(seat(&game2, 0).player, player3);
 }
 
 // ============================================================================
@@ -227,33 +383,32 @@ fn test_multiple_sessions() {
 
 #[test]
 fn test_closest_guess_wins() {
-    let (_env, client, _hub, player1, player2) = setup_test();
+    let (env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 5u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Player1 guesses closer (1 away from any number between 1-10)
     // Player2 guesses further (at least 2 away)
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &10);
 
-    let winner = client.reveal_winner(&session_id);
+    let winners = client.reveal_winner(&session_id);
 
     // Get the final game state to check the winning number
     let game = client.get_game(&session_id);
     let winning_number = game.winning_number.unwrap();
 
     // Calculate which player should have won based on distances
-    let distance1 = if 5 > winning_number {
-        5 - winning_number
-    } else {
-        winning_number - 5
-    };
-    let distance2 = if 10 > winning_number {
-        10 - winning_number
-    } else {
-        winning_number - 10
-    };
+    let distance1 = 5u32.abs_diff(winning_number);
+    let distance2 = 10u32.abs_diff(winning_number);
 
     let expected_winner = if distance1 <= distance2 {
         player1.clone()
@@ -261,59 +416,66 @@ fn test_closest_guess_wins() {
         player2.clone()
     };
     assert_eq!(
-        winner, expected_winner,
+        winners,
+        vec![&env, expected_winner],
         "Player with closer guess should win"
     );
 }
 
 #[test]
 fn test_tie_game_player1_wins() {
-    let (_env, client, _hub, player1, player2) = setup_test();
+    let (env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 6u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Both players guess the same number (guaranteed tie)
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &5);
 
-    let winner = client.reveal_winner(&session_id);
-    assert_eq!(winner, player1, "Player1 should win in a tie");
+    let winners = client.reveal_winner(&session_id);
+    assert_eq!(winners, vec![&env, player1], "Player1 should win in a tie");
 }
 
 #[test]
 fn test_exact_guess_wins() {
-    let (_env, client, _hub, player1, player2) = setup_test();
+    let (env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 7u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Player1 guesses 5 (middle), player2 guesses 10 (edge)
     // Player1 is more likely to be closer to the winning number
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &10);
 
-    let winner = client.reveal_winner(&session_id);
+    let winners = client.reveal_winner(&session_id);
     let game = client.get_game(&session_id);
     let winning_number = game.winning_number.unwrap();
 
     // Verify the winner matches the distance calculation
-    let distance1 = if 5 > winning_number {
-        5 - winning_number
-    } else {
-        winning_number - 5
-    };
-    let distance2 = if 10 > winning_number {
-        10 - winning_number
-    } else {
-        winning_number - 10
-    };
+    let distance1 = 5u32.abs_diff(winning_number);
+    let distance2 = 10u32.abs_diff(winning_number);
     let expected_winner = if distance1 <= distance2 {
         player1.clone()
     } else {
         player2.clone()
     };
-    assert_eq!(winner, expected_winner);
+    assert_eq!(winners, vec![&env, expected_winner]);
 }
 
 // ============================================================================
@@ -325,7 +487,14 @@ fn test_cannot_guess_twice() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 8u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Make first guess
     client.make_guess(&session_id, &player1, &5);
@@ -340,7 +509,14 @@ fn test_cannot_reveal_before_both_guesses() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 9u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Only player1 guesses
     client.make_guess(&session_id, &player1, &5);
@@ -351,7 +527,6 @@ fn test_cannot_reveal_before_both_guesses() {
 }
 
 #[test]
-#[should_panic(expected = "Guess must be between 1 and 10")]
 fn test_cannot_guess_below_range() {
     let (env, client, _hub, player1, _player2) = setup_test();
 
@@ -362,14 +537,15 @@ fn test_cannot_guess_below_range() {
         &Address::generate(&env),
         &100_0000000,
         &100_0000000,
+        &None,
     );
 
-    // Try to guess 0 (below range) - should panic
-    client.make_guess(&session_id, &player1, &0);
+    // Try to guess 0 (below range) - should fail
+    let result = client.try_make_guess(&session_id, &player1, &0);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
 }
 
 #[test]
-#[should_panic(expected = "Guess must be between 1 and 10")]
 fn test_cannot_guess_above_range() {
     let (env, client, _hub, player1, _player2) = setup_test();
 
@@ -380,10 +556,12 @@ fn test_cannot_guess_above_range() {
         &Address::generate(&env),
         &100_0000000,
         &100_0000000,
+        &None,
     );
 
-    // Try to guess 11 (above range) - should panic
-    client.make_guess(&session_id, &player1, &11);
+    // Try to guess 11 (above range) - should fail
+    let result = client.try_make_guess(&session_id, &player1, &11);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
 }
 
 #[test]
@@ -392,7 +570,14 @@ fn test_non_player_cannot_guess() {
     let non_player = Address::generate(&env);
 
     let session_id = 11u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Non-player tries to guess
     let result = client.try_make_guess(&session_id, &non_player, &5);
@@ -412,7 +597,14 @@ fn test_cannot_guess_after_game_ended() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 12u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     // Both players make guesses
     client.make_guess(&session_id, &player1, &5);
@@ -431,14 +623,21 @@ fn test_cannot_reveal_twice() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 14u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
 
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &7);
 
     // First reveal succeeds
     let winner = client.reveal_winner(&session_id);
-    assert!(winner == player1 || winner == player2);
+    assert_eq!(winner.len(), 1);
 
     // Second reveal should return same winner (idempotent)
     let winner2 = client.reveal_winner(&session_id);
@@ -459,8 +658,22 @@ fn test_multiple_games_independent() {
     let session2 = 21u32;
 
     // Start two games
-    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000);
-    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000);
+    client.start_game(
+        &session1,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    client.start_game(
+        &session2,
+        &player3,
+        &player4,
+        &50_0000000,
+        &50_0000000,
+        &None,
+    );
 
     // Play both games independently
     client.make_guess(&session1, &player1, &3);
@@ -472,15 +685,15 @@ fn test_multiple_games_independent() {
     let winner1 = client.reveal_winner(&session1);
     let winner2 = client.reveal_winner(&session2);
 
-    assert!(winner1 == player1 || winner1 == player2);
-    assert!(winner2 == player3 || winner2 == player4);
+    assert!(winner1 == vec![&env, player1] || winner1 == vec![&env, player2]);
+    assert!(winner2 == vec![&env, player3] || winner2 == vec![&env, player4]);
 
     // Verify both games are independent
     let final_game1 = client.get_game(&session1);
     let final_game2 = client.get_game(&session2);
 
-    assert!(final_game1.winner.is_some()); // Game 1 has ended
-    assert!(final_game2.winner.is_some()); // Game 2 has ended
+    assert!(!final_game1.winners.is_empty()); // Game 1 has ended
+    assert!(!final_game2.winners.is_empty()); // Game 2 has ended
 
     // Note: winning numbers could be the same by chance, so we just verify they're both set
     assert!(final_game1.winning_number.is_some());
@@ -495,11 +708,11 @@ fn test_asymmetric_points() {
     let points1 = 200_0000000;
     let points2 = 50_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points1, &points2);
+    client.start_game(&session_id, &player1, &player2, &points1, &points2, &None);
 
     let game = client.get_game(&session_id);
-    assert_eq!(game.player1_points, points1);
-    assert_eq!(game.player2_points, points2);
+    assert_eq!(seat(&game, 0).points, points1);
+    assert_eq!(seat(&game, 1).points, points2);
 
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &5);
@@ -507,7 +720,605 @@ fn test_asymmetric_points() {
 
     // Game completes successfully with asymmetric points
     let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some()); // Game has ended
+    assert!(!final_game.winners.is_empty()); // Game has ended
+}
+
+// ============================================================================
+// Game Config Tests
+// ============================================================================
+
+#[test]
+fn test_custom_range() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let config = GuessConfig {
+        min: 100,
+        max: 200,
+        tie_policy: TiePolicy::Player1Wins,
+    };
+    let session_id = 30u32;
+    client.start_game(&session_id, &player1, &player2, &100, &100, &Some(config));
+
+    // The classic range no longer applies
+    let result = client.try_make_guess(&session_id, &player1, &5);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
+    let result = client.try_make_guess(&session_id, &player1, &201);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
+
+    client.make_guess(&session_id, &player1, &100);
+    client.make_guess(&session_id, &player2, &200);
+    let winners = client.reveal_winner(&session_id);
+
+    let game = client.get_game(&session_id);
+    let winning_number = game.winning_number.unwrap();
+    assert!((100..=200).contains(&winning_number));
+    let player1_closer = 100u32.abs_diff(winning_number) <= 200u32.abs_diff(winning_number);
+    assert_eq!(
+        winners,
+        vec![&env, if player1_closer { player1 } else { player2 }]
+    );
+}
+
+#[test]
+fn test_invalid_range_rejected() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    for (min, max) in [(5, 5), (10, 1)] {
+        let config = GuessConfig {
+            min,
+            max,
+            tie_policy: TiePolicy::Draw,
+        };
+        let result = client.try_start_game(&31, &player1, &player2, &100, &100, &Some(config));
+        assert_number_guess_error(&result, Error::InvalidRange);
+    }
+}
+
+#[test]
+fn test_draw_policy_shares_tie() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    let config = GuessConfig {
+        min: 1,
+        max: 10,
+        tie_policy: TiePolicy::Draw,
+    };
+    let session_id = 32u32;
+    client.start_game(&session_id, &player1, &player2, &100, &100, &Some(config));
+
+    // Both players guess the same number (guaranteed tie)
+    client.make_guess(&session_id, &player1, &5);
+    client.make_guess(&session_id, &player2, &5);
+
+    let winners = client.reveal_winner(&session_id);
+    let both = vec![&env, player1, player2];
+    assert_eq!(winners, both);
+
+    // A two-player draw is settled through the N-player hub call
+    assert_eq!(hub.get_winners(&session_id), Some(both));
+    assert_eq!(hub.get_player1_won(&session_id), None);
+}
+
+// ============================================================================
+// Multi-Player Table Tests
+// ============================================================================
+
+#[test]
+fn test_start_table_rejects_bad_tables() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let result = client.try_start_table(
+        &40,
+        &vec![&env, player1.clone()],
+        &vec![&env, 100i128],
+        &None,
+    );
+    assert_number_guess_error(&result, Error::InvalidTableSize);
+
+    let mut players = Vec::new(&env);
+    let mut points = Vec::new(&env);
+    for _ in 0..7 {
+        players.push_back(Address::generate(&env));
+        points.push_back(100i128);
+    }
+    let result = client.try_start_table(&41, &players, &points, &None);
+    assert_number_guess_error(&result, Error::InvalidTableSize);
+
+    let result = client.try_start_table(
+        &42,
+        &vec![&env, player1.clone(), player2, player1],
+        &vec![&env, 100i128, 100, 100],
+        &None,
+    );
+    assert_number_guess_error(&result, Error::SelfPlay);
+}
+
+#[test]
+fn test_cannot_play_against_yourself() {
+    let (_env, client, _hub, player1, _player2) = setup_test();
+
+    let result = client.try_start_game(&43, &player1, &player1, &100, &100, &None);
+    assert_number_guess_error(&result, Error::SelfPlay);
+}
+
+#[test]
+fn test_table_closest_guess_wins() {
+    let (env, client, hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+    let guesses = [1u32, 5, 10];
+
+    let session_id = 44u32;
+    client.start_table(&session_id, &players, &vec![&env, 100i128, 100, 100], &None);
+    for i in 0..3 {
+        client.make_guess(&session_id, &players.get(i).unwrap(), &guesses[i as usize]);
+    }
+    let winners = client.reveal_winner(&session_id);
+
+    let winning_number = client.get_game(&session_id).winning_number.unwrap();
+    let best = guesses
+        .iter()
+        .map(|g| g.abs_diff(winning_number))
+        .min()
+        .unwrap();
+    let mut expected = Vec::new(&env);
+    for i in 0..3 {
+        if guesses[i as usize].abs_diff(winning_number) == best {
+            expected.push_back(players.get(i).unwrap());
+        }
+    }
+    assert_eq!(winners, expected);
+    assert_eq!(hub.get_winners(&session_id), Some(expected));
+}
+
+#[test]
+fn test_table_tie_policies() {
+    let (env, client, hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+
+    // The first two seats guess the same number, so they are always equally close
+    for (session_id, tie_policy) in [(45u32, TiePolicy::Draw), (46u32, TiePolicy::Player1Wins)] {
+        let config = GuessConfig {
+            min: 1,
+            max: 10,
+            tie_policy,
+        };
+        client.start_table(
+            &session_id,
+            &players,
+            &vec![&env, 100i128, 100, 100],
+            &Some(config),
+        );
+        client.make_guess(&session_id, &players.get(0).unwrap(), &3);
+        client.make_guess(&session_id, &players.get(1).unwrap(), &3);
+        client.make_guess(&session_id, &players.get(2).unwrap(), &8);
+        let winners = client.reveal_winner(&session_id);
+
+        let winning_number = client.get_game(&session_id).winning_number.unwrap();
+        if 8u32.abs_diff(winning_number) < 3u32.abs_diff(winning_number) {
+            assert_eq!(winners, vec![&env, players.get(2).unwrap()]);
+        } else if tie_policy == TiePolicy::Draw {
+            assert!(winners.contains(players.get(0).unwrap()));
+            assert!(winners.contains(players.get(1).unwrap()));
+        } else {
+            assert_eq!(winners.get(0).unwrap(), players.get(0).unwrap());
+            assert!(!winners.contains(players.get(1).unwrap()));
+        }
+        assert_eq!(hub.get_winners(&session_id), Some(winners));
+    }
+}
+
+// ============================================================================
+// Randomness Beacon Tests
+// ============================================================================
+
+/// Point the game at a fresh mock beacon
+fn use_beacon(env: &Env, client: &NumberGuessContractClient) -> MockBeaconClient<'static> {
+    let beacon_addr = env.register(MockBeacon, ());
+    client.set_beacon(&Some(beacon_addr.clone()));
+    MockBeaconClient::new(env, &beacon_addr)
+}
+
+#[test]
+fn test_beacon_seed_requested_after_last_guess() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+
+    let session_id = 50u32;
+    client.start_game(&session_id, &player1, &player2, &100, &100, &None);
+    assert_eq!(
+        client.get_game(&session_id).beacon,
+        Some(beacon.address.clone())
+    );
+
+    client.make_guess(&session_id, &player1, &3);
+    assert!(!beacon.was_requested(&client.address, &session_id));
+    client.make_guess(&session_id, &player2, &7);
+    assert!(beacon.was_requested(&client.address, &session_id));
+}
+
+#[test]
+fn test_reveal_waits_for_beacon_seed() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+
+    let session_id = 51u32;
+    client.start_game(&session_id, &player1, &player2, &100, &100, &None);
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &7);
+
+    let result = client.try_reveal_winner(&session_id);
+    assert_number_guess_error(&result, Error::SeedNotReady);
+
+    let seed = BytesN::from_array(&env, &[42u8; 32]);
+    beacon.finalize(&client.address, &session_id, &seed);
+    let winners = client.reveal_winner(&session_id);
+
+    // The winning number comes straight from the beacon seed
+    let expected = env.as_contract(&client.address, || {
+        env.prng().seed(seed.into());
+        env.prng().gen_range::<u64>(1..=10) as u32
+    });
+    let game = client.get_game(&session_id);
+    assert_eq!(game.winning_number, Some(expected));
+    assert_eq!(winners.len(), 1);
+    assert_eq!(
+        hub.get_player1_won(&session_id),
+        Some(winners.get(0).unwrap() == player1)
+    );
+}
+
+#[test]
+fn test_games_keep_beacon_they_started_with() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // Started without a beacon, so it seeds itself even after one is configured
+    client.start_game(&52, &player1, &player2, &100, &100, &None);
+    let beacon = use_beacon(&env, &client);
+    assert_eq!(client.get_beacon(), Some(beacon.address.clone()));
+
+    client.make_guess(&52, &player1, &3);
+    client.make_guess(&52, &player2, &7);
+    assert!(!beacon.was_requested(&client.address, &52));
+    client.reveal_winner(&52);
+
+    // Clearing the beacon goes back to self-seeded games
+    client.set_beacon(&None);
+    assert_eq!(client.get_beacon(), None);
+    client.start_game(&53, &player1, &player2, &100, &100, &None);
+    assert_eq!(client.get_game(&53).beacon, None);
+}
+
+// ============================================================================
+// Game Hub Extension Tests
+// ============================================================================
+
+/// Point the game at a hub with only `start_game`/`end_game` and turn the extensions off
+fn use_standard_hub(
+    env: &Env,
+    client: &NumberGuessContractClient,
+) -> StandardGameHubClient<'static> {
+    let hub_addr = env.register(StandardGameHub, ());
+    client.set_hub(&hub_addr);
+    client.set_hub_extensions(&false);
+    StandardGameHubClient::new(env, &hub_addr)
+}
+
+#[test]
+fn test_hub_extensions_off_by_default() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(MockGameHub, ());
+    let contract_id = env.register(NumberGuessContract, (&admin, &hub_addr));
+    let client = NumberGuessContractClient::new(&env, &contract_id);
+    assert!(!client.get_hub_extensions());
+}
+
+#[test]
+fn test_standard_hub_plays_classic_two_seat_games() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub = use_standard_hub(&env, &client);
+
+    let session_id = 1u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    client.make_guess(&session_id, &player1, &5);
+    client.make_guess(&session_id, &player2, &5);
+
+    // Equal guesses tie, and the classic two-seat tie goes to player 1
+    assert_eq!(client.reveal_winner(&session_id), vec![&env, player1]);
+    assert_eq!(hub.get_player1_won(&session_id), Some(true));
+}
+
+#[test]
+fn test_standard_hub_refuses_extensions() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    use_standard_hub(&env, &client);
+
+    let result = client.try_start_table(
+        &1,
+        &three_players(&env),
+        &vec![&env, 100_0000000, 100_0000000, 100_0000000],
+        &None,
+    );
+    assert_number_guess_error(&result, Error::HubExtensionsRequired);
+
+    let config = GuessConfig {
+        min: 1,
+        max: 10,
+        tie_policy: TiePolicy::Draw,
+    };
+    let result = client.try_start_game(&2, &player1, &player2, &10, &10, &Some(config));
+    assert_number_guess_error(&result, Error::HubExtensionsRequired);
+}
+
+// ============================================================================
+// Channel Tests
+// ============================================================================
+
+fn channel_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn channel_public_keys(env: &Env, keys: &[SigningKey]) -> Vec<BytesN<32>> {
+    let mut public_keys = Vec::new(env);
+    for key in keys {
+        public_keys.push_back(BytesN::from_array(env, &key.verifying_key().to_bytes()));
+    }
+    public_keys
+}
+
+fn channel_state(
+    env: &Env,
+    session_id: u32,
+    nonce: u64,
+    winners: Option<Vec<Address>>,
+) -> ChannelState {
+    ChannelState {
+        session_id,
+        nonce,
+        state_hash: BytesN::from_array(env, &[nonce as u8; 32]),
+        finished: winners.is_some(),
+        winners: winners.unwrap_or(Vec::new(env)),
+    }
+}
+
+fn co_sign(
+    env: &Env,
+    client: &NumberGuessContractClient,
+    keys: &[SigningKey],
+    state: &ChannelState,
+) -> Vec<BytesN<64>> {
+    let digest = client.channel_state_digest(state).to_array();
+    let mut signatures = Vec::new(env);
+    for key in keys {
+        signatures.push_back(BytesN::from_array(env, &key.sign(&digest).to_bytes()));
+    }
+    signatures
+}
+
+#[test]
+fn test_channel_settles_final_state() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 40u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+    assert_number_guess_error(
+        &client.try_open_channel(&session_id, &channel_public_keys(&env, &keys)),
+        Error::ChannelExists,
+    );
+    assert_number_guess_error(
+        &client.try_make_guess(&session_id, &player1, &5),
+        Error::ChannelOpen,
+    );
+    assert_number_guess_error(&client.try_reveal_winner(&session_id), Error::ChannelOpen);
+
+    let state = channel_state(&env, session_id, 3, Some(vec![&env, player2.clone()]));
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    assert_eq!(
+        client.settle_channel(&session_id),
+        vec![&env, player2.clone()]
+    );
+
+    assert_eq!(hub.get_player1_won(&session_id), Some(false));
+    assert_eq!(client.get_game(&session_id).winners, vec![&env, player2]);
+    assert!(client.get_channel(&session_id).settled);
+    assert_number_guess_error(
+        &client.try_settle_channel(&session_id),
+        Error::GameAlreadyEnded,
+    );
+}
+
+#[test]
+fn test_channel_dispute_keeps_highest_nonce() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 41u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    assert_number_guess_error(
+        &client.try_submit_channel_state(
+            &session_id,
+            &channel_state(&env, session_id, 1, None),
+            &Vec::new(&env),
+        ),
+        Error::ChannelNotFound,
+    );
+
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+    assert_number_guess_error(
+        &client.try_settle_channel(&session_id),
+        Error::NoChannelState,
+    );
+
+    let state = channel_state(&env, session_id, 2, None);
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    let stale = channel_state(&env, session_id, 1, Some(vec![&env, player1.clone()]));
+    assert_number_guess_error(
+        &client.try_submit_channel_state(
+            &session_id,
+            &stale,
+            &co_sign(&env, &client, &keys, &stale),
+        ),
+        Error::StaleChannelState,
+    );
+    let latest = channel_state(&env, session_id, 4, None);
+    client.submit_channel_state(
+        &session_id,
+        &latest,
+        &co_sign(&env, &client, &keys, &latest),
+    );
+    assert_number_guess_error(
+        &client.try_settle_channel(&session_id),
+        Error::ChallengeOpen,
+    );
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += state_channel::CHALLENGE_LEDGERS + 1);
+    let late = channel_state(&env, session_id, 5, Some(vec![&env, player1.clone()]));
+    assert_number_guess_error(
+        &client.try_submit_channel_state(&session_id, &late, &co_sign(&env, &client, &keys, &late)),
+        Error::ChallengeClosed,
+    );
+
+    // The loser stopped signing, so instead of a draw the game goes back on chain
+    assert_eq!(client.get_channel(&session_id).state, latest);
+    assert_eq!(client.settle_channel(&session_id), Vec::new(&env));
+    assert_eq!(hub.get_winners(&session_id), None);
+    assert_number_guess_error(
+        &client.try_settle_channel(&session_id),
+        Error::GameAlreadyEnded,
+    );
+    client.make_guess(&session_id, &player1, &5);
+    client.make_guess(&session_id, &player2, &6);
+    client.reveal_winner(&session_id);
+    assert!(hub.get_player1_won(&session_id).is_some());
+}
+
+#[test]
+fn test_channel_opened_never_signed() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 43u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+
+    // Any seat can open the window without a state
+    assert_number_guess_error(
+        &client.try_challenge_channel(&session_id, &Address::generate(&env)),
+        Error::NotPlayer,
+    );
+    client.challenge_channel(&session_id, &player2);
+    assert_number_guess_error(
+        &client.try_challenge_channel(&session_id, &player1),
+        Error::ChallengeOpen,
+    );
+    assert_number_guess_error(
+        &client.try_settle_channel(&session_id),
+        Error::ChallengeOpen,
+    );
+    assert_number_guess_error(
+        &client.try_make_guess(&session_id, &player1, &5),
+        Error::ChannelOpen,
+    );
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += state_channel::CHALLENGE_LEDGERS + 1);
+    assert_eq!(client.settle_channel(&session_id), Vec::new(&env));
+    client.make_guess(&session_id, &player1, &5);
+    assert_number_guess_error(
+        &client.try_open_channel(&session_id, &channel_public_keys(&env, &keys)),
+        Error::ChannelExists,
+    );
+}
+
+#[test]
+fn test_channel_draw_on_standard_hub_follows_tie_policy() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub = use_standard_hub(&env, &client);
+    let session_id = 45u32;
+    client.start_game(
+        &session_id,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &None,
+    );
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+
+    // The standard hub has no shared wins, so a co-signed draw goes to player 1 like an on-chain tie
+    let state = channel_state(&env, session_id, 3, Some(Vec::new(&env)));
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    assert_eq!(client.settle_channel(&session_id), vec![&env, player1]);
+    assert_eq!(hub.get_player1_won(&session_id), Some(true));
+}
+
+#[test]
+fn test_channel_needs_every_seat() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let session_id = 42u32;
+    let players = three_players(&env);
+    client.start_table(&session_id, &players, &vec![&env, 10, 10, 10], &None);
+
+    let keys = [channel_key(1), channel_key(2), channel_key(3)];
+    assert_number_guess_error(
+        &client.try_open_channel(&session_id, &channel_public_keys(&env, &keys[..2])),
+        Error::InvalidChannelState,
+    );
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+
+    let state = channel_state(
+        &env,
+        session_id,
+        1,
+        Some(vec![&env, Address::generate(&env)]),
+    );
+    assert_number_guess_error(
+        &client.try_submit_channel_state(
+            &session_id,
+            &state,
+            &co_sign(&env, &client, &keys, &state),
+        ),
+        Error::InvalidChannelState,
+    );
+    let state = channel_state(&env, session_id, 1, None);
+    assert_number_guess_error(
+        &client.try_submit_channel_state(
+            &session_id,
+            &state,
+            &co_sign(&env, &client, &keys[..2], &state),
+        ),
+        Error::InvalidChannelState,
+    );
 }
 
 // ============================================================================
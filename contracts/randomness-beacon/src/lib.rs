@@ -0,0 +1,565 @@
+#![no_std]
+
+//! # Randomness Beacon
+//!
+//! A shared source of randomness for the workspace's games. Instead of each game deriving
+//! a seed from data every player knows in advance (session ID, addresses), a game asks the
+//! beacon for a seed and the seed is built from secrets contributed by several parties.
+//!
+//! **Rounds:**
+//! A requester (normally a game contract) opens a round for a request ID with `request_seed`.
+//! Each round runs through two windows:
+//! 1. Commit - anyone can `commit` the SHA-256 hash of a 32-byte secret, posting a bond
+//! 2. Reveal - contributors `reveal` their secret and get their bond back
+//!
+//! Once the reveal window closes (or every contributor has revealed), anyone can `finalize`
+//! the round. The seed is the SHA-256 of the request ID and every revealed secret in commit
+//! order, so it is unpredictable as long as one contributor kept their secret to themselves.
+//!
+//! **Slashing:**
+//! A contributor who commits but does not reveal could otherwise wait to see the other
+//! secrets and withhold their own to steer the seed. Their bond is slashed at finalization
+//! and shared between the contributors who did reveal. If fewer than `min_contributors`
+//! revealed, a lone revealer could have ground the seed by choosing their secret last, so
+//! the unclaimed bonds go to the admin and the round is reopened with fresh windows.
+
+use soroban_sdk::{
+    Address, Bytes, BytesN, Env, Vec, contract, contracterror, contractevent, contractimpl,
+    contracttype, token,
+};
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    RequestNotFound = 1,
+    RequestExists = 2,
+    CommitClosed = 3,
+    AlreadyCommitted = 4,
+    DuplicateCommitment = 5,
+    RoundFull = 6,
+    RevealNotOpen = 7,
+    RevealClosed = 8,
+    NotContributor = 9,
+    AlreadyRevealed = 10,
+    CommitmentMismatch = 11,
+    RevealNotClosed = 12,
+    AlreadyFinalized = 13,
+    InvalidConfig = 14,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[contractevent]
+pub struct RoundOpened {
+    pub requester: Address,
+    pub request_id: u32,
+    pub commit_deadline: u32,
+    pub reveal_deadline: u32,
+}
+
+#[contractevent]
+pub struct SeedFinalized {
+    pub requester: Address,
+    pub request_id: u32,
+    pub seed: BytesN<32>,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeaconConfig {
+    pub token: Address,        // Token bonds are posted in
+    pub bond: i128,            // Bond each contributor posts with their commitment
+    pub commit_ledgers: u32,   // Length of the commit window
+    pub reveal_ledgers: u32,   // Length of the reveal window
+    pub min_contributors: u32, // Revealed secrets a seed needs
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    pub contributor: Address,
+    pub commitment: BytesN<32>,     // SHA-256 of the secret
+    pub secret: Option<BytesN<32>>, // Set once revealed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub commit_deadline: u32,  // Last ledger commitments are accepted
+    pub reveal_deadline: u32,  // Last ledger secrets are accepted
+    pub token: Address,        // Bond token in force when the round opened
+    pub bond: i128,            // Bond in force when the round opened
+    pub min_contributors: u32, // Revealed secrets needed, in force when the round opened
+    pub contributions: Vec<Contribution>,
+    pub seed: Option<BytesN<32>>, // Set once the round is finalized
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Round(Address, u32),
+    Config,
+    Admin,
+}
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// Rounds hold bonds, so they are kept in persistent storage and bumped on every write.
+// Games read their seed for as long as the game itself lives.
+
+/// TTL for round storage (30 days in ledgers, ~5 seconds per ledger)
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+const ROUND_TTL_LEDGERS: u32 = 518_400;
+
+/// Default bond (1 XLM in stroops when bonds are posted in the native token)
+const DEFAULT_BOND: i128 = 10_000_000;
+
+/// Default commit and reveal windows (5 minutes each, ~5 seconds per ledger)
+const DEFAULT_COMMIT_LEDGERS: u32 = 60;
+const DEFAULT_REVEAL_LEDGERS: u32 = 60;
+
+/// Default revealed secrets a seed needs
+const DEFAULT_MIN_CONTRIBUTORS: u32 = 2;
+
+/// Most contributions a single round accepts
+const MAX_CONTRIBUTORS: u32 = 16;
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct RandomnessBeaconContract;
+
+#[contractimpl]
+impl RandomnessBeaconContract {
+    /// Initialize the contract with admin and bond token.
+    /// Bonds, commit and reveal windows start at their defaults and can be changed
+    /// with `set_config`.
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can configure and upgrade contract, receives unclaimed bonds)
+    /// * `token` - Address of the token contract bonds are posted in
+    pub fn __constructor(env: Env, admin: Address, token: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(
+            &DataKey::Config,
+            &BeaconConfig {
+                token,
+                bond: DEFAULT_BOND,
+                commit_ledgers: DEFAULT_COMMIT_LEDGERS,
+                reveal_ledgers: DEFAULT_REVEAL_LEDGERS,
+                min_contributors: DEFAULT_MIN_CONTRIBUTORS,
+            },
+        );
+    }
+
+    /// Open a round for a new seed.
+    /// The commit window starts immediately.
+    ///
+    /// # Arguments
+    /// * `requester` - Address the seed is requested for (normally the calling game contract)
+    /// * `request_id` - Identifier chosen by the requester, e.g. its session ID
+    pub fn request_seed(env: Env, requester: Address, request_id: u32) -> Result<(), Error> {
+        requester.require_auth();
+
+        let key = DataKey::Round(requester.clone(), request_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::RequestExists);
+        }
+
+        let config = Self::get_config(env.clone());
+        let mut round = Round {
+            commit_deadline: 0,
+            reveal_deadline: 0,
+            token: config.token.clone(),
+            bond: config.bond,
+            min_contributors: config.min_contributors,
+            contributions: Vec::new(&env),
+            seed: None,
+        };
+        Self::open_windows(&env, &mut round, &config);
+        Self::save_round(&env, &key, &round);
+
+        RoundOpened {
+            requester,
+            request_id,
+            commit_deadline: round.commit_deadline,
+            reveal_deadline: round.reveal_deadline,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Commit to a secret during the commit window, posting the round's bond.
+    ///
+    /// # Arguments
+    /// * `requester` - Address the round was requested for
+    /// * `request_id` - The request ID of the round
+    /// * `contributor` - Address of the contributor (pays the bond)
+    /// * `commitment` - SHA-256 hash of the contributor's 32-byte secret
+    pub fn commit(
+        env: Env,
+        requester: Address,
+        request_id: u32,
+        contributor: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), Error> {
+        contributor.require_auth();
+
+        let key = DataKey::Round(requester, request_id);
+        let mut round = Self::load_round(&env, &key)?;
+        if round.seed.is_some() {
+            return Err(Error::AlreadyFinalized);
+        }
+        if env.ledger().sequence() > round.commit_deadline {
+            return Err(Error::CommitClosed);
+        }
+        for contribution in round.contributions.iter() {
+            if contribution.contributor == contributor {
+                return Err(Error::AlreadyCommitted);
+            }
+            // A copied commitment adds nothing to the seed, only the original contributor can reveal it
+            if contribution.commitment == commitment {
+                return Err(Error::DuplicateCommitment);
+            }
+        }
+        if round.contributions.len() >= MAX_CONTRIBUTORS {
+            return Err(Error::RoundFull);
+        }
+
+        // Take the bond before recording the commitment
+        if round.bond > 0 {
+            token::Client::new(&env, &round.token).transfer(
+                &contributor,
+                env.current_contract_address(),
+                &round.bond,
+            );
+        }
+
+        round.contributions.push_back(Contribution {
+            contributor,
+            commitment,
+            secret: None,
+        });
+        Self::save_round(&env, &key, &round);
+
+        Ok(())
+    }
+
+    /// Reveal a committed secret during the reveal window.
+    /// The contributor's bond is returned straight away.
+    ///
+    /// # Arguments
+    /// * `requester` - Address the round was requested for
+    /// * `request_id` - The request ID of the round
+    /// * `contributor` - Address of the contributor
+    /// * `secret` - The secret whose SHA-256 hash was committed
+    pub fn reveal(
+        env: Env,
+        requester: Address,
+        request_id: u32,
+        contributor: Address,
+        secret: BytesN<32>,
+    ) -> Result<(), Error> {
+        contributor.require_auth();
+
+        let key = DataKey::Round(requester, request_id);
+        let mut round = Self::load_round(&env, &key)?;
+        if round.seed.is_some() {
+            return Err(Error::AlreadyFinalized);
+        }
+        let sequence = env.ledger().sequence();
+        if sequence <= round.commit_deadline {
+            return Err(Error::RevealNotOpen);
+        }
+        if sequence > round.reveal_deadline {
+            return Err(Error::RevealClosed);
+        }
+
+        let index = round
+            .contributions
+            .iter()
+            .position(|contribution| contribution.contributor == contributor)
+            .ok_or(Error::NotContributor)? as u32;
+        let mut contribution = round.contributions.get_unchecked(index);
+        if contribution.secret.is_some() {
+            return Err(Error::AlreadyRevealed);
+        }
+        let hash: BytesN<32> = env.crypto().sha256(&Bytes::from(secret.clone())).into();
+        if hash != contribution.commitment {
+            return Err(Error::CommitmentMismatch);
+        }
+
+        contribution.secret = Some(secret);
+        round.contributions.set(index, contribution);
+        Self::save_round(&env, &key, &round);
+
+        // Return the bond
+        if round.bond > 0 {
+            token::Client::new(&env, &round.token).transfer(
+                &env.current_contract_address(),
+                &contributor,
+                &round.bond,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Finalize a round and fix its seed.
+    /// Can be called by anyone once the reveal window has closed, or earlier once every
+    /// contributor has revealed. Bonds of contributors who did not reveal are shared between
+    /// those who did (any remainder goes to the first revealer).
+    ///
+    /// If fewer than the round's `min_contributors` revealed, the unclaimed bonds go to the
+    /// admin and the round is reopened with fresh commit and reveal windows.
+    ///
+    /// # Arguments
+    /// * `requester` - Address the round was requested for
+    /// * `request_id` - The request ID of the round
+    ///
+    /// # Returns
+    /// * `Option<BytesN<32>>` - The seed, or `None` if the round was reopened
+    pub fn finalize(
+        env: Env,
+        requester: Address,
+        request_id: u32,
+    ) -> Result<Option<BytesN<32>>, Error> {
+        let key = DataKey::Round(requester.clone(), request_id);
+        let mut round = Self::load_round(&env, &key)?;
+        if round.seed.is_some() {
+            return Err(Error::AlreadyFinalized);
+        }
+
+        let mut revealers = Vec::new(&env);
+        let mut seed_bytes = Bytes::from_array(&env, &request_id.to_be_bytes());
+        for contribution in round.contributions.iter() {
+            if let Some(secret) = contribution.secret {
+                seed_bytes.append(&Bytes::from(secret));
+                revealers.push_back(contribution.contributor);
+            }
+        }
+
+        let sequence = env.ledger().sequence();
+        let everyone_revealed = sequence > round.commit_deadline
+            && !round.contributions.is_empty()
+            && revealers.len() == round.contributions.len();
+        if sequence <= round.reveal_deadline && !everyone_revealed {
+            return Err(Error::RevealNotClosed);
+        }
+
+        // Slash contributors who did not reveal
+        let slashed = round
+            .bond
+            .checked_mul((round.contributions.len() - revealers.len()) as i128)
+            .ok_or(Error::InvalidConfig)?;
+        let bond_token = token::Client::new(&env, &round.token);
+
+        if revealers.len() < round.min_contributors {
+            if slashed > 0 {
+                let admin = Self::get_admin(env.clone());
+                bond_token.transfer(&env.current_contract_address(), &admin, &slashed);
+            }
+
+            // Too few secrets to trust the seed, start over
+            let config = Self::get_config(env.clone());
+            round.contributions = Vec::new(&env);
+            round.token = config.token.clone();
+            round.bond = config.bond;
+            round.min_contributors = config.min_contributors;
+            Self::open_windows(&env, &mut round, &config);
+            Self::save_round(&env, &key, &round);
+
+            RoundOpened {
+                requester,
+                request_id,
+                commit_deadline: round.commit_deadline,
+                reveal_deadline: round.reveal_deadline,
+            }
+            .publish(&env);
+
+            return Ok(None);
+        }
+
+        let seed: BytesN<32> = env.crypto().sha256(&seed_bytes).into();
+        round.seed = Some(seed.clone());
+        Self::save_round(&env, &key, &round);
+
+        if slashed > 0 {
+            let share = slashed / revealers.len() as i128;
+            let remainder = slashed % revealers.len() as i128;
+            for (i, revealer) in revealers.iter().enumerate() {
+                let amount = if i == 0 { share + remainder } else { share };
+                if amount > 0 {
+                    bond_token.transfer(&env.current_contract_address(), &revealer, &amount);
+                }
+            }
+        }
+
+        SeedFinalized {
+            requester,
+            request_id,
+            seed: seed.clone(),
+        }
+        .publish(&env);
+
+        Ok(Some(seed))
+    }
+
+    /// Get the finalized seed of a round.
+    ///
+    /// # Arguments
+    /// * `requester` - Address the round was requested for
+    /// * `request_id` - The request ID of the round
+    ///
+    /// # Returns
+    /// * `Option<BytesN<32>>` - The seed, or `None` until the round is finalized
+    pub fn get_seed(env: Env, requester: Address, request_id: u32) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get::<_, Round>(&DataKey::Round(requester, request_id))
+            .and_then(|round| round.seed)
+    }
+
+    /// Get round information.
+    ///
+    /// # Arguments
+    /// * `requester` - Address the round was requested for
+    /// * `request_id` - The request ID of the round
+    ///
+    /// # Returns
+    /// * `Round` - The round state (windows, contributions and seed once finalized)
+    pub fn get_round(env: Env, requester: Address, request_id: u32) -> Result<Round, Error> {
+        Self::load_round(&env, &DataKey::Round(requester, request_id))
+    }
+
+    // ========================================================================
+    // Internal Helper Functions
+    // ========================================================================
+
+    /// Load a round from persistent storage
+    fn load_round(env: &Env, key: &DataKey) -> Result<Round, Error> {
+        env.storage()
+            .persistent()
+            .get(key)
+            .ok_or(Error::RequestNotFound)
+    }
+
+    /// Store a round and keep it alive for at least 30 days
+    fn save_round(env: &Env, key: &DataKey, round: &Round) {
+        env.storage().persistent().set(key, round);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, ROUND_TTL_LEDGERS, ROUND_TTL_LEDGERS);
+    }
+
+    /// Start a round's commit window now, followed by its reveal window
+    fn open_windows(env: &Env, round: &mut Round, config: &BeaconConfig) {
+        round.commit_deadline = env.ledger().sequence().saturating_add(config.commit_ledgers);
+        round.reveal_deadline = round.commit_deadline.saturating_add(config.reveal_ledgers);
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address
+    ///
+    /// # Returns
+    /// * `Address` - The admin address
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    /// Set a new admin address
+    ///
+    /// # Arguments
+    /// * `new_admin` - The new admin address
+    pub fn set_admin(env: Env, new_admin: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    }
+
+    /// Get the bond and window config applied to newly opened rounds
+    ///
+    /// # Returns
+    /// * `BeaconConfig` - The current config
+    pub fn get_config(env: Env) -> BeaconConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .expect("Config not set")
+    }
+
+    /// Set the bond and window config for newly opened rounds.
+    /// Rounds already open keep the bond, token and minimum they opened with.
+    ///
+    /// # Arguments
+    /// * `config` - Bond token, bond (not negative), window lengths (non-zero) and
+    ///   `min_contributors` (1 to 16)
+    pub fn set_config(env: Env, config: BeaconConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if config.bond < 0
+            || config.commit_ledgers == 0
+            || config.reveal_ledgers == 0
+            || config.min_contributors == 0
+            || config.min_contributors > MAX_CONTRIBUTORS
+        {
+            return Err(Error::InvalidConfig);
+        }
+        env.storage().instance().set(&DataKey::Config, &config);
+
+        Ok(())
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,496 @@
+#![cfg(test)]
+
+// Unit tests for the randomness beacon using a Stellar Asset Contract as the bond token.
+
+use crate::{BeaconConfig, Error, RandomnessBeaconContract, RandomnessBeaconContractClient};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, Bytes, BytesN, Env};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const BOND: i128 = 10_000_000;
+const BALANCE: i128 = 100_000_000;
+
+struct Setup {
+    env: Env,
+    beacon: RandomnessBeaconContractClient<'static>,
+    token: TokenClient<'static>,
+    admin: Address,
+    requester: Address,
+}
+
+fn setup_test() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // Set ledger info for time-based operations
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    let contract_id = env.register(RandomnessBeaconContract, (&admin, &token_addr));
+    let beacon = RandomnessBeaconContractClient::new(&env, &contract_id);
+    let token = TokenClient::new(&env, &token_addr);
+    let requester = Address::generate(&env);
+
+    Setup {
+        env,
+        beacon,
+        token,
+        admin,
+        requester,
+    }
+}
+
+/// Create a contributor holding enough tokens to post bonds
+fn funded_contributor(setup: &Setup) -> Address {
+    let contributor = Address::generate(&setup.env);
+    StellarAssetClient::new(&setup.env, &setup.token.address).mint(&contributor, &BALANCE);
+    contributor
+}
+
+fn secret(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+fn commitment(env: &Env, secret: &BytesN<32>) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::from(secret.clone())).into()
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+/// Move past the commit window of a freshly opened round
+fn open_reveal(env: &Env) {
+    advance_ledgers(env, 61);
+}
+
+/// Move past the reveal window of a freshly opened round
+fn close_reveal(env: &Env) {
+    advance_ledgers(env, 121);
+}
+
+/// Assert that a Result contains a specific beacon error
+fn assert_beacon_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected_error: Error,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Round Flow Tests
+// ============================================================================
+
+#[test]
+fn test_commit_reveal_finalize() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let bob = funded_contributor(&s);
+    let (alice_secret, bob_secret) = (secret(&s.env, 1), secret(&s.env, 2));
+
+    s.beacon.request_seed(&s.requester, &7);
+    s.beacon
+        .commit(&s.requester, &7, &alice, &commitment(&s.env, &alice_secret));
+    s.beacon
+        .commit(&s.requester, &7, &bob, &commitment(&s.env, &bob_secret));
+    assert_eq!(s.token.balance(&alice), BALANCE - BOND);
+    assert_eq!(s.beacon.get_seed(&s.requester, &7), None);
+
+    open_reveal(&s.env);
+    s.beacon.reveal(&s.requester, &7, &alice, &alice_secret);
+    s.beacon.reveal(&s.requester, &7, &bob, &bob_secret);
+    assert_eq!(s.token.balance(&alice), BALANCE);
+
+    // Everyone revealed, so the round can be finalized early
+    let seed = s.beacon.finalize(&s.requester, &7).unwrap();
+
+    let mut expected = Bytes::from_array(&s.env, &7u32.to_be_bytes());
+    expected.append(&Bytes::from(alice_secret));
+    expected.append(&Bytes::from(bob_secret));
+    let expected: BytesN<32> = s.env.crypto().sha256(&expected).into();
+    assert_eq!(seed, expected);
+    assert_eq!(s.beacon.get_seed(&s.requester, &7), Some(expected));
+}
+
+#[test]
+fn test_seeds_are_kept_per_requester_and_request() {
+    let s = setup_test();
+    let other = Address::generate(&s.env);
+    let alice = funded_contributor(&s);
+    let bob = funded_contributor(&s);
+    let (alice_secret, bob_secret) = (secret(&s.env, 1), secret(&s.env, 2));
+
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon.request_seed(&other, &1);
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+    s.beacon
+        .commit(&s.requester, &1, &bob, &commitment(&s.env, &bob_secret));
+
+    open_reveal(&s.env);
+    s.beacon.reveal(&s.requester, &1, &alice, &alice_secret);
+    s.beacon.reveal(&s.requester, &1, &bob, &bob_secret);
+    s.beacon.finalize(&s.requester, &1);
+
+    assert!(s.beacon.get_seed(&s.requester, &1).is_some());
+    assert_eq!(s.beacon.get_seed(&other, &1), None);
+    assert_eq!(s.beacon.get_seed(&s.requester, &2), None);
+}
+
+#[test]
+fn test_cannot_request_twice() {
+    let s = setup_test();
+    s.beacon.request_seed(&s.requester, &1);
+
+    let result = s.beacon.try_request_seed(&s.requester, &1);
+    assert_beacon_error(&result, Error::RequestExists);
+}
+
+#[test]
+fn test_commit_rules() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let bob = funded_contributor(&s);
+    let alice_commitment = commitment(&s.env, &secret(&s.env, 1));
+
+    let result = s
+        .beacon
+        .try_commit(&s.requester, &1, &alice, &alice_commitment);
+    assert_beacon_error(&result, Error::RequestNotFound);
+
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon.commit(&s.requester, &1, &alice, &alice_commitment);
+
+    let result = s.beacon.try_commit(
+        &s.requester,
+        &1,
+        &alice,
+        &commitment(&s.env, &secret(&s.env, 3)),
+    );
+    assert_beacon_error(&result, Error::AlreadyCommitted);
+
+    let result = s
+        .beacon
+        .try_commit(&s.requester, &1, &bob, &alice_commitment);
+    assert_beacon_error(&result, Error::DuplicateCommitment);
+
+    open_reveal(&s.env);
+    let result = s.beacon.try_commit(
+        &s.requester,
+        &1,
+        &bob,
+        &commitment(&s.env, &secret(&s.env, 2)),
+    );
+    assert_beacon_error(&result, Error::CommitClosed);
+}
+
+#[test]
+fn test_round_is_capped() {
+    let s = setup_test();
+    s.beacon.request_seed(&s.requester, &1);
+    for i in 0..16u8 {
+        let contributor = funded_contributor(&s);
+        s.beacon.commit(
+            &s.requester,
+            &1,
+            &contributor,
+            &commitment(&s.env, &secret(&s.env, i)),
+        );
+    }
+
+    let late = funded_contributor(&s);
+    let result = s.beacon.try_commit(
+        &s.requester,
+        &1,
+        &late,
+        &commitment(&s.env, &secret(&s.env, 99)),
+    );
+    assert_beacon_error(&result, Error::RoundFull);
+}
+
+#[test]
+fn test_reveal_rules() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let bob = Address::generate(&s.env);
+    let alice_secret = secret(&s.env, 1);
+
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+
+    let result = s.beacon.try_reveal(&s.requester, &1, &alice, &alice_secret);
+    assert_beacon_error(&result, Error::RevealNotOpen);
+
+    open_reveal(&s.env);
+    let result = s.beacon.try_reveal(&s.requester, &1, &bob, &alice_secret);
+    assert_beacon_error(&result, Error::NotContributor);
+
+    let result = s
+        .beacon
+        .try_reveal(&s.requester, &1, &alice, &secret(&s.env, 2));
+    assert_beacon_error(&result, Error::CommitmentMismatch);
+
+    s.beacon.reveal(&s.requester, &1, &alice, &alice_secret);
+    let result = s.beacon.try_reveal(&s.requester, &1, &alice, &alice_secret);
+    assert_beacon_error(&result, Error::AlreadyRevealed);
+}
+
+#[test]
+fn test_reveal_closes_with_window() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let alice_secret = secret(&s.env, 1);
+
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+
+    close_reveal(&s.env);
+    let result = s.beacon.try_reveal(&s.requester, &1, &alice, &alice_secret);
+    assert_beacon_error(&result, Error::RevealClosed);
+}
+
+// ============================================================================
+// Finalization and Slashing Tests
+// ============================================================================
+
+#[test]
+fn test_cannot_finalize_while_reveals_outstanding() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let bob = funded_contributor(&s);
+    let alice_secret = secret(&s.env, 1);
+
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+    s.beacon.commit(
+        &s.requester,
+        &1,
+        &bob,
+        &commitment(&s.env, &secret(&s.env, 2)),
+    );
+
+    let result = s.beacon.try_finalize(&s.requester, &1);
+    assert_beacon_error(&result, Error::RevealNotClosed);
+
+    open_reveal(&s.env);
+    s.beacon.reveal(&s.requester, &1, &alice, &alice_secret);
+    let result = s.beacon.try_finalize(&s.requester, &1);
+    assert_beacon_error(&result, Error::RevealNotClosed);
+}
+
+#[test]
+fn test_non_revealers_are_slashed_to_revealers() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let bob = funded_contributor(&s);
+    let carol = funded_contributor(&s);
+    let alice_secret = secret(&s.env, 1);
+    let bob_secret = secret(&s.env, 2);
+
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+    s.beacon
+        .commit(&s.requester, &1, &bob, &commitment(&s.env, &bob_secret));
+    s.beacon.commit(
+        &s.requester,
+        &1,
+        &carol,
+        &commitment(&s.env, &secret(&s.env, 3)),
+    );
+
+    open_reveal(&s.env);
+    s.beacon.reveal(&s.requester, &1, &alice, &alice_secret);
+    s.beacon.reveal(&s.requester, &1, &bob, &bob_secret);
+
+    advance_ledgers(&s.env, 60);
+    assert!(s.beacon.finalize(&s.requester, &1).is_some());
+
+    // Carol's bond is split between Alice and Bob
+    assert_eq!(s.token.balance(&alice), BALANCE + BOND / 2);
+    assert_eq!(s.token.balance(&bob), BALANCE + BOND / 2);
+    assert_eq!(s.token.balance(&carol), BALANCE - BOND);
+    assert_eq!(s.token.balance(&s.beacon.address), 0);
+
+    let result = s.beacon.try_finalize(&s.requester, &1);
+    assert_beacon_error(&result, Error::AlreadyFinalized);
+}
+
+#[test]
+fn test_round_without_reveals_is_reopened() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let bob = funded_contributor(&s);
+    let (alice_secret, bob_secret) = (secret(&s.env, 1), secret(&s.env, 2));
+
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon.commit(
+        &s.requester,
+        &1,
+        &alice,
+        &commitment(&s.env, &secret(&s.env, 9)),
+    );
+
+    close_reveal(&s.env);
+    assert_eq!(s.beacon.finalize(&s.requester, &1), None);
+
+    // The unclaimed bond goes to the admin and a fresh round starts now
+    assert_eq!(s.token.balance(&s.admin), BOND);
+    let round = s.beacon.get_round(&s.requester, &1);
+    assert!(round.contributions.is_empty());
+    assert_eq!(round.commit_deadline, s.env.ledger().sequence() + 60);
+
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+    s.beacon
+        .commit(&s.requester, &1, &bob, &commitment(&s.env, &bob_secret));
+    open_reveal(&s.env);
+    s.beacon.reveal(&s.requester, &1, &alice, &alice_secret);
+    s.beacon.reveal(&s.requester, &1, &bob, &bob_secret);
+    assert!(s.beacon.finalize(&s.requester, &1).is_some());
+}
+
+#[test]
+fn test_round_with_single_revealer_is_reopened() {
+    let s = setup_test();
+    let alice = funded_contributor(&s);
+    let bob = funded_contributor(&s);
+    let alice_secret = secret(&s.env, 1);
+
+    // Bob stays hidden, so Alice's secret alone would fix the seed
+    s.beacon.request_seed(&s.requester, &1);
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+    s.beacon.commit(
+        &s.requester,
+        &1,
+        &bob,
+        &commitment(&s.env, &secret(&s.env, 9)),
+    );
+    open_reveal(&s.env);
+    s.beacon.reveal(&s.requester, &1, &alice, &alice_secret);
+    let result = s.beacon.try_finalize(&s.requester, &1);
+    assert_beacon_error(&result, Error::RevealNotClosed);
+
+    advance_ledgers(&s.env, 60);
+    assert_eq!(s.beacon.finalize(&s.requester, &1), None);
+    assert_eq!(s.beacon.get_seed(&s.requester, &1), None);
+    assert_eq!(s.token.balance(&alice), BALANCE);
+    assert_eq!(s.token.balance(&bob), BALANCE - BOND);
+    assert_eq!(s.token.balance(&s.admin), BOND);
+    let round = s.beacon.get_round(&s.requester, &1);
+    assert!(round.contributions.is_empty());
+    assert_eq!(round.commit_deadline, s.env.ledger().sequence() + 60);
+}
+
+// ============================================================================
+// Admin Function Tests
+// ============================================================================
+
+#[test]
+fn test_config_applies_to_new_rounds() {
+    let s = setup_test();
+    let mut config = s.beacon.get_config();
+    assert_eq!(config.bond, BOND);
+    assert_eq!(config.min_contributors, 2);
+
+    config.bond = 0;
+    config.commit_ledgers = 10;
+    config.min_contributors = 1;
+    s.beacon.set_config(&config);
+    s.beacon.request_seed(&s.requester, &1);
+
+    // Without a bond, contributors need no tokens
+    let alice = Address::generate(&s.env);
+    let alice_secret = secret(&s.env, 1);
+    s.beacon
+        .commit(&s.requester, &1, &alice, &commitment(&s.env, &alice_secret));
+    advance_ledgers(&s.env, 11);
+    s.beacon.reveal(&s.requester, &1, &alice, &alice_secret);
+    assert!(s.beacon.finalize(&s.requester, &1).is_some());
+}
+
+#[test]
+fn test_invalid_config_rejected() {
+    let s = setup_test();
+    let config = BeaconConfig {
+        token: s.token.address.clone(),
+        bond: -1,
+        commit_ledgers: 10,
+        reveal_ledgers: 10,
+        min_contributors: 1,
+    };
+    let result = s.beacon.try_set_config(&config);
+    assert_beacon_error(&result, Error::InvalidConfig);
+
+    let config = BeaconConfig {
+        bond: 0,
+        reveal_ledgers: 0,
+        ..config
+    };
+    let result = s.beacon.try_set_config(&config);
+    assert_beacon_error(&result, Error::InvalidConfig);
+
+    let config = BeaconConfig {
+        reveal_ledgers: 10,
+        min_contributors: 0,
+        ..config
+    };
+    let result = s.beacon.try_set_config(&config);
+    assert_beacon_error(&result, Error::InvalidConfig);
+}
+
+#[test]
+fn test_set_admin() {
+    let s = setup_test();
+    let new_admin = Address::generate(&s.env);
+    s.beacon.set_admin(&new_admin);
+    assert_eq!(s.beacon.get_admin(), new_admin);
+}
@@ -0,0 +1,151 @@
+//! # Channels
+//!
+//! Off-chain play for a running game through the shared `state-channel` crate.
+//! Once every seat has opened a channel, hands are played as co-signed states off
+//! chain and on-chain moves are rejected. Only the outcome is submitted: a finished
+//! state settles at once and ends the Game Hub session the same way `reveal_winner` does,
+//! except a draw, which deals new hands on chain as `reveal_winner` does. A challenge
+//! window that closes without a finished state reopens on-chain play with a fresh turn
+//! deadline, so a seat that stopped signing can still be timed out with `claim_timeout`.
+
+use soroban_sdk::{contractimpl, Address, BytesN, Env, Vec};
+use state_channel::{Channel, ChannelGame, ChannelState, Settlement};
+
+// The contract's client and args types are needed by `#[contractimpl]` in this module
+use crate::{
+    next_deadline, redeal, DataKey, Error, Game, Phase, TwentyOneContract, TwentyOneContractArgs,
+    TwentyOneContractClient,
+};
+
+state_channel::impl_from_channel_error!(Error);
+
+#[contractimpl]
+impl TwentyOneContract {
+    /// Open a state channel for a running game.
+    /// Every seat authorizes `(session_id, key)` for the ed25519 key it signs states with.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `keys` - Channel key of each seat, in seat order
+    pub fn open_channel(env: Env, session_id: u32, keys: Vec<BytesN<32>>) -> Result<(), Error> {
+        state_channel::open_game::<Game>(&env, session_id, keys)
+    }
+
+    /// Submit the latest co-signed state. Anyone holding it can submit.
+    /// The first submission opens the challenge window; a higher nonce replaces it.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `state` - The state to submit
+    /// * `signatures` - Every seat's signature over `channel_state_digest(state)`, in seat order
+    pub fn submit_channel_state(
+        env: Env,
+        session_id: u32,
+        state: ChannelState,
+        signatures: Vec<BytesN<64>>,
+    ) -> Result<(), Error> {
+        state_channel::submit_game::<Game>(&env, session_id, state, signatures)
+    }
+
+    /// Open the challenge window when the table stopped signing before any state was
+    /// co-signed. Once it closes without a state, `settle_channel` reopens on-chain play.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the seated player challenging
+    pub fn challenge_channel(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        state_channel::challenge_game::<Game>(&env, session_id, &player)
+    }
+
+    /// Settle the channel. A finished state settles at once and ends the Game Hub session
+    /// with its winners; a finished draw deals new hands instead. Once the challenge window
+    /// closes without a finished state, the channel closes and the game is played on chain
+    /// again, with a fresh deadline for the seat on turn.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - Addresses of the winning players, empty if play went back on chain
+    pub fn settle_channel(env: Env, session_id: u32) -> Result<Vec<Address>, Error> {
+        let (mut game, settlement) = state_channel::settle_game::<Game>(&env, session_id)?;
+        let winners = match settlement {
+            Settlement::Winners(winners) if winners.len() < game.seats.len() => winners,
+            Settlement::Winners(_) => return resume_play(&env, session_id, game, true),
+            Settlement::Resume => return resume_play(&env, session_id, game, false),
+        };
+
+        // Call GameHub FIRST (before setting winners)
+        Self::end_game_with_hub(&env, session_id, &game.seats, &winners)?;
+
+        game.winners = winners.clone();
+        game.phase = Phase::Ended;
+        env.storage()
+            .temporary()
+            .set(&DataKey::Game(session_id), &game);
+        Ok(winners)
+    }
+
+    /// Get the channel of a game.
+    pub fn get_channel(env: Env, session_id: u32) -> Result<Channel, Error> {
+        Ok(state_channel::get(&env, session_id)?)
+    }
+
+    /// The digest every seat signs for a state of this contract's channels.
+    pub fn channel_state_digest(env: Env, state: ChannelState) -> BytesN<32> {
+        state_channel::state_digest(&env, &state)
+    }
+}
+
+/// On-chain moves are rejected while a game's channel is open
+pub(crate) fn ensure_no_channel(env: &Env, session_id: u32) -> Result<(), Error> {
+    if state_channel::is_open(env, session_id) {
+        return Err(Error::ChannelOpen);
+    }
+    Ok(())
+}
+
+/// Put a game back into on-chain play after its channel settled without winners.
+/// The seat on turn gets a fresh deadline; a draw deals new hands as in `reveal_winner`.
+fn resume_play(
+    env: &Env,
+    session_id: u32,
+    mut game: Game,
+    new_round: bool,
+) -> Result<Vec<Address>, Error> {
+    // A table still waiting on its opening hands is dealt by `deal`
+    if new_round && game.phase != Phase::Dealing {
+        redeal(env, session_id, &mut game)?;
+    }
+    if game.phase == Phase::Playing {
+        game.turn_deadline = next_deadline(env, &game.timeout);
+    }
+    env.storage()
+        .temporary()
+        .set(&DataKey::Game(session_id), &game);
+    Ok(Vec::new(env))
+}
+
+impl ChannelGame for Game {
+    type Error = Error;
+
+    fn load_running(env: &Env, session_id: u32) -> Result<Self, Error> {
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if !game.winners.is_empty() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        Ok(game)
+    }
+
+    fn seat_players(&self, env: &Env) -> Vec<Address> {
+        let mut players = Vec::new(env);
+        for seat in self.seats.iter() {
+            players.push_back(seat.player);
+        }
+        players
+    }
+}
@@ -2,16 +2,48 @@
 
 //! # Twenty-One Game
 //!
-//! A two-player card game where players try to get as close to 21 as possible without going over.
+//! A card game for 2-6 players where players try to get as close to 21 as possible without going over.
 //! Each player is dealt 2 cards and can choose to "hit" (draw another card) or "stick" (end their turn).
 //! Cards are valued 1-13 where Ace=1, 2-10=face value, Jack/Queen/King=10.
 //!
+//! **Seating and Turns:**
+//! Players sit at a table in the order they were given to `start_game`/`start_table`
+//! and act in seat order: the first seat plays all of its hands, then the next seat, and so on.
+//!
+//! **Turn Deadlines:**
+//! The seat to act must move within a configurable number of ledgers. Once the deadline
+//! passes, any other seated player can `claim_timeout`, which either sticks the slow
+//! seat's remaining hands or makes it forfeit, depending on the contract's timeout policy.
+//!
+//! **Doubling Down and Splitting:**
+//! On a fresh two-card hand a player may "double down" (double the points riding on the
+//! hand, take exactly one card and stick) or, holding a pair, "split" it into two hands that
+//! are played one after the other. Both actions lock the extra points with the Game Hub, so
+//! they need a hub with the extensions below.
+//! Each hand is scored independently against every hand of the other seats.
+//!
+//! **Randomness:**
+//! Without a beacon, cards are seeded from the session ID and the player addresses, so the
+//! opening hands are known before the game starts. When the admin configures a randomness
+//! beacon, starting a game requests a seed from the beacon instead and the table waits in
+//! the `Dealing` phase until anyone calls `deal` with the seed finalized. A finalized seed is
+//! public, so every later card comes from a beacon round of its own: `hit`, `double_down`
+//! and `split` only commit the seat to the draw and request the seed, and anyone applies
+//! the draw with `resolve_draw` once the round is finalized. The seat cannot move, and
+//! cannot be timed out, while a draw is pending. New hands after a draw wait on a fresh
+//! round in the `Dealing` phase too.
+//!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
+//! Two-seat games use the Game Hub's two-player `start_game`/`end_game` session, which is all
+//! the Game Hub interface defines. Tables of 3-6 seats (`start_game_multi`/`end_game_multi`)
+//! and extra points locked by doubling and splitting (`add_points`) are extensions only some
+//! hubs implement, such as `mock-game-hub`. They are off until the admin confirms the
+//! configured hub supports them with `set_hub_extensions`.
 
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror,
+    Address, Bytes, BytesN, Env, IntoVal, Vec, contract, contractclient, contracterror,
     contractimpl, contracttype, vec
 };
 
@@ -29,7 +61,29 @@ pub trait GameHub {
         player2_points: i128,
     );
 
+    // Extensions, only called when `set_hub_extensions` is on
+    fn start_game_multi(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    );
+
+    fn add_points(env: Env, session_id: u32, player: Address, points: i128);
+
     fn end_game(env: Env, session_id: u32, player1_won: bool);
+
+    fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>);
+}
+
+// Import RandomnessBeacon contract interface
+// Seeds are requested and read per (this contract, session ID)
+#[contractclient(name = "BeaconClient")]
+pub trait RandomnessBeacon {
+    fn request_seed(env: Env, requester: Address, request_id: u32);
+
+    fn get_seed(env: Env, requester: Address, request_id: u32) -> Option<BytesN<32>>;
 }
 
 // ============================================================================
@@ -51,6 +105,28 @@ pub enum Error {
     SelfPlay = 9,
     RoundOverflow = 10,
     InvalidHandData = 11,
+    DoubleDownNotAllowed = 12,
+    SplitNotAllowed = 13,
+    MaxHandsReached = 14,
+    InvalidTableSize = 15,
+    NotYourTurn = 16,
+    TurnNotExpired = 17,
+    NoActiveTurn = 18,
+    CannotClaimOwnTimeout = 19,
+    InvalidTimeout = 20,
+    SeedNotReady = 21,
+    AlreadyDealt = 22,
+    ChannelNotFound = 23,
+    ChannelExists = 24,
+    ChannelOpen = 25,
+    InvalidChannelState = 26,
+    StaleChannelState = 27,
+    ChallengeClosed = 28,
+    ChallengeOpen = 29,
+    NoChannelState = 30,
+    HubExtensionsRequired = 31,
+    DrawPending = 32,
+    NoPendingDraw = 33,
 }
 
 // ============================================================================
@@ -68,19 +144,74 @@ pub enum Error {
 // Data Types
 // ============================================================================
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hand {
+    pub cards: Bytes, // Each byte represents a card (1-13)
+    pub points: i128, // Points riding on this hand
+    pub doubled: bool,
+    pub stuck: bool,
+    pub busted: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Seat {
+    pub player: Address,
+    pub points: i128, // Total points locked, including doubles and splits
+    pub hands: Vec<Hand>,
+    pub active_hand: u32, // Index of the hand currently being played
+    pub stuck: bool,      // Set once every hand of the seat is finished
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Dealing, // Waiting for the randomness beacon seed before the opening deal
+    Playing, // A seat is on turn
+    Reveal,  // Every seat has finished, waiting for reveal_winner
+    Ended,   // Winners have been reported to the Game Hub
+}
+
+/// What happens to a seat that lets its turn deadline pass
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeoutPolicy {
+    AutoStick, // Every unfinished hand of the seat is stuck as it stands
+    Forfeit,   // Every hand of the seat is counted as busted
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeoutConfig {
+    pub turn_ledgers: u32, // Ledgers a seat has to make each move
+    pub policy: TimeoutPolicy,
+}
+
+/// A card draw waiting on its randomness beacon round
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DrawKind {
+    Hit,        // One card on the active hand
+    DoubleDown, // One card on the doubled active hand, which then sticks
+    Split,      // The second card of both hands of a split
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
-    pub player1: Address,
-    pub player2: Address,
-    pub player1_points: i128,
-    pub player2_points: i128,
-    pub player1_hand: Bytes,  // Each byte represents a card (1-13)
-    pub player2_hand: Bytes,  // Each byte represents a card (1-13)
-    pub player1_stuck: bool,
-    pub player2_stuck: bool,
-    pub winner: Option<Address>,
+    pub seats: Vec<Seat>,
+    pub turn: u32,                // Index of the seat to act, equal to seats.len() once all are done
+    pub winners: Vec<Address>,    // Empty while the game is running
     pub round: u32,
+    pub phase: Phase,
+    pub turn_deadline: u32,       // Ledger sequence after which the seat on turn can be timed out
+    pub timeout: TimeoutConfig,   // Timeout config in force when the game started
+    pub beacon: Option<Address>,  // Randomness beacon in force when the game started
+    pub seed: Option<BytesN<32>>, // Beacon seed the round's opening hands were dealt from
+    pub deal_request: u32,        // Beacon request ID the next opening deal waits on
+    pub pending_draw: Option<u32>, // Beacon request ID of the seat on turn's draw
+    pub draw_kind: DrawKind,       // What the pending draw deals
 }
 
 #[contracttype]
@@ -88,7 +219,11 @@ pub struct Game {
 pub enum DataKey {
     Game(u32),
     GameHubAddress,
+    BeaconAddress,
+    BeaconRequests,
+    HubExtensions,
     Admin,
+    TimeoutConfig,
 }
 
 // ============================================================================
@@ -101,6 +236,21 @@ pub enum DataKey {
 /// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
 const GAME_TTL_LEDGERS: u32 = 518_400;
 
+/// Maximum number of hands a player can hold after splitting
+const MAX_HANDS: u32 = 4;
+
+/// Smallest and largest supported table
+const MIN_SEATS: u32 = 2;
+const MAX_SEATS: u32 = 6;
+
+/// Default time a seat has to make each move (1 hour, ~5 seconds per ledger)
+const DEFAULT_TURN_TIMEOUT_LEDGERS: u32 = 720;
+
+/// Draws and re-deals take beacon request IDs from the top half of the u32 range. Opening
+/// deals request their seed under the session ID, so the two never meet while session IDs
+/// stay below it.
+const DRAW_REQUEST_BASE: u32 = 1 << 31;
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -134,6 +284,230 @@ fn deal_card(env: &Env, seed: BytesN<32>) -> u8 {
     env.prng().gen_range::<u64>(1..=13) as u8
 }
 
+/// Build the base seed for a round's opening deal.
+/// Seed components (all deterministic and identical between sim/submit):
+/// 1. Session ID - unique per game
+/// 2. Player addresses - every seat contributes (replaced by the beacon seed when there is one)
+/// 3. Round - only mixed in for re-deals after a draw
+///
+/// Note: We do NOT include ledger sequence or timestamp because those differ
+/// between simulation and submission.
+fn round_seed(
+    env: &Env,
+    session_id: u32,
+    players: &Vec<Address>,
+    beacon_seed: &Option<BytesN<32>>,
+    round: u32,
+) -> BytesN<32> {
+    let mut seed_bytes = Bytes::new(env);
+    seed_bytes.append(&Bytes::from_array(env, &session_id.to_be_bytes()));
+    match beacon_seed {
+        Some(beacon_seed) => seed_bytes.append(&Bytes::from(beacon_seed.clone())),
+        None => {
+            for player in players.iter() {
+                seed_bytes.append(&player.to_string().to_bytes());
+            }
+        }
+    }
+    if round > 1 {
+        seed_bytes.append(&Bytes::from_array(env, &round.to_be_bytes()));
+    }
+    env.crypto().keccak256(&seed_bytes).into()
+}
+
+/// Deal the opening two cards of a round to one seat.
+/// `seat_tag` (seat index + 1) keeps the seats' hands apart.
+fn deal_opening_hand(env: &Env, base_seed: &BytesN<32>, seat_tag: u8) -> Bytes {
+    let mut hand = Bytes::new(env);
+    for i in 0..2 {
+        let mut card_seed_bytes = Bytes::new(env);
+        card_seed_bytes.append(&Bytes::from(base_seed.clone()));
+        card_seed_bytes.append(&Bytes::from_array(env, &[i, seat_tag])); // [card_index, seat]
+        let card_seed = env.crypto().keccak256(&card_seed_bytes);
+        hand.push_back(deal_card(env, card_seed.into()));
+    }
+    hand
+}
+
+/// Deal the next card for one of a player's hands.
+/// `dealt` is the number of cards the seat holds across all of its hands. It grows with
+/// every draw, and a split only moves a card, so every draw of a round gets its own seed,
+/// even when the same hand is split again.
+fn deal_next_card(
+    env: &Env,
+    session_id: u32,
+    player: &Address,
+    beacon_seed: &Option<BytesN<32>>,
+    hand_index: u32,
+    dealt: u32,
+    round: u32,
+) -> u8 {
+    let mut seed_bytes = Bytes::new(env);
+    seed_bytes.append(&Bytes::from_array(env, &session_id.to_be_bytes()));
+    seed_bytes.append(&player.to_string().to_bytes());
+    if let Some(beacon_seed) = beacon_seed {
+        seed_bytes.append(&Bytes::from(beacon_seed.clone()));
+    }
+    seed_bytes.append(&Bytes::from_array(env, &hand_index.to_be_bytes()));
+    seed_bytes.append(&Bytes::from_array(env, &dealt.to_be_bytes()));
+    seed_bytes.append(&Bytes::from_array(env, &round.to_be_bytes()));
+    let card_seed = env.crypto().keccak256(&seed_bytes);
+    deal_card(env, card_seed.into())
+}
+
+/// Number of cards a seat holds across all of its hands
+fn cards_dealt(seat: &Seat) -> u32 {
+    seat.hands.iter().map(|hand| hand.cards.len()).sum()
+}
+
+/// Build a fresh, unplayed hand
+fn new_hand(cards: Bytes, points: i128) -> Hand {
+    Hand {
+        cards,
+        points,
+        doubled: false,
+        stuck: false,
+        busted: false,
+    }
+}
+
+/// Deal every seat a fresh single hand for `round`.
+/// Points locked so far (including doubles and splits) ride on the new hands.
+fn deal_round(
+    env: &Env,
+    session_id: u32,
+    seats: &mut Vec<Seat>,
+    beacon_seed: &Option<BytesN<32>>,
+    round: u32,
+) {
+    let mut players = Vec::new(env);
+    for seat in seats.iter() {
+        players.push_back(seat.player);
+    }
+    let base_seed = round_seed(env, session_id, &players, beacon_seed, round);
+
+    for i in 0..seats.len() {
+        let mut seat = seats.get_unchecked(i);
+        let cards = deal_opening_hand(env, &base_seed, (i + 1) as u8);
+        seat.hands = vec![env, new_hand(cards, seat.points)];
+        seat.active_hand = 0;
+        seat.stuck = false;
+        seats.set(i, seat);
+    }
+}
+
+/// Compare two finished hands.
+/// Returns `Some(true)` if `a` wins, `Some(false)` if `b` wins and `None` on a push.
+/// A busted hand loses to any standing hand; two busted hands push.
+fn compare_hands(a: &Hand, b: &Hand) -> Result<Option<bool>, Error> {
+    match (a.busted, b.busted) {
+        (true, true) => Ok(None),
+        (true, false) => Ok(Some(false)),
+        (false, true) => Ok(Some(true)),
+        (false, false) => {
+            let a_value = calculate_hand_value(&a.cards)?;
+            let b_value = calculate_hand_value(&b.cards)?;
+            if a_value > b_value {
+                Ok(Some(true))
+            } else if b_value > a_value {
+                Ok(Some(false))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Score every seat's hands against every other seat's hands.
+/// The winner of each pairing of hands collects the points riding on both hands.
+fn score_seats(env: &Env, seats: &Vec<Seat>) -> Result<Vec<i128>, Error> {
+    let mut scores = Vec::new(env);
+    for _ in 0..seats.len() {
+        scores.push_back(0i128);
+    }
+
+    for i in 0..seats.len() {
+        for j in (i + 1)..seats.len() {
+            for hand_i in seats.get_unchecked(i).hands.iter() {
+                for hand_j in seats.get_unchecked(j).hands.iter() {
+                    let pot = hand_i
+                        .points
+                        .checked_add(hand_j.points)
+                        .ok_or(Error::InvalidHandData)?;
+                    let pair_winner = match compare_hands(&hand_i, &hand_j)? {
+                        Some(true) => i,
+                        Some(false) => j,
+                        None => continue,
+                    };
+                    let score = scores
+                        .get_unchecked(pair_winner)
+                        .checked_add(pot)
+                        .ok_or(Error::InvalidHandData)?;
+                    scores.set(pair_winner, score);
+                }
+            }
+        }
+    }
+    Ok(scores)
+}
+
+/// Move `active_hand` past every finished hand.
+/// Returns true once the seat has no hand left to play.
+fn advance_active_hand(hands: &Vec<Hand>, active_hand: &mut u32) -> Result<bool, Error> {
+    while *active_hand < hands.len() {
+        let hand = hands.get(*active_hand).ok_or(Error::InvalidHandData)?;
+        if !hand.stuck && !hand.busted {
+            return Ok(false);
+        }
+        *active_hand += 1;
+    }
+    Ok(true)
+}
+
+/// True once a seat has finished with every hand busted
+fn busted_out(seat: &Seat) -> bool {
+    seat.stuck && seat.hands.iter().all(|hand| hand.busted)
+}
+
+/// Ledger sequence after which the seat now on turn can be timed out
+fn next_deadline(env: &Env, timeout: &TimeoutConfig) -> u32 {
+    env.ledger().sequence().saturating_add(timeout.turn_ledgers)
+}
+
+/// Open a beacon round of its own for a draw or re-deal
+fn request_beacon_seed(env: &Env, beacon: &Address) -> u32 {
+    let requests: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::BeaconRequests)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::BeaconRequests, &((requests + 1) % DRAW_REQUEST_BASE));
+    let request_id = DRAW_REQUEST_BASE | requests;
+    BeaconClient::new(env, beacon).request_seed(&env.current_contract_address(), &request_id);
+    request_id
+}
+
+/// Start the next round after a draw. Every seat gets new hands, dealt now without a
+/// beacon or by `deal` once a fresh beacon round is finalized.
+fn redeal(env: &Env, session_id: u32, game: &mut Game) -> Result<(), Error> {
+    game.round = game.round.checked_add(1).ok_or(Error::RoundOverflow)?;
+    game.turn = 0;
+    match game.beacon.clone() {
+        Some(beacon) => {
+            game.deal_request = request_beacon_seed(env, &beacon);
+            game.phase = Phase::Dealing;
+        }
+        None => {
+            deal_round(env, session_id, &mut game.seats, &None, game.round);
+            game.phase = Phase::Playing;
+        }
+    }
+    game.turn_deadline = next_deadline(env, &game.timeout);
+    Ok(())
+}
+
 // ============================================================================
 // Contract Definition
 // ============================================================================
@@ -158,7 +532,7 @@ impl TwentyOneContract {
 
     /// Start a new game between two players with points.
     /// This creates a session in the Game Hub and locks points before starting the game.
-    /// Each player is dealt 2 cards to start.
+    /// Each player is dealt one hand of 2 cards to start; player1 acts first.
     ///
     /// **CRITICAL:** This method requires authorization from THIS contract (not players).
     /// The Game Hub will call `game_id.require_auth()` which checks this contract's address.
@@ -177,84 +551,115 @@ impl TwentyOneContract {
         player1_points: i128,
         player2_points: i128,
     ) -> Result<(), Error> {
-        // Prevent self-play: Player 1 and Player 2 must be different
-        if player1 == player2 {
-            return Err(Error::SelfPlay);
-        }
+        Self::start_table(
+            env.clone(),
+            session_id,
+            vec![&env, player1, player2],
+            vec![&env, player1_points, player2_points],
+        )
+    }
 
-        // Require authentication from both players (they consent to committing points)
-        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
-        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
+    /// Start a new game at a table of 2-6 players with points.
+    /// Players take seats in the given order, which is also the order they act in.
+    /// Tables of more than two seats need the Game Hub extensions (`set_hub_extensions`).
+    /// Each player is dealt one hand of 2 cards to start. With a randomness beacon
+    /// configured, the seed is requested instead and the hands are dealt by `deal`.
+    ///
+    /// **CRITICAL:** This method requires authorization from THIS contract (not players).
+    /// The Game Hub will call `game_id.require_auth()` which checks this contract's address.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `players` - Addresses of the seated players, in seat order
+    /// * `points` - Points amount committed by each seat, in seat order
+    pub fn start_table(
+        env: Env,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    ) -> Result<(), Error> {
+        if players.len() < MIN_SEATS || players.len() > MAX_SEATS || points.len() != players.len() {
+            return Err(Error::InvalidTableSize);
+        }
+        if players.len() > 2 && !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        // Prevent self-play: every seat must be taken by a different player
+        for i in 0..players.len() {
+            for j in (i + 1)..players.len() {
+                if players.get_unchecked(i) == players.get_unchecked(j) {
+                    return Err(Error::SelfPlay);
+                }
+            }
+        }
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        // Require authentication from every player (they consent to committing points)
+        for i in 0..players.len() {
+            players.get_unchecked(i).require_auth_for_args(vec![
+                &env,
+                session_id.into_val(&env),
+                points.get_unchecked(i).into_val(&env),
+            ]);
+        }
 
         // Call the Game Hub to start the session and lock points
         // This requires THIS contract's authorization (env.current_contract_address())
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
-
-        // Generate deterministic seed for card dealing
-        // Seed components (all deterministic and identical between sim/submit):
-        // 1. Session ID - unique per game
-        // 2. Player addresses - both players contribute
-        //
-        // Note: We do NOT include ledger sequence or timestamp because those differ
-        // between simulation and submission.
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-        seed_bytes.append(&player1.to_string().to_bytes());
-        seed_bytes.append(&player2.to_string().to_bytes());
-        let base_seed = env.crypto().keccak256(&seed_bytes);
-
-        // Deal initial hands (2 cards each)
-        // Use different seeds for each card to ensure variety
-        let mut player1_hand = Bytes::new(&env);
-        let mut player2_hand = Bytes::new(&env);
-
-        // Deal 2 cards to player1
-        for i in 0..2 {
-            let mut card_seed_bytes = Bytes::new(&env);
-            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-            card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
-            let card_seed = env.crypto().keccak256(&card_seed_bytes);
-            player1_hand.push_back(deal_card(&env, card_seed.into()));
-        }
-
-        // Deal 2 cards to player2
-        for i in 0..2 {
-            let mut card_seed_bytes = Bytes::new(&env);
-            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-            card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
-            let card_seed = env.crypto().keccak256(&card_seed_bytes);
-            player2_hand.push_back(deal_card(&env, card_seed.into()));
-        }
-
-        // Create game
+        let game_hub = Self::game_hub(&env);
+        if players.len() == 2 {
+            game_hub.start_game(
+                &env.current_contract_address(),
+                &session_id,
+                &players.get_unchecked(0),
+                &players.get_unchecked(1),
+                &points.get_unchecked(0),
+                &points.get_unchecked(1),
+            );
+        } else {
+            game_hub.start_game_multi(&env.current_contract_address(), &session_id, &players, &points);
+        }
+
+        // Seat the players
+        let mut seats = Vec::new(&env);
+        for i in 0..players.len() {
+            seats.push_back(Seat {
+                player: players.get_unchecked(i),
+                points: points.get_unchecked(i),
+                hands: Vec::new(&env),
+                active_hand: 0,
+                stuck: false,
+            });
+        }
+
+        // Deal initial hands (2 cards each) now, or once the beacon seed is ready
+        let beacon = Self::get_beacon(env.clone());
+        let phase = match &beacon {
+            Some(beacon) => {
+                BeaconClient::new(&env, beacon)
+                    .request_seed(&env.current_contract_address(), &session_id);
+                Phase::Dealing
+            }
+            None => {
+                deal_round(&env, session_id, &mut seats, &None, 1);
+                Phase::Playing
+            }
+        };
+
+        // Create game, the first seat is on turn once dealt
+        let timeout = Self::get_timeout_config(env.clone());
         let game = Game {
-            player1: player1.clone(),
-            player2: player2.clone(),
-            player1_points,
-            player2_points,
-            player1_hand,
-            player2_hand,
-            player1_stuck: false,
-            player2_stuck: false,
-            winner: None,
+            seats,
+            turn: 0,
+            winners: Vec::new(&env),
             round: 1,
+            phase,
+            turn_deadline: next_deadline(&env, &timeout),
+            timeout,
+            beacon,
+            seed: None,
+            deal_request: session_id,
+            pending_draw: None,
+            draw_kind: DrawKind::Hit,
         };
 
         // Store game in temporary storage with 30-day TTL
@@ -271,16 +676,13 @@ impl TwentyOneContract {
         Ok(())
     }
 
-    /// Player draws another card ("hit").
-    /// If the player's hand value exceeds 21, they bust and lose immediately.
+    /// Deal the opening hands of a game waiting on its randomness beacon seed.
+    /// Anyone can call this once the beacon has finalized the seed; the first seat is
+    /// then on turn.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player drawing a card
-    pub fn hit(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
-        player.require_auth();
-
-        // Get game from temporary storage
+    pub fn deal(env: Env, session_id: u32) -> Result<(), Error> {
         let key = DataKey::Game(session_id);
         let mut game: Game = env
             .storage()
@@ -288,141 +690,256 @@ impl TwentyOneContract {
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
-        // Check game is still active (no winner yet)
-        if game.winner.is_some() {
-            return Err(Error::GameAlreadyEnded);
+        if game.phase != Phase::Dealing {
+            return Err(Error::AlreadyDealt);
         }
+        channel::ensure_no_channel(&env, session_id)?;
+        let beacon = game.beacon.clone().ok_or(Error::AlreadyDealt)?;
+        let seed = BeaconClient::new(&env, &beacon)
+            .get_seed(&env.current_contract_address(), &game.deal_request)
+            .ok_or(Error::SeedNotReady)?;
+
+        game.seed = Some(seed);
+        deal_round(&env, session_id, &mut game.seats, &game.seed, game.round);
+        game.phase = Phase::Playing;
+        game.turn_deadline = next_deadline(&env, &game.timeout);
+        env.storage().temporary().set(&key, &game);
 
-        // Check player hasn't stuck yet
-        let is_player1 = player == game.player1;
-        let is_player2 = player == game.player2;
+        Ok(())
+    }
 
-        if !is_player1 && !is_player2 {
-            return Err(Error::NotPlayer);
-        }
+    /// Player draws another card ("hit") on their active hand.
+    /// If the hand value exceeds 21 the hand busts and play moves to the player's next hand.
+    /// With a randomness beacon the card is drawn by `resolve_draw`.
+    /// A player who busts every hand they hold is out; once only one seat is left
+    /// standing, that seat wins immediately.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player drawing a card
+    pub fn hit(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
 
-        if is_player1 && game.player1_stuck {
-            return Err(Error::AlreadyStuck);
-        }
+        let (mut game, seat_index) = Self::load_active_game(&env, session_id, &player)?;
+        Self::draw(&env, session_id, &mut game, seat_index, DrawKind::Hit)
+    }
 
-        if is_player2 && game.player2_stuck {
-            return Err(Error::AlreadyStuck);
-        }
+    /// Player chooses to stick (end their active hand with its current cards).
+    /// Once every seat has finished all of its hands, the game can be revealed.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player sticking
+    pub fn stick(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
 
-        // Generate seed for new card based on current hand size
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-        seed_bytes.append(&player.to_string().to_bytes());
+        let (mut game, seat_index) = Self::load_active_game(&env, session_id, &player)?;
+        let mut seat = game.seats.get_unchecked(seat_index);
+        let mut hand = seat.hands.get(seat.active_hand).ok_or(Error::InvalidHandData)?;
 
-        let card_count = if is_player1 {
-            game.player1_hand.len()
-        } else {
-            game.player2_hand.len()
-        };
+        hand.stuck = true;
+        seat.hands.set(seat.active_hand, hand);
 
-        seed_bytes.append(&Bytes::from_array(&env, &(card_count as u32).to_be_bytes()));
-        seed_bytes.append(&Bytes::from_array(&env, &game.round.to_be_bytes()));
+        Self::finish_move(&env, session_id, &mut game, seat_index, seat)
+    }
 
-        let card_seed = env.crypto().keccak256(&seed_bytes);
-        let new_card = deal_card(&env, card_seed.into());
+    /// Double down on the active hand.
+    /// Only allowed on a two-card hand. The points riding on the hand are doubled
+    /// (the extra points are locked with the Game Hub), exactly one card is drawn
+    /// and the hand is stuck automatically. The player authorizes
+    /// `(session_id, extra_points)`, like the `(session_id, points)` of taking a seat.
+    /// With a randomness beacon the card is drawn by `resolve_draw`.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player doubling down
+    pub fn double_down(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        let (mut game, seat_index) = Self::load_active_game(&env, session_id, &player)?;
+        let mut seat = game.seats.get_unchecked(seat_index);
+        let mut hand = seat.hands.get(seat.active_hand).ok_or(Error::InvalidHandData)?;
+
+        if hand.cards.len() != 2 {
+            return Err(Error::DoubleDownNotAllowed);
+        }
+        if !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
 
-        // Add card to player's hand
-        if is_player1 {
-            game.player1_hand.push_back(new_card);
+        // The player authorizes the points they add, as they do when taking a seat
+        let extra_points = hand.points;
+        player.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            extra_points.into_val(&env),
+        ]);
+
+        // Lock the extra points with the Game Hub before touching game state
+        Self::game_hub(&env).add_points(&session_id, &player, &extra_points);
+        seat.points = seat
+            .points
+            .checked_add(extra_points)
+            .ok_or(Error::InvalidHandData)?;
+        hand.points = hand
+            .points
+            .checked_add(extra_points)
+            .ok_or(Error::InvalidHandData)?;
+        hand.doubled = true;
+        seat.hands.set(seat.active_hand, hand);
+        game.seats.set(seat_index, seat);
 
-            // Check if player busted
-            let hand_value = calculate_hand_value(&game.player1_hand)?;
-            if hand_value > 21 {
-                // Player 1 busted, player 2 wins
-                // Call GameHub FIRST (before setting winner)
-                Self::end_game_with_hub(&env, session_id, false)?;
+        Self::draw(&env, session_id, &mut game, seat_index, DrawKind::DoubleDown)
+    }
 
-                // Only set winner AFTER GameHub succeeds
-                game.winner = Some(game.player2.clone());
-                env.storage().temporary().set(&key, &game);
+    /// Split a pair into two hands.
+    /// Only allowed on a two-card hand holding two cards of the same rank. The second
+    /// card moves to a new hand (played after the player's existing hands) carrying the
+    /// same points, which are locked with the Game Hub. Each hand is then dealt a
+    /// second card (by `resolve_draw` with a randomness beacon) and played independently.
+    /// The player authorizes `(session_id, split_points)`.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player splitting
+    pub fn split(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        let (mut game, seat_index) = Self::load_active_game(&env, session_id, &player)?;
+        let mut seat = game.seats.get_unchecked(seat_index);
+        let mut hand = seat.hands.get(seat.active_hand).ok_or(Error::InvalidHandData)?;
+
+        if hand.cards.len() != 2 {
+            return Err(Error::SplitNotAllowed);
+        }
+        let first_card = hand.cards.get(0).ok_or(Error::InvalidHandData)?;
+        let second_card = hand.cards.get(1).ok_or(Error::InvalidHandData)?;
+        if first_card != second_card {
+            return Err(Error::SplitNotAllowed);
+        }
+        if seat.hands.len() >= MAX_HANDS {
+            return Err(Error::MaxHandsReached);
+        }
+        if !Self::get_hub_extensions(env.clone()) {
+            return Err(Error::HubExtensionsRequired);
+        }
 
-                // Return Ok - caller should check game.winner to see if game ended
-                return Ok(());
-            }
-        } else {
-            game.player2_hand.push_back(new_card);
+        let split_points = hand.points;
+        player.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            split_points.into_val(&env),
+        ]);
+
+        // Lock the points for the new hand with the Game Hub before touching game state
+        Self::game_hub(&env).add_points(&session_id, &player, &split_points);
+        seat.points = seat
+            .points
+            .checked_add(split_points)
+            .ok_or(Error::InvalidHandData)?;
 
-            // Check if player busted
-            let hand_value = calculate_hand_value(&game.player2_hand)?;
-            if hand_value > 21 {
-                // Player 2 busted, player 1 wins
-                // Call GameHub FIRST (before setting winner)
-                Self::end_game_with_hub(&env, session_id, true)?;
+        // Keep the first card on the active hand and move the second to a new hand
+        hand.cards = Bytes::from_array(&env, &[first_card]);
+        seat.hands.set(seat.active_hand, hand);
+        seat.hands
+            .push_back(new_hand(Bytes::from_array(&env, &[second_card]), split_points));
+        game.seats.set(seat_index, seat);
 
-                // Only set winner AFTER GameHub succeeds
-                game.winner = Some(game.player1.clone());
-                env.storage().temporary().set(&key, &game);
+        // Each hand is then dealt its second card
+        Self::draw(&env, session_id, &mut game, seat_index, DrawKind::Split)
+    }
 
-                // Return Ok - caller should check game.winner to see if game ended
-                return Ok(());
-            }
+    /// Apply the pending draw once the beacon has finalized its seed. Anyone can call this.
+    /// The seat on turn then gets a fresh deadline.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn resolve_draw(env: Env, session_id: u32) -> Result<(), Error> {
+        let mut game = Self::get_game(env.clone(), session_id)?;
+        if !game.winners.is_empty() {
+            return Err(Error::GameAlreadyEnded);
         }
-
-        // Store updated game
-        env.storage().temporary().set(&key, &game);
-
-        Ok(())
+        channel::ensure_no_channel(&env, session_id)?;
+        let request_id = game.pending_draw.ok_or(Error::NoPendingDraw)?;
+        let beacon = game.beacon.clone().ok_or(Error::NoPendingDraw)?;
+        let seed = BeaconClient::new(&env, &beacon)
+            .get_seed(&env.current_contract_address(), &request_id)
+            .ok_or(Error::SeedNotReady)?;
+
+        game.pending_draw = None;
+        let (seat_index, kind) = (game.turn, game.draw_kind);
+        Self::apply_draw(&env, session_id, &mut game, seat_index, kind, &Some(seed))
     }
 
-    /// Player chooses to stick (end their turn with current hand).
-    /// If both players have stuck, the game can be revealed.
+    /// Claim that the seat on turn let its deadline pass.
+    /// Any other seated player can call this once the ledger sequence is past the
+    /// turn deadline. Under `AutoStick` the slow seat's unfinished hands are stuck as
+    /// they stand; under `Forfeit` all of its hands count as busted. Play then moves on
+    /// to the next seat exactly as if the slow seat had finished its turn.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player sticking
-    pub fn stick(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
-        player.require_auth();
+    /// * `claimer` - Address of the seated player making the claim
+    pub fn claim_timeout(env: Env, session_id: u32, claimer: Address) -> Result<(), Error> {
+        claimer.require_auth();
 
-        // Get game from temporary storage
-        let key = DataKey::Game(session_id);
         let mut game: Game = env
             .storage()
             .temporary()
-            .get(&key)
+            .get(&DataKey::Game(session_id))
             .ok_or(Error::GameNotFound)?;
 
-        // Check game is still active (no winner yet)
-        if game.winner.is_some() {
+        if game.phase == Phase::Ended {
             return Err(Error::GameAlreadyEnded);
         }
+        channel::ensure_no_channel(&env, session_id)?;
+        let claimer_index = Self::seat_of(&game, &claimer)?;
+        if game.phase != Phase::Playing {
+            return Err(Error::NoActiveTurn);
+        }
+        if claimer_index == game.turn {
+            return Err(Error::CannotClaimOwnTimeout);
+        }
+        // No seat is on the clock while its draw waits on the beacon
+        if game.pending_draw.is_some() {
+            return Err(Error::DrawPending);
+        }
+        if env.ledger().sequence() <= game.turn_deadline {
+            return Err(Error::TurnNotExpired);
+        }
 
-        // Mark player as stuck
-        if player == game.player1 {
-            if game.player1_stuck {
-                return Err(Error::AlreadyStuck);
-            }
-            game.player1_stuck = true;
-        } else if player == game.player2 {
-            if game.player2_stuck {
-                return Err(Error::AlreadyStuck);
+        let seat_index = game.turn;
+        let mut seat = game.seats.get_unchecked(seat_index);
+        let mut hands = Vec::new(&env);
+        for (i, mut hand) in seat.hands.iter().enumerate() {
+            match game.timeout.policy {
+                TimeoutPolicy::AutoStick => {
+                    if i as u32 >= seat.active_hand && !hand.busted {
+                        hand.stuck = true;
+                    }
+                }
+                TimeoutPolicy::Forfeit => {
+                    hand.stuck = false;
+                    hand.busted = true;
+                }
             }
-            game.player2_stuck = true;
-        } else {
-            return Err(Error::NotPlayer);
+            hands.push_back(hand);
         }
+        seat.hands = hands;
 
-        // Store updated game
-        env.storage().temporary().set(&key, &game);
-
-        Ok(())
+        Self::finish_move(&env, session_id, &mut game, seat_index, seat)
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
-    /// Can only be called after both players have stuck.
-    /// This calculates hand values, determines the winner (closest to 21),
-    /// and handles draws by dealing new hands.
+    /// Reveal the winners of the game and submit outcome to GameHub.
+    /// Can only be called after every seat has finished all of its hands.
+    /// Every hand is compared against every hand of the other seats (closest to 21
+    /// without going over wins the pairing, busted hands lose) and the winner of each
+    /// pairing collects the points riding on both hands. The seats collecting the most
+    /// win and share the payout. If every seat collects the same, the round is a draw
+    /// and new hands are dealt.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `Vec<Address>` - Addresses of the winning players
+    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Vec<Address>, Error> {
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -431,81 +948,53 @@ impl TwentyOneContract {
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
-        // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+        // Check if game already ended (has winners)
+        if !game.winners.is_empty() {
+            return Ok(game.winners);
         }
+        // Channel games are settled with settle_channel
+        channel::ensure_no_channel(&env, session_id)?;
 
-        // Check both players have stuck
-        if !game.player1_stuck || !game.player2_stuck {
+        // Check every seat has finished all of its hands
+        if game.phase == Phase::Dealing {
+            return Err(Error::SeedNotReady);
+        }
+        if game.seats.iter().any(|seat| !seat.stuck) {
             return Err(Error::BothPlayersNotStuck);
         }
 
-        // Calculate hand values
-        let player1_value = calculate_hand_value(&game.player1_hand)?;
-        let player2_value = calculate_hand_value(&game.player2_hand)?;
-
-        // Determine winner (closest to 21 without going over)
-        // Note: Bust conditions are already handled in hit(), so values should be <= 21
-        let winner = if player1_value > player2_value {
-            // Player 1 is closer to 21
-            game.player1.clone()
-        } else if player2_value > player1_value {
-            // Player 2 is closer to 21
-            game.player2.clone()
-        } else {
-            // Draw - deal new hands and continue
-            game.round = game.round.checked_add(1).ok_or(Error::RoundOverflow)?;
-
-            // Reset stuck flags
-            game.player1_stuck = false;
-            game.player2_stuck = false;
-
-            // Clear hands
-            game.player1_hand = Bytes::new(&env);
-            game.player2_hand = Bytes::new(&env);
-
-            // Deal new hands (2 cards each)
-            let mut seed_bytes = Bytes::new(&env);
-            seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-            seed_bytes.append(&game.player1.to_string().to_bytes());
-            seed_bytes.append(&game.player2.to_string().to_bytes());
-            seed_bytes.append(&Bytes::from_array(&env, &game.round.to_be_bytes()));
-            let base_seed = env.crypto().keccak256(&seed_bytes);
-
-            // Deal 2 cards to player1
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player1_hand.push_back(deal_card(&env, card_seed.into()));
+        // Score every pairing of hands
+        let scores = score_seats(&env, &game.seats)?;
+        let mut best = i128::MIN;
+        for score in scores.iter() {
+            best = best.max(score);
+        }
+        let mut winners = Vec::new(&env);
+        for i in 0..game.seats.len() {
+            if scores.get_unchecked(i) == best {
+                winners.push_back(game.seats.get_unchecked(i).player);
             }
+        }
 
-            // Deal 2 cards to player2
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player2_hand.push_back(deal_card(&env, card_seed.into()));
-            }
+        if winners.len() == game.seats.len() {
+            // Draw - deal new hands and continue
+            redeal(&env, session_id, &mut game)?;
 
             // Store updated game and return error to indicate draw
             env.storage().temporary().set(&key, &game);
 
             return Err(Error::Draw);
-        };
+        }
 
-        // Call GameHub FIRST (before setting winner)
-        let player1_won = winner == game.player1;
-        Self::end_game_with_hub(&env, session_id, player1_won)?;
+        // Call GameHub FIRST (before setting winners)
+        Self::end_game_with_hub(&env, session_id, &game.seats, &winners)?;
 
-        // Only update game with winner AFTER GameHub succeeds
-        game.winner = Some(winner.clone());
+        // Only update game with winners AFTER GameHub succeeds
+        game.winners = winners.clone();
+        game.phase = Phase::Ended;
         env.storage().temporary().set(&key, &game);
 
-        Ok(winner)
+        Ok(winners)
     }
 
     /// Get game information.
@@ -514,7 +1003,7 @@ impl TwentyOneContract {
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Game` - The game state (includes hands and winner after game ends)
+    /// * `Game` - The game state (includes hands and winners after game ends)
     pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
         let key = DataKey::Game(session_id);
         env.storage()
@@ -524,50 +1013,246 @@ impl TwentyOneContract {
     }
 
     /// Get the current hand value for a player.
+    /// This is the value of the hand being played, or of the last hand once the
+    /// player has finished.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     /// * `player` - Address of the player
     ///
     /// # Returns
-    /// * `u32` - The total value of the player's hand
+    /// * `u32` - The total value of the player's current hand
     pub fn get_hand_value(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
-        let key = DataKey::Game(session_id);
-        let game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
+        let game = Self::get_game(env, session_id)?;
+        let seat = game.seats.get_unchecked(Self::seat_of(&game, &player)?);
+        if seat.hands.is_empty() {
+            return Err(Error::SeedNotReady);
+        }
+        let index = seat.active_hand.min(seat.hands.len() - 1);
+        let hand = seat.hands.get(index).ok_or(Error::InvalidHandData)?;
+        calculate_hand_value(&hand.cards)
+    }
 
-        if player == game.player1 {
-            calculate_hand_value(&game.player1_hand)
-        } else if player == game.player2 {
-            calculate_hand_value(&game.player2_hand)
-        } else {
-            Err(Error::NotPlayer)
+    /// Get the value of every hand a player holds, in play order.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    ///
+    /// # Returns
+    /// * `Vec<u32>` - The total value of each of the player's hands
+    pub fn get_hand_values(env: Env, session_id: u32, player: Address) -> Result<Vec<u32>, Error> {
+        let game = Self::get_game(env.clone(), session_id)?;
+        let seat = game.seats.get_unchecked(Self::seat_of(&game, &player)?);
+        let mut values = Vec::new(&env);
+        for hand in seat.hands.iter() {
+            values.push_back(calculate_hand_value(&hand.cards)?);
         }
+        Ok(values)
     }
 
     // ========================================================================
     // Internal Helper Functions
     // ========================================================================
 
-    /// Helper to end game with the Game Hub
-    fn end_game_with_hub(env: &Env, session_id: u32, player1_won: bool) -> Result<(), Error> {
-        // Get GameHub address
+    /// Get a GameHub client for the configured hub
+    fn game_hub(env: &Env) -> GameHubClient<'_> {
         let game_hub_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::GameHubAddress)
             .expect("GameHub address not set");
+        GameHubClient::new(env, &game_hub_addr)
+    }
+
+    /// Resolve which seat a player occupies
+    fn seat_of(game: &Game, player: &Address) -> Result<u32, Error> {
+        game.seats
+            .iter()
+            .position(|seat| seat.player == *player)
+            .map(|index| index as u32)
+            .ok_or(Error::NotPlayer)
+    }
+
+    /// Load a game that `player` can act in right now.
+    /// Returns the game and the player's seat index.
+    fn load_active_game(env: &Env, session_id: u32, player: &Address) -> Result<(Game, u32), Error> {
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+
+        // Check game is still active (no winners yet)
+        if !game.winners.is_empty() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        channel::ensure_no_channel(env, session_id)?;
+
+        let seat_index = Self::seat_of(&game, player)?;
+
+        // Nothing to play until the opening hands are dealt
+        if game.phase == Phase::Dealing {
+            return Err(Error::SeedNotReady);
+        }
+        if game.seats.get_unchecked(seat_index).stuck {
+            return Err(Error::AlreadyStuck);
+        }
+        if game.pending_draw.is_some() {
+            return Err(Error::DrawPending);
+        }
+
+        // Seats act in order
+        if game.turn != seat_index {
+            return Err(Error::NotYourTurn);
+        }
+
+        Ok((game, seat_index))
+    }
+
+    /// Draw for the seat on turn: now without a beacon, otherwise once `resolve_draw`
+    /// has the seed of a beacon round opened for this draw
+    fn draw(
+        env: &Env,
+        session_id: u32,
+        game: &mut Game,
+        seat_index: u32,
+        kind: DrawKind,
+    ) -> Result<(), Error> {
+        let Some(beacon) = game.beacon.clone() else {
+            return Self::apply_draw(env, session_id, game, seat_index, kind, &None);
+        };
+        game.pending_draw = Some(request_beacon_seed(env, &beacon));
+        game.draw_kind = kind;
+        env.storage()
+            .temporary()
+            .set(&DataKey::Game(session_id), game);
+        Ok(())
+    }
+
+    /// Deal the cards of a draw and finish the seat's move
+    fn apply_draw(
+        env: &Env,
+        session_id: u32,
+        game: &mut Game,
+        seat_index: u32,
+        kind: DrawKind,
+        seed: &Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        let mut seat = game.seats.get_unchecked(seat_index);
+        let mut hand = seat.hands.get(seat.active_hand).ok_or(Error::InvalidHandData)?;
+        let dealt = cards_dealt(&seat);
+        hand.cards.push_back(deal_next_card(
+            env,
+            session_id,
+            &seat.player,
+            seed,
+            seat.active_hand,
+            dealt,
+            game.round,
+        ));
+
+        match kind {
+            DrawKind::Hit => {
+                if calculate_hand_value(&hand.cards)? > 21 {
+                    hand.busted = true;
+                }
+            }
+            // Doubling forces a stick, unless the single card busted the hand
+            DrawKind::DoubleDown => {
+                if calculate_hand_value(&hand.cards)? > 21 {
+                    hand.busted = true;
+                } else {
+                    hand.stuck = true;
+                }
+            }
+            // The split hand was added last
+            DrawKind::Split => {
+                let split_index = seat.hands.len() - 1;
+                let mut split_hand = seat.hands.get(split_index).ok_or(Error::InvalidHandData)?;
+                split_hand.cards.push_back(deal_next_card(
+                    env,
+                    session_id,
+                    &seat.player,
+                    seed,
+                    split_index,
+                    dealt + 1,
+                    game.round,
+                ));
+                seat.hands.set(split_index, split_hand);
+            }
+        }
+        seat.hands.set(seat.active_hand, hand);
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(env, &game_hub_addr);
+        Self::finish_move(env, session_id, game, seat_index, seat)
+    }
+
+    /// Write back a seat after a move, advance to its next unfinished hand (or the
+    /// next seat) and end the game once only one seat is left standing.
+    /// Every move gives the seat on turn a fresh deadline.
+    fn finish_move(
+        env: &Env,
+        session_id: u32,
+        game: &mut Game,
+        seat_index: u32,
+        mut seat: Seat,
+    ) -> Result<(), Error> {
+        seat.stuck = advance_active_hand(&seat.hands, &mut seat.active_hand)?;
+        if seat.stuck {
+            game.turn = seat_index + 1;
+        }
+        game.seats.set(seat_index, seat);
+        if game.turn >= game.seats.len() {
+            game.phase = Phase::Reveal;
+        } else {
+            game.turn_deadline = next_deadline(env, &game.timeout);
+        }
+
+        // Every other seat busted out, the remaining seat wins
+        let mut standing = Vec::new(env);
+        for seat in game.seats.iter() {
+            if !busted_out(&seat) {
+                standing.push_back(seat.player);
+            }
+        }
+        if standing.len() == 1 {
+            // Call GameHub FIRST (before setting winners)
+            Self::end_game_with_hub(env, session_id, &game.seats, &standing)?;
+
+            // Only set winners AFTER GameHub succeeds
+            game.winners = standing;
+            game.phase = Phase::Ended;
+        }
+
+        // Store updated game
+        // Caller should check game.winners to see if game ended
+        env.storage()
+            .temporary()
+            .set(&DataKey::Game(session_id), game);
+
+        Ok(())
+    }
+
+    /// Helper to end game with the Game Hub.
+    /// A two-seat game with a single winner settles through the two-player session,
+    /// everything else reports the full list of winners through the hub extensions.
+    fn end_game_with_hub(
+        env: &Env,
+        session_id: u32,
+        seats: &Vec<Seat>,
+        winners: &Vec<Address>,
+    ) -> Result<(), Error> {
+        let game_hub = Self::game_hub(env);
 
         // Call the Game Hub to end the session
         // This unlocks points and updates standings
         // Event emitted by the Game Hub contract (GameEnded)
-        game_hub.end_game(&session_id, &player1_won);
+        if seats.len() == 2 && winners.len() == 1 {
+            let player1_won = winners.get_unchecked(0) == seats.get_unchecked(0).player;
+            game_hub.end_game(&session_id, &player1_won);
+        } else {
+            game_hub.end_game_multi(&session_id, winners);
+        }
 
         Ok(())
     }
@@ -630,6 +1315,98 @@ impl TwentyOneContract {
             .set(&DataKey::GameHubAddress, &new_hub);
     }
 
+    /// Get the turn timeout config applied to newly started games
+    ///
+    /// # Returns
+    /// * `TimeoutConfig` - The configured timeout, or the default (720 ledgers, auto-stick)
+    pub fn get_timeout_config(env: Env) -> TimeoutConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TimeoutConfig)
+            .unwrap_or(TimeoutConfig {
+                turn_ledgers: DEFAULT_TURN_TIMEOUT_LEDGERS,
+                policy: TimeoutPolicy::AutoStick,
+            })
+    }
+
+    /// Set the turn timeout config for newly started games.
+    /// Games already in progress keep the config they started with.
+    ///
+    /// # Arguments
+    /// * `config` - Ledgers per move (must be non-zero) and the timeout policy
+    pub fn set_timeout_config(env: Env, config: TimeoutConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if config.turn_ledgers == 0 {
+            return Err(Error::InvalidTimeout);
+        }
+        env.storage().instance().set(&DataKey::TimeoutConfig, &config);
+
+        Ok(())
+    }
+
+    /// Get the randomness beacon used by newly started games
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The beacon contract address, or `None` if games seed themselves
+    pub fn get_beacon(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BeaconAddress)
+    }
+
+    /// Set the randomness beacon used by newly started games.
+    /// Games already in progress keep the beacon (or lack of one) they started with.
+    ///
+    /// # Arguments
+    /// * `beacon` - The beacon contract address, or `None` to go back to self-seeded games
+    pub fn set_beacon(env: Env, beacon: Option<Address>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        match beacon {
+            Some(beacon) => env.storage().instance().set(&DataKey::BeaconAddress, &beacon),
+            None => env.storage().instance().remove(&DataKey::BeaconAddress),
+        }
+    }
+
+    /// Whether the configured Game Hub supports the multi-seat and `add_points` extensions
+    ///
+    /// # Returns
+    /// * `bool` - True once the admin has turned the extensions on (off by default)
+    pub fn get_hub_extensions(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::HubExtensions)
+            .unwrap_or(false)
+    }
+
+    /// Turn the Game Hub extensions on or off.
+    /// Only turn them on for a hub that implements `start_game_multi`, `end_game_multi`
+    /// and `add_points`; the standard Game Hub only has `start_game` and `end_game`.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether tables of 3+ seats, doubling down and splitting are allowed
+    pub fn set_hub_extensions(env: Env, enabled: bool) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::HubExtensions, &enabled);
+    }
+
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
@@ -646,6 +1423,14 @@ impl TwentyOneContract {
     }
 }
 
+// ============================================================================
+// Channels
+// ============================================================================
+
+mod channel;
+
+pub use state_channel::{Channel, ChannelState};
+
 // ============================================================================
 // Tests
 // ============================================================================
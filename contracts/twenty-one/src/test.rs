@@ -7,9 +7,15 @@
 // For full integration tests with the real GameHub contract, see:
 // contracts/game_hub/src/tests/twenty_one_integration.rs
 
-use crate::{Error, TwentyOneContract, TwentyOneContractClient};
-use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env};
+use crate::{
+    ChannelState, Error, Game, Hand, Phase, Seat, TimeoutConfig, TimeoutPolicy, TwentyOneContract,
+    TwentyOneContractClient,
+};
+use ed25519_dalek::{Signer as _, SigningKey};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, Ledger as _};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec,
+};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -18,6 +24,14 @@ use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env};
 #[contract]
 pub struct MockGameHub;
 
+#[contracttype]
+#[derive(Clone)]
+pub enum HubDataKey {
+    AddedPoints(u32, Address),
+    Player1Won(u32),
+    Winners(u32),
+}
+
 #[contractimpl]
 impl MockGameHub {
     pub fn start_game(
@@ -32,15 +46,133 @@ impl MockGameHub {
         // Mock implementation - does nothing
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+    pub fn add_points(env: Env, session_id: u32, player: Address, points: i128) {
+        // Track extra points so tests can check doubles and splits were locked
+        let key = HubDataKey::AddedPoints(session_id, player);
+        let total: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(total + points));
+    }
+
+    pub fn start_game_multi(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _players: Vec<Address>,
+        _points: Vec<i128>,
+    ) {
         // Mock implementation - does nothing
     }
 
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        // Track the result so tests can check two-player settlements
+        env.storage()
+            .instance()
+            .set(&HubDataKey::Player1Won(session_id), &player1_won);
+    }
+
+    pub fn get_player1_won(env: Env, session_id: u32) -> Option<bool> {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::Player1Won(session_id))
+    }
+
+    pub fn end_game_multi(env: Env, session_id: u32, winners: Vec<Address>) {
+        // Track winners so tests can check multi-winner payouts
+        env.storage()
+            .instance()
+            .set(&HubDataKey::Winners(session_id), &winners);
+    }
+
+    pub fn get_winners(env: Env, session_id: u32) -> Option<Vec<Address>> {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::Winners(session_id))
+    }
+
+    pub fn get_added_points(env: Env, session_id: u32, player: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&HubDataKey::AddedPoints(session_id, player))
+            .unwrap_or(0)
+    }
+
     pub fn add_game(_env: Env, _game_address: Address) {
         // Mock implementation - does nothing
     }
 }
 
+// ============================================================================
+// Standard GameHub for Unit Testing
+// ============================================================================
+
+// Only the two-player Game Hub interface, without any of the extensions
+#[contract]
+pub struct StandardGameHub;
+
+#[contractimpl]
+impl StandardGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        env.storage().instance().set(&session_id, &player1_won);
+    }
+
+    pub fn get_player1_won(env: Env, session_id: u32) -> Option<bool> {
+        env.storage().instance().get(&session_id)
+    }
+}
+
+// ============================================================================
+// Mock RandomnessBeacon for Unit Testing
+// ============================================================================
+
+#[contracttype]
+pub enum BeaconDataKey {
+    Requested(Address, u32),
+    Seed(Address, u32),
+}
+
+#[contract]
+pub struct MockBeacon;
+
+#[contractimpl]
+impl MockBeacon {
+    pub fn request_seed(env: Env, requester: Address, request_id: u32) {
+        // Track requests so tests can check when the seed was asked for
+        env.storage()
+            .instance()
+            .set(&BeaconDataKey::Requested(requester, request_id), &true);
+    }
+
+    pub fn get_seed(env: Env, requester: Address, request_id: u32) -> Option<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&BeaconDataKey::Seed(requester, request_id))
+    }
+
+    pub fn was_requested(env: Env, requester: Address, request_id: u32) -> bool {
+        env.storage()
+            .instance()
+            .has(&BeaconDataKey::Requested(requester, request_id))
+    }
+
+    /// Stand-in for a finalized commit-reveal round
+    pub fn finalize(env: Env, requester: Address, request_id: u32, seed: BytesN<32>) {
+        env.storage()
+            .instance()
+            .set(&BeaconDataKey::Seed(requester, request_id), &seed);
+    }
+}
+
 // ============================================================================
 // Test Helpers
 // ============================================================================
@@ -81,6 +213,9 @@ fn setup_test() -> (
     // Register twenty-one as a whitelisted game (mock does nothing)
     game_hub.add_game(&contract_id);
 
+    // The mock implements the multi-seat and add_points extensions
+    client.set_hub_extensions(&true);
+
     let player1 = Address::generate(&env);
     let player2 = Address::generate(&env);
 
@@ -151,6 +286,98 @@ fn calculate_hand_value_helper(hand: &Bytes) -> u32 {
     total
 }
 
+/// Helper to get a seat of the table
+fn seat(game: &Game, index: u32) -> Seat {
+    game.seats.get(index).unwrap()
+}
+
+/// Helper to get the cards of a player's first hand
+fn first_hand(game: &Game, is_player1: bool) -> Bytes {
+    let index = if is_player1 { 0 } else { 1 };
+    seat(game, index).hands.get(0).unwrap().cards
+}
+
+/// Helper to get one of player1's hands
+fn player1_hand_at(client: &TwentyOneContractClient, session_id: u32, index: u32) -> Hand {
+    seat(&client.get_game(&session_id), 0)
+        .hands
+        .get(index)
+        .unwrap()
+}
+
+/// Start games on increasing session ids until player1 is dealt a hand matching `want_pair`.
+/// Dealing is deterministic, so this always lands on the same session for a given setup.
+fn start_game_until(
+    client: &TwentyOneContractClient,
+    player1: &Address,
+    player2: &Address,
+    first_session: u32,
+    want_pair: bool,
+) -> u32 {
+    let mut session_id = first_session;
+    loop {
+        client.start_game(&session_id, player1, player2, &100_0000000, &100_0000000);
+        let cards = first_hand(&client.get_game(&session_id), true);
+        let is_pair = cards.get(0).unwrap() == cards.get(1).unwrap();
+        if is_pair == want_pair {
+            return session_id;
+        }
+        session_id += 1;
+    }
+}
+
+/// Reference scoring for a whole table, indexed by seat
+fn score_table_helper(game: &Game) -> [i128; 6] {
+    let mut scores = [0i128; 6];
+    for i in 0..game.seats.len() {
+        for j in (i + 1)..game.seats.len() {
+            for hand_i in seat(game, i).hands.iter() {
+                for hand_j in seat(game, j).hands.iter() {
+                    let value_i = calculate_hand_value_helper(&hand_i.cards);
+                    let value_j = calculate_hand_value_helper(&hand_j.cards);
+                    let pot = hand_i.points + hand_j.points;
+                    match (hand_i.busted, hand_j.busted) {
+                        (true, true) => {}
+                        (true, false) => scores[j as usize] += pot,
+                        (false, true) => scores[i as usize] += pot,
+                        (false, false) if value_i > value_j => scores[i as usize] += pot,
+                        (false, false) if value_j > value_i => scores[j as usize] += pot,
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    scores
+}
+
+/// Reference scoring: every hand of player1 against every hand of player2,
+/// the winner of a pairing collects the points riding on both hands
+fn score_helper(game: &Game) -> (i128, i128) {
+    let mut score1 = 0i128;
+    let mut score2 = 0i128;
+    for hand1 in seat(game, 0).hands.iter() {
+        for hand2 in seat(game, 1).hands.iter() {
+            let value1 = calculate_hand_value_helper(&hand1.cards);
+            let value2 = calculate_hand_value_helper(&hand2.cards);
+            let pot = hand1.points + hand2.points;
+            let player1_wins = match (hand1.busted, hand2.busted) {
+                (true, true) => None,
+                (true, false) => Some(false),
+                (false, true) => Some(true),
+                (false, false) if value1 == value2 => None,
+                (false, false) => Some(value1 > value2),
+            };
+            match player1_wins {
+                Some(true) => score1 += pot,
+                Some(false) => score2 += pot,
+                None => {}
+            }
+        }
+    }
+    (score1, score2)
+}
+
 // ============================================================================
 // Basic Game Flow Tests
 // ============================================================================
@@ -167,28 +394,37 @@ fn test_complete_game_simple() {
 
     // Get game to verify initial state
     let game = client.get_game(&session_id);
-    assert!(game.winner.is_none()); // Game is still active
-    assert_eq!(game.player1, player1);
-    assert_eq!(game.player2, player2);
-    assert_eq!(game.player1_points, points);
-    assert_eq!(game.player2_points, points);
-    assert_eq!(game.player1_hand.len(), 2); // 2 cards dealt
-    assert_eq!(game.player2_hand.len(), 2); // 2 cards dealt
-    assert_eq!(game.player1_stuck, false);
-    assert_eq!(game.player2_stuck, false);
+    assert!(game.winners.is_empty()); // Game is still active
+    assert_eq!(seat(&game, 0).player, player1);
+    assert_eq!(seat(&game, 1).player, player2);
+    assert_eq!(seat(&game, 0).points, points);
+    assert_eq!(seat(&game, 1).points, points);
+    assert_eq!(seat(&game, 0).hands.len(), 1); // One hand each
+    assert_eq!(seat(&game, 1).hands.len(), 1);
+    assert_eq!(first_hand(&game, true).len(), 2); // 2 cards dealt
+    assert_eq!(first_hand(&game, false).len(), 2); // 2 cards dealt
+    assert!(!seat(&game, 0).stuck);
+    assert!(!seat(&game, 1).stuck);
 
     // Both players stick immediately (no hits)
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
-    // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    // Reveal winner (retry on draws, which deal new hands)
+    let mut result = client.try_reveal_winner(&session_id);
+    while result == Err(Ok(Error::Draw)) {
+        client.stick(&session_id, &player1);
+        client.stick(&session_id, &player2);
+        result = client.try_reveal_winner(&session_id);
+    }
+    let winners = result.unwrap().unwrap();
+    assert_eq!(winners.len(), 1);
+    let winner = winners.get(0).unwrap();
     assert!(winner == player1 || winner == player2);
 
     // Verify game is ended
     let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some());
-    assert_eq!(final_game.winner.unwrap(), winner);
+    assert_eq!(final_game.winners, winners);
 }
 
 #[test]
@@ -201,17 +437,23 @@ fn test_initial_cards_dealt() {
     let game = client.get_game(&session_id);
 
     // Each player should have exactly 2 cards
-    assert_eq!(game.player1_hand.len(), 2);
-    assert_eq!(game.player2_hand.len(), 2);
+    let player1_hand = first_hand(&game, true);
+    let player2_hand = first_hand(&game, false);
+    assert_eq!(player1_hand.len(), 2);
+    assert_eq!(player2_hand.len(), 2);
+
+    // Hands start with the committed points riding on them
+    assert_eq!(seat(&game, 0).hands.get(0).unwrap().points, 100_0000000);
+    assert_eq!(seat(&game, 1).hands.get(0).unwrap().points, 100_0000000);
 
     // Cards should be in valid range (1-13)
-    for i in 0..game.player1_hand.len() {
-        let card = game.player1_hand.get(i).unwrap();
-        assert!(card >= 1 && card <= 13, "Card should be between 1-13");
+    for i in 0..player1_hand.len() {
+        let card = player1_hand.get(i).unwrap();
+        assert!((1..=13).contains(&card), "Card should be between 1-13");
     }
-    for i in 0..game.player2_hand.len() {
-        let card = game.player2_hand.get(i).unwrap();
-        assert!(card >= 1 && card <= 13, "Card should be between 1-13");
+    for i in 0..player2_hand.len() {
+        let card = player2_hand.get(i).unwrap();
+        assert!((1..=13).contains(&card), "Card should be between 1-13");
     }
 }
 
@@ -227,13 +469,13 @@ fn test_get_hand_value() {
     let player2_value = client.get_hand_value(&session_id, &player2);
 
     // Hand values should be reasonable (2-20 for 2 cards, since max is 10 per card)
-    assert!(player1_value >= 2 && player1_value <= 20);
-    assert!(player2_value >= 2 && player2_value <= 20);
+    assert!((2..=20).contains(&player1_value));
+    assert!((2..=20).contains(&player2_value));
 
     // Verify hand value matches calculation
     let game = client.get_game(&session_id);
-    let expected_value1 = calculate_hand_value_helper(&game.player1_hand);
-    let expected_value2 = calculate_hand_value_helper(&game.player2_hand);
+    let expected_value1 = calculate_hand_value_helper(&first_hand(&game, true));
+    let expected_value2 = calculate_hand_value_helper(&first_hand(&game, false));
 
     assert_eq!(player1_value, expected_value1);
     assert_eq!(player2_value, expected_value2);
@@ -247,13 +489,16 @@ fn test_hit_adds_card() {
     client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
 
     let initial_game = client.get_game(&session_id);
-    let initial_hand_size = initial_game.player1_hand.len();
+    let initial_hand_size = first_hand(&initial_game, true).len();
 
     // Player 1 hits
     client.hit(&session_id, &player1);
 
     let after_hit_game = client.get_game(&session_id);
-    assert_eq!(after_hit_game.player1_hand.len(), initial_hand_size + 1);
+    assert_eq!(
+        first_hand(&after_hit_game, true).len(),
+        initial_hand_size + 1
+    );
 }
 
 #[test]
@@ -279,7 +524,7 @@ fn test_multiple_hits_allowed() {
     client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
 
     let initial_game = client.get_game(&session_id);
-    let initial_hand_size = initial_game.player1_hand.len();
+    let initial_hand_size = first_hand(&initial_game, true).len();
 
     // Player 1 hits multiple times (be careful not to bust in deterministic test)
     // This test may fail if player1 busts, but we're testing the mechanics
@@ -288,13 +533,13 @@ fn test_multiple_hits_allowed() {
     // If first hit succeeds (didn't bust), try another
     if result1.is_ok() {
         let mid_game = client.get_game(&session_id);
-        assert_eq!(mid_game.player1_hand.len(), initial_hand_size + 1);
+        assert_eq!(first_hand(&mid_game, true).len(), initial_hand_size + 1);
 
         let result2 = client.try_hit(&session_id, &player1);
         // Could succeed or fail (bust), both are valid
         if result2.is_ok() {
             let final_game = client.get_game(&session_id);
-            assert_eq!(final_game.player1_hand.len(), initial_hand_size + 2);
+            assert_eq!(first_hand(&final_game, true).len(), initial_hand_size + 2);
         }
     }
 }
@@ -305,7 +550,7 @@ fn test_multiple_hits_allowed() {
 
 #[test]
 fn test_closer_to_21_wins() {
-    let (_env, client, _hub, player1, player2) = setup_test();
+    let (env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 7u32;
     client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
@@ -314,18 +559,18 @@ fn test_closer_to_21_wins() {
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
-    let winner = client.reveal_winner(&session_id);
+    let result = client.try_reveal_winner(&session_id);
 
     // Get final hand values
     let game = client.get_game(&session_id);
-    let player1_value = calculate_hand_value_helper(&game.player1_hand);
-    let player2_value = calculate_hand_value_helper(&game.player2_hand);
+    let player1_value = calculate_hand_value_helper(&first_hand(&game, true));
+    let player2_value = calculate_hand_value_helper(&first_hand(&game, false));
 
     // Winner should be closer to 21
     if player1_value > player2_value {
-        assert_eq!(winner, player1);
+        assert_eq!(result, Ok(Ok(vec![&env, player1])));
     } else if player2_value > player1_value {
-        assert_eq!(winner, player2);
+        assert_eq!(result, Ok(Ok(vec![&env, player2])));
     }
     // If equal, test will continue (draw handling)
 }
@@ -365,9 +610,10 @@ fn test_bust_detection() {
 
         // Check if game ended (player busted)
         let game = client.get_game(&session_id);
-        if game.winner.is_some() {
+        if let Some(winner) = game.winners.get(0) {
             busted = true;
-            assert_eq!(game.winner.unwrap(), player2, "Player 2 should win when player 1 busts");
+            assert_eq!(winner, player2, "Player 2 should win when player 1 busts");
+            assert!(seat(&game, 0).hands.get(0).unwrap().busted);
 
             // After a bust, subsequent operations should fail with GameAlreadyEnded
             let result = client.try_hit(&session_id, &player1);
@@ -393,9 +639,9 @@ fn test_cannot_hit_after_bust() {
 
         // Check if game ended (player busted)
         let game = client.get_game(&session_id);
-        if game.winner.is_some() {
+        if let Some(winner) = game.winners.get(0) {
             did_bust = true;
-            assert_eq!(game.winner.unwrap(), player2);
+            assert_eq!(winner, player2);
 
             // Try to hit again after busting - should fail with GameAlreadyEnded
             let result = client.try_hit(&session_id, &player1);
@@ -423,8 +669,8 @@ fn test_draw_starts_new_round() {
     client.stick(&session_id, &player2);
 
     let game = client.get_game(&session_id);
-    let player1_value = calculate_hand_value_helper(&game.player1_hand);
-    let player2_value = calculate_hand_value_helper(&game.player2_hand);
+    let player1_value = calculate_hand_value_helper(&first_hand(&game, true));
+    let player2_value = calculate_hand_value_helper(&first_hand(&game, false));
 
     // Only test draw behavior if hands are equal
     if player1_value == player2_value {
@@ -435,10 +681,10 @@ fn test_draw_starts_new_round() {
         // Verify new round was created
         let game_after = client.get_game(&session_id);
         assert_eq!(game_after.round, 2); // Round should increment
-        assert_eq!(game_after.player1_stuck, false); // Flags reset
-        assert_eq!(game_after.player2_stuck, false);
-        assert_eq!(game_after.player1_hand.len(), 2); // New cards dealt
-        assert_eq!(game_after.player2_hand.len(), 2);
+        assert!(!seat(&game_after, 0).stuck); // Flags reset
+        assert!(!seat(&game_after, 1).stuck);
+        assert_eq!(first_hand(&game_after, true).len(), 2); // New cards dealt
+        assert_eq!(first_hand(&game_after, false).len(), 2);
     }
     // If not a draw, test passes (no assertion needed)
 }
@@ -572,7 +818,7 @@ fn test_cannot_stick_after_game_ended() {
     if result.is_ok() {
         // Game ended, verify stuck flag is set
         let game = client.get_game(&session_id);
-        assert!(game.player1_stuck);
+        assert!(seat(&game, 0).stuck);
 
         // Try to stick again after game ended
         let stick_result = client.try_stick(&session_id, &player1);
@@ -592,9 +838,7 @@ fn test_reveal_winner_idempotent() {
 
     // First reveal
     let result1 = client.try_reveal_winner(&session_id);
-    if result1.is_ok() {
-        let winner1 = result1.unwrap().unwrap();
-
+    if let Ok(Ok(winner1)) = result1 {
         // Second reveal should return same winner (idempotent)
         let winner2 = client.reveal_winner(&session_id);
         assert_eq!(winner1, winner2);
@@ -626,12 +870,12 @@ fn test_multiple_games_independent() {
     let game1_check = client.get_game(&session1);
     let game2_check = client.get_game(&session2);
 
-    if game1_check.winner.is_none() {
+    if game1_check.winners.is_empty() {
         client.stick(&session1, &player1);
         client.stick(&session1, &player2);
     }
 
-    if game2_check.winner.is_none() {
+    if game2_check.winners.is_empty() {
         client.stick(&session2, &player3);
         client.stick(&session2, &player4);
     }
@@ -640,8 +884,8 @@ fn test_multiple_games_independent() {
     let game1 = client.get_game(&session1);
     let game2 = client.get_game(&session2);
 
-    assert_eq!(game1.player1, player1);
-    assert_eq!(game2.player1, player3);
+    assert_eq!(seat(&game1, 0).player, player1);
+    assert_eq!(seat(&game2, 0).player, player3);
 }
 
 #[test]
@@ -660,10 +904,10 @@ fn test_multiple_sessions() {
     let game1 = client.get_game(&session1);
     let game2 = client.get_game(&session2);
 
-    assert_eq!(game1.player1, player1);
-    assert_eq!(game2.player1, player3);
-    assert_eq!(game1.player1_points, 100_0000000);
-    assert_eq!(game2.player1_points, 50_0000000);
+    assert_eq!(seat(&game1, 0).player, player1);
+    assert_eq!(seat(&game2, 0).player, player3);
+    assert_eq!(seat(&game1, 0).points, 100_0000000);
+    assert_eq!(seat(&game2, 0).points, 50_0000000);
 }
 
 #[test]
@@ -677,8 +921,8 @@ fn test_asymmetric_points() {
     client.start_game(&session_id, &player1, &player2, &points1, &points2);
 
     let game = client.get_game(&session_id);
-    assert_eq!(game.player1_points, points1);
-    assert_eq!(game.player2_points, points2);
+    assert_eq!(seat(&game, 0).points, points1);
+    assert_eq!(seat(&game, 1).points, points2);
 
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
@@ -703,12 +947,13 @@ fn test_face_cards_worth_10() {
     let game = client.get_game(&session_id);
 
     // Manually verify card values
-    for i in 0..game.player1_hand.len() {
-        let card = game.player1_hand.get(i).unwrap() as u32;
+    let player1_hand = first_hand(&game, true);
+    for i in 0..player1_hand.len() {
+        let card = player1_hand.get(i).unwrap() as u32;
         let expected_value = if card >= 10 { 10 } else { card };
 
         // Verify this matches our expectation
-        assert!(expected_value >= 1 && expected_value <= 10);
+        assert!((1..=10).contains(&expected_value));
     }
 }
 
@@ -724,11 +969,971 @@ fn test_hand_value_calculation() {
 
     // Calculate expected value manually
     let game = client.get_game(&session_id);
-    let expected_value = calculate_hand_value_helper(&game.player1_hand);
+    let expected_value = calculate_hand_value_helper(&first_hand(&game, true));
 
     assert_eq!(contract_value, expected_value);
 }
 
+// ============================================================================
+// Double Down Tests
+// ============================================================================
+
+#[test]
+fn test_double_down_doubles_points_and_sticks() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 40u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    client.double_down(&session_id, &player1);
+    // The player signs for the points they add, like a stake
+    let auths = env.auths();
+    assert_eq!(auths[0].0, player1);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            Symbol::new(&env, "double_down"),
+            vec![
+                &env,
+                session_id.into_val(&env),
+                100_0000000i128.into_val(&env)
+            ],
+        ))
+    );
+
+    let game = client.get_game(&session_id);
+    let hand = seat(&game, 0).hands.get(0).unwrap();
+    assert_eq!(hand.cards.len(), 3); // Exactly one card drawn
+    assert!(hand.doubled);
+    assert_eq!(hand.points, 200_0000000);
+    assert_eq!(seat(&game, 0).points, 200_0000000);
+    assert_eq!(hub.get_added_points(&session_id, &player1), 100_0000000);
+
+    if hand.busted {
+        // Busting the only hand ends the game
+        assert_eq!(game.winners, vec![&env, player2.clone()]);
+    } else {
+        // Doubling forces a stick
+        assert!(hand.stuck);
+        assert!(seat(&game, 0).stuck);
+        let result = client.try_hit(&session_id, &player1);
+        assert_twenty_one_error(&result, Error::AlreadyStuck);
+    }
+}
+
+#[test]
+fn test_double_down_requires_two_card_hand() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    // Find a game where player1 survives one hit
+    let mut session_id = 41u32;
+    loop {
+        client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+        client.hit(&session_id, &player1);
+        if client.get_game(&session_id).winners.is_empty() {
+            break;
+        }
+        session_id += 1;
+    }
+
+    let result = client.try_double_down(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::DoubleDownNotAllowed);
+}
+
+#[test]
+fn test_doubled_hand_wins_double_points() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 60u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.double_down(&session_id, &player1);
+
+    if !client.get_game(&session_id).winners.is_empty() {
+        return; // Busted on the doubled card
+    }
+    client.stick(&session_id, &player2);
+
+    let game = client.get_game(&session_id);
+    let (score1, score2) = score_helper(&game);
+    let result = client.try_reveal_winner(&session_id);
+    if score1 == score2 {
+        assert_twenty_one_error(&result, Error::Draw);
+    } else {
+        // The pot of a pairing includes the doubled stake
+        assert_eq!(score1.max(score2), 300_0000000);
+        let expected = if score1 > score2 { player1 } else { player2 };
+        assert_eq!(result.unwrap().unwrap(), vec![&env, expected]);
+    }
+}
+
+// ============================================================================
+// Split Tests
+// ============================================================================
+
+#[test]
+fn test_split_creates_two_hands() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = start_game_until(&client, &player1, &player2, 100, true);
+    let pair_card = first_hand(&client.get_game(&session_id), true)
+        .get(0)
+        .unwrap();
+
+    client.split(&session_id, &player1);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(seat(&game, 0).hands.len(), 2);
+    assert_eq!(seat(&game, 0).active_hand, 0);
+    assert_eq!(seat(&game, 0).points, 200_0000000);
+    assert_eq!(hub.get_added_points(&session_id, &player1), 100_0000000);
+
+    for hand in seat(&game, 0).hands.iter() {
+        // Each hand keeps one card of the pair and is dealt a second card
+        assert_eq!(hand.cards.len(), 2);
+        assert_eq!(hand.cards.get(0).unwrap(), pair_card);
+        assert_eq!(hand.points, 100_0000000);
+    }
+
+    let values = client.get_hand_values(&session_id, &player1);
+    assert_eq!(values.len(), 2);
+}
+
+#[test]
+fn test_split_requires_pair() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = start_game_until(&client, &player1, &player2, 200, false);
+    let result = client.try_split(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::SplitNotAllowed);
+}
+
+#[test]
+fn test_split_hands_played_in_order() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = start_game_until(&client, &player1, &player2, 300, true);
+    client.split(&session_id, &player1);
+
+    // Sticking the first hand moves play to the second hand
+    client.stick(&session_id, &player1);
+    let game = client.get_game(&session_id);
+    assert_eq!(seat(&game, 0).active_hand, 1);
+    assert!(!seat(&game, 0).stuck);
+    assert!(player1_hand_at(&client, session_id, 0).stuck);
+
+    // Hitting now draws onto the second hand
+    let second_len = player1_hand_at(&client, session_id, 1).cards.len();
+    client.hit(&session_id, &player1);
+    assert_eq!(player1_hand_at(&client, session_id, 0).cards.len(), 2);
+    assert_eq!(
+        player1_hand_at(&client, session_id, 1).cards.len(),
+        second_len + 1
+    );
+}
+
+#[test]
+fn test_bust_on_one_split_hand_continues_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = start_game_until(&client, &player1, &player2, 400, true);
+    client.split(&session_id, &player1);
+
+    // Hit the first split hand until it busts
+    while !player1_hand_at(&client, session_id, 0).busted {
+        client.hit(&session_id, &player1);
+    }
+
+    // Busting one split hand does not end the game; play moves to the next hand
+    let game = client.get_game(&session_id);
+    assert!(game.winners.is_empty());
+    assert_eq!(seat(&game, 0).active_hand, 1);
+    assert!(!seat(&game, 0).stuck);
+
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+
+    // The busted hand loses its pairing, the standing hand is scored normally
+    let game = client.get_game(&session_id);
+    let (score1, score2) = score_helper(&game);
+    assert!(score2 >= 200_0000000);
+    let result = client.try_reveal_winner(&session_id);
+    if score1 == score2 {
+        assert_twenty_one_error(&result, Error::Draw);
+    } else {
+        let expected = if score1 > score2 { player1 } else { player2 };
+        assert_eq!(result.unwrap().unwrap(), vec![&env, expected]);
+    }
+}
+
+#[test]
+fn test_bust_on_all_split_hands_loses() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = start_game_until(&client, &player1, &player2, 500, true);
+    client.split(&session_id, &player1);
+
+    // Bust the first hand, the game continues
+    while !player1_hand_at(&client, session_id, 0).busted {
+        client.hit(&session_id, &player1);
+    }
+    assert!(client.get_game(&session_id).winners.is_empty());
+
+    // Bust the second hand, player2 wins without having to act
+    while client.get_game(&session_id).winners.is_empty() {
+        client.hit(&session_id, &player1);
+    }
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.winners, vec![&env, player2.clone()]);
+    assert!(seat(&game, 0).hands.iter().all(|hand| hand.busted));
+
+    let result = client.try_stick(&session_id, &player2);
+    assert_twenty_one_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_double_down_on_split_hand() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = start_game_until(&client, &player1, &player2, 600, true);
+    client.split(&session_id, &player1);
+    client.double_down(&session_id, &player1);
+
+    let game = client.get_game(&session_id);
+    let first = seat(&game, 0).hands.get(0).unwrap();
+    assert!(first.doubled);
+    assert_eq!(first.points, 200_0000000);
+    assert_eq!(first.cards.len(), 3);
+    assert_eq!(seat(&game, 0).points, 300_0000000);
+    assert_eq!(hub.get_added_points(&session_id, &player1), 200_0000000);
+
+    // Whatever the doubled card, play moves on to the second hand
+    assert!(game.winners.is_empty());
+    assert_eq!(seat(&game, 0).active_hand, 1);
+}
+
+#[test]
+fn test_resplit_deals_new_cards() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    // Find a game where splitting the pair deals the first hand another pair
+    let mut first_session = 700;
+    let session_id = loop {
+        let session_id = start_game_until(&client, &player1, &player2, first_session, true);
+        client.split(&session_id, &player1);
+        let cards = player1_hand_at(&client, session_id, 0).cards;
+        if cards.get(0).unwrap() == cards.get(1).unwrap() {
+            break session_id;
+        }
+        first_session = session_id + 1;
+    };
+    let first_split = seat(&client.get_game(&session_id), 0).hands;
+
+    client.split(&session_id, &player1);
+
+    let game = client.get_game(&session_id);
+    let hands = seat(&game, 0).hands;
+    assert_eq!(hands.len(), 3);
+    assert_eq!(seat(&game, 0).points, 300_0000000);
+    assert_eq!(hub.get_added_points(&session_id, &player1), 200_0000000);
+
+    // Neither hand dealt by the re-split repeats the cards of the first split
+    let resplit_card = hands.get(0).unwrap().cards.get(1).unwrap();
+    let new_hand_card = hands.get(2).unwrap().cards.get(1).unwrap();
+    assert_ne!(
+        resplit_card,
+        first_split.get(0).unwrap().cards.get(1).unwrap()
+    );
+    assert_ne!(
+        new_hand_card,
+        first_split.get(1).unwrap().cards.get(1).unwrap()
+    );
+    assert_eq!(hands.get(1).unwrap(), first_split.get(1).unwrap());
+}
+
+// ============================================================================
+// Multi-Player Table Tests
+// ============================================================================
+
+fn three_players(env: &Env) -> Vec<Address> {
+    vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ]
+}
+
+#[test]
+fn test_start_table_seats_players_in_order() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+    let points = vec![&env, 100_0000000i128, 50_0000000, 25_0000000];
+
+    let session_id = 70u32;
+    client.start_table(&session_id, &players, &points);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.seats.len(), 3);
+    assert_eq!(game.turn, 0);
+    assert_eq!(game.round, 1);
+    for i in 0..3 {
+        let seat = seat(&game, i);
+        assert_eq!(seat.player, players.get(i).unwrap());
+        assert_eq!(seat.points, points.get(i).unwrap());
+        assert_eq!(seat.hands.len(), 1);
+        assert_eq!(seat.hands.get(0).unwrap().cards.len(), 2);
+        assert_eq!(seat.hands.get(0).unwrap().points, points.get(i).unwrap());
+    }
+}
+
+#[test]
+fn test_start_table_rejects_bad_sizes() {
+    let (env, client, _hub, player1, _player2) = setup_test();
+
+    // Too few players
+    let result = client.try_start_table(&71, &vec![&env, player1], &vec![&env, 100i128]);
+    assert_twenty_one_error(&result, Error::InvalidTableSize);
+
+    // Too many players
+    let mut players = Vec::new(&env);
+    let mut points = Vec::new(&env);
+    for _ in 0..7 {
+        players.push_back(Address::generate(&env));
+        points.push_back(100i128);
+    }
+    let result = client.try_start_table(&72, &players, &points);
+    assert_twenty_one_error(&result, Error::InvalidTableSize);
+
+    // Points must be given for every seat
+    let players = three_players(&env);
+    let result = client.try_start_table(&73, &players, &vec![&env, 100i128, 100]);
+    assert_twenty_one_error(&result, Error::InvalidTableSize);
+}
+
+#[test]
+fn test_start_table_rejects_duplicate_seat() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let players = vec![&env, player1.clone(), player2, player1];
+    let result = client.try_start_table(&74, &players, &vec![&env, 100i128, 100, 100]);
+    assert_twenty_one_error(&result, Error::SelfPlay);
+}
+
+#[test]
+fn test_seats_act_in_order() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 75u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // Player2 cannot act before player1 has finished
+    let result = client.try_stick(&session_id, &player2);
+    assert_twenty_one_error(&result, Error::NotYourTurn);
+    let result = client.try_hit(&session_id, &player2);
+    assert_twenty_one_error(&result, Error::NotYourTurn);
+
+    client.stick(&session_id, &player1);
+    assert_eq!(client.get_game(&session_id).turn, 1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.get_game(&session_id).turn, 2);
+}
+
+#[test]
+fn test_table_bust_out_eliminates_seat() {
+    let (env, client, hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+    let seat1 = players.get(0).unwrap();
+    let seat2 = players.get(1).unwrap();
+    let seat3 = players.get(2).unwrap();
+
+    let session_id = 76u32;
+    client.start_table(&session_id, &players, &vec![&env, 100i128, 100, 100]);
+
+    // First seat busts out, the game continues with the other two seats
+    while !client.get_game(&session_id).seats.get(0).unwrap().stuck {
+        client.hit(&session_id, &seat1);
+    }
+    let game = client.get_game(&session_id);
+    assert!(game.winners.is_empty());
+    assert_eq!(game.turn, 1);
+
+    // Second seat busts out, the last seat standing wins without acting
+    while client.get_game(&session_id).winners.is_empty() {
+        client.hit(&session_id, &seat2);
+    }
+    let game = client.get_game(&session_id);
+    assert_eq!(game.winners, vec![&env, seat3.clone()]);
+    assert_eq!(hub.get_winners(&session_id), Some(vec![&env, seat3]));
+}
+
+#[test]
+fn test_table_tie_shares_payout() {
+    let (env, client, hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+
+    // Find a table where exactly two seats share the best score when everyone sticks
+    let mut session_id = 800u32;
+    let tied = loop {
+        client.start_table(&session_id, &players, &vec![&env, 100i128, 100, 100]);
+        for player in players.iter() {
+            client.stick(&session_id, &player);
+        }
+        let scores = score_table_helper(&client.get_game(&session_id));
+        let best = scores[0].max(scores[1]).max(scores[2]);
+        let tied: [bool; 3] = [scores[0] == best, scores[1] == best, scores[2] == best];
+        if tied.iter().filter(|t| **t).count() == 2 {
+            break tied;
+        }
+        session_id += 1;
+    };
+
+    let winners = client.reveal_winner(&session_id);
+    let mut expected = Vec::new(&env);
+    for i in 0..3 {
+        if tied[i as usize] {
+            expected.push_back(players.get(i).unwrap());
+        }
+    }
+    assert_eq!(winners, expected);
+    assert_eq!(hub.get_winners(&session_id), Some(expected));
+}
+
+#[test]
+fn test_table_reveal_matches_reference_scoring() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+
+    let session_id = 77u32;
+    client.start_table(&session_id, &players, &vec![&env, 100i128, 200, 300]);
+    for player in players.iter() {
+        client.stick(&session_id, &player);
+    }
+
+    let scores = score_table_helper(&client.get_game(&session_id));
+    let result = client.try_reveal_winner(&session_id);
+    if scores[0] == scores[1] && scores[1] == scores[2] {
+        assert_twenty_one_error(&result, Error::Draw);
+    } else {
+        let best = scores[0].max(scores[1]).max(scores[2]);
+        let winners = result.unwrap().unwrap();
+        for i in 0..3 {
+            let player = players.get(i).unwrap();
+            assert_eq!(winners.contains(&player), scores[i as usize] == best);
+        }
+    }
+}
+
+// ============================================================================
+// Turn Deadline Tests
+// ============================================================================
+
+/// Move the ledger forward by `ledgers`
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_phase_and_deadline_follow_play() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 90u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let timeout = client.get_timeout_config();
+    assert_eq!(timeout.turn_ledgers, 720);
+    assert_eq!(timeout.policy, TimeoutPolicy::AutoStick);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(game.turn_deadline, 100 + 720);
+    assert_eq!(game.timeout, timeout);
+
+    // Moving to the next seat refreshes the deadline
+    advance_ledgers(&env, 50);
+    client.stick(&session_id, &player1);
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(game.turn_deadline, 150 + 720);
+
+    client.stick(&session_id, &player2);
+    assert_eq!(client.get_game(&session_id).phase, Phase::Reveal);
+
+    // A draw re-deals and puts the first seat back on turn
+    let result = client.try_reveal_winner(&session_id);
+    match result {
+        Ok(_) => assert_eq!(client.get_game(&session_id).phase, Phase::Ended),
+        Err(_) => {
+            assert_twenty_one_error(&result, Error::Draw);
+            let game = client.get_game(&session_id);
+            assert_eq!(game.phase, Phase::Playing);
+            assert_eq!(game.turn, 0);
+        }
+    }
+}
+
+#[test]
+fn test_claim_timeout_before_deadline_fails() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 91u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // The deadline ledger itself is still in time
+    advance_ledgers(&env, 720);
+    let result = client.try_claim_timeout(&session_id, &player2);
+    assert_twenty_one_error(&result, Error::TurnNotExpired);
+}
+
+#[test]
+fn test_claim_timeout_rejects_bad_claimers() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 92u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    advance_ledgers(&env, 721);
+
+    let result = client.try_claim_timeout(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::CannotClaimOwnTimeout);
+
+    let non_player = Address::generate(&env);
+    let result = client.try_claim_timeout(&session_id, &non_player);
+    assert_twenty_one_error(&result, Error::NotPlayer);
+
+    // Once every seat is finished there is no turn left to time out
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    advance_ledgers(&env, 721);
+    let result = client.try_claim_timeout(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::NoActiveTurn);
+}
+
+#[test]
+fn test_claim_timeout_auto_sticks() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 93u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let cards = first_hand(&client.get_game(&session_id), true);
+
+    advance_ledgers(&env, 721);
+    client.claim_timeout(&session_id, &player2);
+
+    // Player1's hand is stuck as it stood, and player2 is now on turn
+    let game = client.get_game(&session_id);
+    let hand = seat(&game, 0).hands.get(0).unwrap();
+    assert!(hand.stuck);
+    assert!(!hand.busted);
+    assert_eq!(hand.cards, cards);
+    assert!(seat(&game, 0).stuck);
+    assert_eq!(game.turn, 1);
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(game.turn_deadline, 821 + 720);
+
+    // Player2 can still play out their turn
+    client.stick(&session_id, &player2);
+    assert_eq!(client.get_game(&session_id).phase, Phase::Reveal);
+}
+
+#[test]
+fn test_claim_timeout_forfeit_ends_two_player_game() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    client.set_timeout_config(&TimeoutConfig {
+        turn_ledgers: 10,
+        policy: TimeoutPolicy::Forfeit,
+    });
+
+    let session_id = 94u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.stick(&session_id, &player1);
+
+    advance_ledgers(&env, 11);
+    client.claim_timeout(&session_id, &player1);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Ended);
+    assert_eq!(game.winners, vec![&env, player1.clone()]);
+    assert!(seat(&game, 1).hands.iter().all(|hand| hand.busted));
+    assert_eq!(hub.get_player1_won(&session_id), Some(true));
+
+    let result = client.try_claim_timeout(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_claim_timeout_forfeit_at_table_moves_on() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let players = three_players(&env);
+
+    client.set_timeout_config(&TimeoutConfig {
+        turn_ledgers: 10,
+        policy: TimeoutPolicy::Forfeit,
+    });
+
+    let session_id = 95u32;
+    client.start_table(&session_id, &players, &vec![&env, 100i128, 100, 100]);
+
+    // Any seated player can claim, not only the next seat
+    advance_ledgers(&env, 11);
+    client.claim_timeout(&session_id, &players.get(2).unwrap());
+
+    let game = client.get_game(&session_id);
+    assert!(game.winners.is_empty());
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(game.turn, 1);
+    assert!(seat(&game, 0).stuck);
+    assert!(seat(&game, 0).hands.iter().all(|hand| hand.busted));
+}
+
+#[test]
+fn test_timeout_config_applies_to_new_games_only() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 96u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let config = TimeoutConfig {
+        turn_ledgers: 5,
+        policy: TimeoutPolicy::Forfeit,
+    };
+    client.set_timeout_config(&config);
+    assert_eq!(client.get_timeout_config(), config);
+
+    // The running game keeps the config it started with
+    advance_ledgers(&env, 6);
+    let result = client.try_claim_timeout(&session_id, &player2);
+    assert_twenty_one_error(&result, Error::TurnNotExpired);
+    assert_eq!(client.get_game(&session_id).timeout.turn_ledgers, 720);
+
+    let result = client.try_set_timeout_config(&TimeoutConfig {
+        turn_ledgers: 0,
+        policy: TimeoutPolicy::AutoStick,
+    });
+    assert_twenty_one_error(&result, Error::InvalidTimeout);
+}
+
+// ============================================================================
+// Randomness Beacon Tests
+// ============================================================================
+
+/// Point the game at a fresh mock beacon
+fn use_beacon(env: &Env, client: &TwentyOneContractClient) -> MockBeaconClient<'static> {
+    let beacon_addr = env.register(MockBeacon, ());
+    client.set_beacon(&Some(beacon_addr.clone()));
+    MockBeaconClient::new(env, &beacon_addr)
+}
+
+#[test]
+fn test_beacon_game_waits_for_deal() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+
+    let session_id = 110u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    assert!(beacon.was_requested(&client.address, &session_id));
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Dealing);
+    assert_eq!(game.beacon, Some(beacon.address.clone()));
+    assert!(seat(&game, 0).hands.is_empty());
+
+    // Nothing can be played before the deal
+    let result = client.try_hit(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::SeedNotReady);
+    let result = client.try_get_hand_value(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::SeedNotReady);
+    let result = client.try_claim_timeout(&session_id, &player2);
+    assert_twenty_one_error(&result, Error::NoActiveTurn);
+    let result = client.try_deal(&session_id);
+    assert_twenty_one_error(&result, Error::SeedNotReady);
+
+    let seed = BytesN::from_array(&env, &[9u8; 32]);
+    beacon.finalize(&client.address, &session_id, &seed);
+    advance_ledgers(&env, 10);
+    client.deal(&session_id);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(game.seed, Some(seed));
+    assert_eq!(game.turn_deadline, 110 + 720);
+    assert_eq!(first_hand(&game, true).len(), 2);
+    assert_eq!(first_hand(&game, false).len(), 2);
+
+    let result = client.try_deal(&session_id);
+    assert_twenty_one_error(&result, Error::AlreadyDealt);
+}
+
+#[test]
+fn test_draws_wait_for_their_own_beacon_round() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let beacon = use_beacon(&env, &client);
+
+    let session_id = 114u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    beacon.finalize(
+        &client.address,
+        &session_id,
+        &BytesN::from_array(&env, &[9u8; 32]),
+    );
+    client.deal(&session_id);
+
+    // The hit only opens a beacon round; the card is unknown until it is finalized
+    let hit_request = 1u32 << 31;
+    client.hit(&session_id, &player1);
+    assert!(beacon.was_requested(&client.address, &hit_request));
+    let game = client.get_game(&session_id);
+    assert_eq!(game.pending_draw, Some(hit_request));
+    assert_eq!(first_hand(&game, true).len(), 2);
+
+    // The seat can't move, and can't be timed out, while its draw is pending
+    let result = client.try_stick(&session_id, &player1);
+    assert_twenty_one_error(&result, Error::DrawPending);
+    advance_ledgers(&env, 721);
+    let result = client.try_claim_timeout(&session_id, &player2);
+    assert_twenty_one_error(&result, Error::DrawPending);
+    let result = client.try_resolve_draw(&session_id);
+    assert_twenty_one_error(&result, Error::SeedNotReady);
+
+    beacon.finalize(
+        &client.address,
+        &hit_request,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    client.resolve_draw(&session_id);
+    let game = client.get_game(&session_id);
+    assert_eq!(game.pending_draw, None);
+    assert_eq!(first_hand(&game, true).len(), 3);
+    assert_eq!(game.turn_deadline, env.ledger().sequence() + 720);
+    let result = client.try_resolve_draw(&session_id);
+    assert_twenty_one_error(&result, Error::NoPendingDraw);
+
+    // A double down locks its points now and draws from the next round
+    if !seat(&game, 0).stuck {
+        client.stick(&session_id, &player1);
+    }
+    client.double_down(&session_id, &player2);
+    assert_eq!(hub.get_added_points(&session_id, &player2), 100_0000000);
+    let hand = seat(&client.get_game(&session_id), 1).hands.get(0).unwrap();
+    assert!(hand.doubled);
+    assert_eq!(hand.cards.len(), 2);
+
+    beacon.finalize(
+        &client.address,
+        &(hit_request + 1),
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+    client.resolve_draw(&session_id);
+    let game = client.get_game(&session_id);
+    let hand = seat(&game, 1).hands.get(0).unwrap();
+    assert_eq!(hand.cards.len(), 3);
+    assert!(hand.stuck || hand.busted);
+}
+
+#[test]
+fn test_cards_drawn_from_beacon_seed() {
+    let seed_byte = 3u8;
+
+    // The beacon seed replaces the player addresses, so two different tables
+    // dealt from the same seed and session get the same opening hands
+    let mut hands = [[0u8; 4]; 2];
+    for table in hands.iter_mut() {
+        let (env, client, _hub, player1, player2) = setup_test();
+        let beacon = use_beacon(&env, &client);
+        client.start_game(&111, &player1, &player2, &100_0000000, &100_0000000);
+        beacon.finalize(
+            &client.address,
+            &111,
+            &BytesN::from_array(&env, &[seed_byte; 32]),
+        );
+        client.deal(&111);
+
+        let game = client.get_game(&111);
+        first_hand(&game, true).copy_into_slice(&mut table[..2]);
+        first_hand(&game, false).copy_into_slice(&mut table[2..]);
+    }
+    assert_eq!(hands[0], hands[1]);
+}
+
+#[test]
+fn test_deal_not_needed_without_beacon() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    assert_eq!(client.get_beacon(), None);
+
+    client.start_game(&113, &player1, &player2, &100_0000000, &100_0000000);
+    let game = client.get_game(&113);
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(game.beacon, None);
+
+    let result = client.try_deal(&113);
+    assert_twenty_one_error(&result, Error::AlreadyDealt);
+}
+
+// ============================================================================
+// Channel Tests
+// ============================================================================
+
+fn channel_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn channel_public_keys(env: &Env, keys: &[SigningKey]) -> Vec<BytesN<32>> {
+    let mut public_keys = Vec::new(env);
+    for key in keys {
+        public_keys.push_back(BytesN::from_array(env, &key.verifying_key().to_bytes()));
+    }
+    public_keys
+}
+
+fn channel_state(
+    env: &Env,
+    session_id: u32,
+    nonce: u64,
+    winners: Option<Vec<Address>>,
+) -> ChannelState {
+    ChannelState {
+        session_id,
+        nonce,
+        state_hash: BytesN::from_array(env, &[nonce as u8; 32]),
+        finished: winners.is_some(),
+        winners: winners.unwrap_or(Vec::new(env)),
+    }
+}
+
+fn co_sign(
+    env: &Env,
+    client: &TwentyOneContractClient,
+    keys: &[SigningKey],
+    state: &ChannelState,
+) -> Vec<BytesN<64>> {
+    let digest = client.channel_state_digest(state).to_array();
+    let mut signatures = Vec::new(env);
+    for key in keys {
+        signatures.push_back(BytesN::from_array(env, &key.sign(&digest).to_bytes()));
+    }
+    signatures
+}
+
+#[test]
+fn test_channel_settles_final_state() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 40u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+    assert_twenty_one_error(&client.try_stick(&session_id, &player1), Error::ChannelOpen);
+    assert_twenty_one_error(
+        &client.try_claim_timeout(&session_id, &player2),
+        Error::ChannelOpen,
+    );
+
+    let state = channel_state(&env, session_id, 12, Some(vec![&env, player2.clone()]));
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    assert_eq!(
+        client.settle_channel(&session_id),
+        vec![&env, player2.clone()]
+    );
+    assert_eq!(hub.get_player1_won(&session_id), Some(false));
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Ended);
+    assert_eq!(game.winners, vec![&env, player2]);
+}
+
+#[test]
+fn test_channel_opened_never_signed() {
+    let (env, client, hub, player1, player2) = setup_test();
+    let session_id = 42u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+    assert_twenty_one_error(
+        &client.try_settle_channel(&session_id),
+        Error::NoChannelState,
+    );
+
+    // Player1 never co-signs anything, so player2 challenges without a state
+    advance_ledgers(&env, 1000);
+    assert_twenty_one_error(
+        &client.try_claim_timeout(&session_id, &player2),
+        Error::ChannelOpen,
+    );
+    assert_twenty_one_error(
+        &client.try_challenge_channel(&session_id, &Address::generate(&env)),
+        Error::NotPlayer,
+    );
+    client.challenge_channel(&session_id, &player2);
+    advance_ledgers(&env, state_channel::CHALLENGE_LEDGERS + 1);
+    assert_eq!(client.settle_channel(&session_id), Vec::new(&env));
+
+    // On-chain play is back, and the seat on turn gets a fresh deadline
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(
+        game.turn_deadline,
+        1100 + state_channel::CHALLENGE_LEDGERS + 1 + 720
+    );
+    assert_twenty_one_error(
+        &client.try_claim_timeout(&session_id, &player2),
+        Error::TurnNotExpired,
+    );
+    advance_ledgers(&env, 721);
+    client.claim_timeout(&session_id, &player2);
+    assert_eq!(client.get_game(&session_id).turn, 1);
+    assert_eq!(hub.get_player1_won(&session_id), None);
+}
+
+#[test]
+fn test_channel_loser_stops_signing() {
+    let (env, client, hub, player1, player2) = setup_test();
+    client.set_timeout_config(&TimeoutConfig {
+        turn_ledgers: 10,
+        policy: TimeoutPolicy::Forfeit,
+    });
+    let session_id = 43u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+
+    // Player1 falls behind and stops signing; player2 submits the last co-signed state
+    let state = channel_state(&env, session_id, 8, None);
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    advance_ledgers(&env, state_channel::CHALLENGE_LEDGERS + 1);
+    assert_eq!(client.settle_channel(&session_id), Vec::new(&env));
+    assert_eq!(hub.get_winners(&session_id), None);
+
+    // Stalling on chain forfeits instead of forcing a draw
+    advance_ledgers(&env, 11);
+    client.claim_timeout(&session_id, &player2);
+    let game = client.get_game(&session_id);
+    assert_eq!(game.phase, Phase::Ended);
+    assert_eq!(game.winners, vec![&env, player2]);
+    assert_eq!(hub.get_player1_won(&session_id), Some(false));
+}
+
+#[test]
+fn test_channel_draw_deals_new_hands() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub = use_standard_hub(&env, &client);
+    let session_id = 44u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let round = client.get_game(&session_id).round;
+    let keys = [channel_key(1), channel_key(2)];
+    client.open_channel(&session_id, &channel_public_keys(&env, &keys));
+
+    // A co-signed draw never reaches the hub; new hands are dealt on chain as in reveal_winner
+    let state = channel_state(&env, session_id, 4, Some(Vec::new(&env)));
+    client.submit_channel_state(&session_id, &state, &co_sign(&env, &client, &keys, &state));
+    assert_eq!(client.settle_channel(&session_id), Vec::new(&env));
+    let game = client.get_game(&session_id);
+    assert_eq!(game.round, round + 1);
+    assert_eq!(game.turn, 0);
+    assert_eq!(game.phase, Phase::Playing);
+    assert_eq!(hub.get_player1_won(&session_id), None);
+    client.stick(&session_id, &player1);
+}
+
 // ============================================================================
 // Admin Function Tests
 // ============================================================================
@@ -748,6 +1953,67 @@ fn test_get_admin() {
     assert_eq!(retrieved_admin, admin);
 }
 
+/// Point the game at a hub with only `start_game`/`end_game` and turn the extensions off
+fn use_standard_hub(env: &Env, client: &TwentyOneContractClient) -> StandardGameHubClient<'static> {
+    let hub_addr = env.register(StandardGameHub, ());
+    client.set_hub(&hub_addr);
+    client.set_hub_extensions(&false);
+    StandardGameHubClient::new(env, &hub_addr)
+}
+
+#[test]
+fn test_hub_extensions_off_by_default() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(MockGameHub, ());
+    let contract_id = env.register(TwentyOneContract, (&admin, &hub_addr));
+    let client = TwentyOneContractClient::new(&env, &contract_id);
+    assert!(!client.get_hub_extensions());
+}
+
+#[test]
+fn test_standard_hub_plays_two_seat_games() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let hub = use_standard_hub(&env, &client);
+
+    let session_id = 1u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let mut result = Err(Ok(Error::Draw));
+    while result == Err(Ok(Error::Draw)) {
+        client.stick(&session_id, &player1);
+        client.stick(&session_id, &player2);
+        result = client.try_reveal_winner(&session_id);
+    }
+    let winners = result.unwrap().unwrap();
+    assert_eq!(
+        hub.get_player1_won(&session_id),
+        Some(winners.get(0).unwrap() == player1)
+    );
+}
+
+#[test]
+fn test_standard_hub_refuses_extensions() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    // Find pairs while the mock hub is still in place, then swap to the standard hub
+    let pair_session = start_game_until(&client, &player1, &player2, 100, true);
+    use_standard_hub(&env, &client);
+
+    let result = client.try_start_table(
+        &1,
+        &three_players(&env),
+        &vec![&env, 100_0000000, 100_0000000, 100_0000000],
+    );
+    assert_twenty_one_error(&result, Error::HubExtensionsRequired);
+
+    let result = client.try_double_down(&pair_session, &player1);
+    assert_twenty_one_error(&result, Error::HubExtensionsRequired);
+    let result = client.try_split(&pair_session, &player1);
+    assert_twenty_one_error(&result, Error::HubExtensionsRequired);
+    assert_eq!(hub.get_added_points(&pair_session, &player1), 0);
+    assert_eq!(seat(&client.get_game(&pair_session), 0).hands.len(), 1);
+}
+
 #[test]
 fn test_get_hub() {
     let env = Env::default();
@@ -837,13 +2103,19 @@ fn test_deterministic_card_dealing() {
 
     // Start second game with same session_id in new environment (should be identical)
     let (_env2, client2, _hub2, player1_2, player2_2) = setup_test();
-    client2.start_game(&session_id, &player1_2, &player2_2, &100_0000000, &100_0000000);
+    client2.start_game(
+        &session_id,
+        &player1_2,
+        &player2_2,
+        &100_0000000,
+        &100_0000000,
+    );
     let game2 = client2.get_game(&session_id);
 
     // Note: Since we generate new addresses each time, the cards will be different
     // But we can verify that within the same session, cards are consistent
-    assert_eq!(game1.player1_hand.len(), 2);
-    assert_eq!(game2.player1_hand.len(), 2);
+    assert_eq!(first_hand(&game1, true).len(), 2);
+    assert_eq!(first_hand(&game2, true).len(), 2);
 }
 
 #[test]
@@ -0,0 +1,374 @@
+#![no_std]
+//! # Mastermind Core
+//!
+//! Rules of the Mastermind variant played by `my-game`, in plain Rust so the contract,
+//! its tests and off-chain tools all score guesses the same way as the Noir circuit.
+//!
+//! **Codes:**
+//! A code is `PEGS` pegs, each a colour in `1..=colors`. The classic rule set uses six
+//! colours, giving 6^4 = 1296 codes.
+//!
+//! **Scoring:**
+//! `exact` counts pegs with the right colour in the right slot. `partial` counts the
+//! remaining colour matches, each secret peg matching at most one guess peg.
+//!
+//! **Public Inputs:**
+//! The circuit takes a guess as `guess_packed`, its pegs read as big-endian base-256
+//! digits. `Code::to_field` gives that value as the 32-byte field the verifier sees.
+//!
+//! **Commitments:**
+//! The codemaker commits to `be31(blake2s(pegs || salt))`: the first 31 bytes of the
+//! BLAKE2s-256 digest of the pegs and a 16-byte salt, read as a field element.
+//! `Code::commitment` computes it so a revealed code can be checked against it.
+//!
+//! **Hints:**
+//! Besides feedback, the codemaker can be asked to prove a `Hint` about the code. Each
+//! hint kind has its own statement type, so its proofs can't pass for another statement.
+
+use core::fmt;
+
+mod blake2s;
+
+pub use blake2s::blake2s_256;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Pegs in every code
+pub const PEGS: usize = 4;
+
+/// Largest colour count a rule set may use
+pub const MAX_COLORS: u8 = 9;
+
+/// Bytes of salt in a code commitment
+pub const SALT_LEN: usize = 16;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    InvalidRules,
+    InvalidColor,
+    InvalidFeedback,
+    InvalidHint,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidRules => write!(f, "colour count must be between 2 and {MAX_COLORS}"),
+            Error::InvalidColor => write!(f, "peg colour outside the rule set"),
+            Error::InvalidFeedback => write!(f, "feedback no guess can produce"),
+            Error::InvalidHint => write!(f, "hint outside the rule set"),
+        }
+    }
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+/// Rule set a code is drawn from.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    colors: u8,
+}
+
+impl Rules {
+    /// The classic game: six colours.
+    pub const CLASSIC: Rules = Rules { colors: 6 };
+
+    /// Create a rule set with `colors` colours.
+    ///
+    /// # Arguments
+    /// * `colors` - Number of colours, between 2 and `MAX_COLORS`
+    pub fn new(colors: u8) -> Result<Rules, Error> {
+        if !(2..=MAX_COLORS).contains(&colors) {
+            return Err(Error::InvalidRules);
+        }
+        Ok(Rules { colors })
+    }
+
+    pub fn colors(&self) -> u8 {
+        self.colors
+    }
+
+    /// Number of codes in the rule set's space.
+    pub fn code_count(&self) -> u32 {
+        (self.colors as u32).pow(PEGS as u32)
+    }
+
+    pub fn is_valid_color(&self, color: u8) -> bool {
+        (1..=self.colors).contains(&color)
+    }
+
+    /// All codes of the rule set, in ascending order.
+    pub fn codes(&self) -> Codes {
+        Codes {
+            rules: *self,
+            next: 0,
+        }
+    }
+
+    /// Codes that would have produced every feedback in `history`.
+    pub fn consistent_codes<'a>(&self, history: &'a [Turn]) -> impl Iterator<Item = Code> + 'a {
+        self.codes().filter(move |code| code.is_consistent(history))
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::CLASSIC
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Code([u8; PEGS]);
+
+impl Code {
+    /// Create a code, checking every peg against the rule set.
+    pub fn new(rules: &Rules, pegs: [u8; PEGS]) -> Result<Code, Error> {
+        if pegs.iter().any(|peg| !rules.is_valid_color(*peg)) {
+            return Err(Error::InvalidColor);
+        }
+        Ok(Code(pegs))
+    }
+
+    /// The `index`-th code of the rule set in ascending order.
+    pub fn from_index(rules: &Rules, index: u32) -> Option<Code> {
+        if index >= rules.code_count() {
+            return None;
+        }
+        let base = rules.colors as u32;
+        let mut rest = index;
+        let mut pegs = [0u8; PEGS];
+        for peg in pegs.iter_mut().rev() {
+            *peg = (rest % base) as u8 + 1;
+            rest /= base;
+        }
+        Some(Code(pegs))
+    }
+
+    /// Unpack a `guess_packed` value.
+    pub fn from_packed(rules: &Rules, packed: u32) -> Result<Code, Error> {
+        Code::new(rules, packed.to_be_bytes())
+    }
+
+    pub fn pegs(&self) -> [u8; PEGS] {
+        self.0
+    }
+
+    /// Position of the code in `Rules::codes`.
+    pub fn index(&self, rules: &Rules) -> u32 {
+        let base = rules.colors as u32;
+        self.0
+            .iter()
+            .fold(0, |index, peg| index * base + (*peg - 1) as u32)
+    }
+
+    /// The circuit's `guess_packed` value.
+    pub fn pack(&self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+
+    /// `guess_packed` as the 32-byte big-endian field in the proof's public inputs.
+    pub fn to_field(&self) -> [u8; 32] {
+        let mut field = [0u8; 32];
+        field[32 - PEGS..].copy_from_slice(&self.0);
+        field
+    }
+
+    /// The circuit's commitment to this code, as the 32-byte field in the public inputs.
+    pub fn commitment(&self, salt: &[u8; SALT_LEN]) -> [u8; 32] {
+        let mut preimage = [0u8; PEGS + SALT_LEN];
+        preimage[..PEGS].copy_from_slice(&self.0);
+        preimage[PEGS..].copy_from_slice(salt);
+        let digest = blake2s_256(&preimage);
+
+        let mut field = [0u8; 32];
+        field[1..].copy_from_slice(&digest[..31]);
+        field
+    }
+
+    /// Score `guess` against this code as the secret.
+    pub fn score(&self, guess: &Code) -> Feedback {
+        // Same approach as the circuit: total colour matches, less the exact ones.
+        let mut secret_counts = [0u8; MAX_COLORS as usize + 1];
+        let mut guess_counts = [0u8; MAX_COLORS as usize + 1];
+        let mut exact = 0;
+        for (secret_peg, guess_peg) in self.0.iter().zip(guess.0.iter()) {
+            if secret_peg == guess_peg {
+                exact += 1;
+            }
+            secret_counts[*secret_peg as usize] += 1;
+            guess_counts[*guess_peg as usize] += 1;
+        }
+
+        let total: u8 = secret_counts
+            .iter()
+            .zip(guess_counts.iter())
+            .map(|(secret_count, guess_count)| *secret_count.min(guess_count))
+            .sum();
+
+        Feedback {
+            exact,
+            partial: total - exact,
+        }
+    }
+
+    /// Whether this code, as the secret, would have produced every feedback in `history`.
+    pub fn is_consistent(&self, history: &[Turn]) -> bool {
+        history
+            .iter()
+            .all(|turn| self.score(&turn.guess) == turn.feedback)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Feedback {
+    exact: u8,
+    partial: u8,
+}
+
+impl Feedback {
+    /// Feedback for a solved code.
+    pub const SOLVED: Feedback = Feedback {
+        exact: PEGS as u8,
+        partial: 0,
+    };
+
+    /// Create feedback, rejecting pairs no guess can score.
+    ///
+    /// # Arguments
+    /// * `exact` - Right colour, right slot
+    /// * `partial` - Right colour, wrong slot
+    pub fn new(exact: u32, partial: u32) -> Result<Feedback, Error> {
+        let pegs = PEGS as u32;
+        if exact > pegs || partial > pegs || exact + partial > pegs {
+            return Err(Error::InvalidFeedback);
+        }
+        // Three pegs in place leave one slot, which cannot be a misplaced match
+        if exact == pegs - 1 && partial == 1 {
+            return Err(Error::InvalidFeedback);
+        }
+        Ok(Feedback {
+            exact: exact as u8,
+            partial: partial as u8,
+        })
+    }
+
+    pub fn exact(&self) -> u32 {
+        self.exact as u32
+    }
+
+    pub fn partial(&self) -> u32 {
+        self.partial as u32
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == Feedback::SOLVED
+    }
+}
+
+/// Question about the secret code that the codemaker answers with a proof.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Hint {
+    /// Colour appearing most often, the lowest one on a tie
+    MostFrequentColor,
+    /// 1 if the peg at `position` has `color`, otherwise 0
+    PositionIs { position: u8, color: u8 },
+    /// How many pegs have `color`
+    ColorCount { color: u8 },
+}
+
+impl Hint {
+    /// Statement type of the hint's proofs. Feedback proofs are statement type 0.
+    pub fn statement_type(&self) -> u32 {
+        match self {
+            Hint::MostFrequentColor => 1,
+            Hint::PositionIs { .. } => 2,
+            Hint::ColorCount { .. } => 3,
+        }
+    }
+
+    /// Check the hint's position and colour against the rule set.
+    pub fn validate(&self, rules: &Rules) -> Result<(), Error> {
+        let valid = match self {
+            Hint::MostFrequentColor => true,
+            Hint::PositionIs { position, color } => {
+                (*position as usize) < PEGS && rules.is_valid_color(*color)
+            }
+            Hint::ColorCount { color } => rules.is_valid_color(*color),
+        };
+        if !valid {
+            return Err(Error::InvalidHint);
+        }
+        Ok(())
+    }
+
+    /// The hint's true answer for `secret`.
+    pub fn answer(&self, secret: &Code) -> u32 {
+        let count = |color: u8| secret.0.iter().filter(|peg| **peg == color).count() as u32;
+        match self {
+            Hint::MostFrequentColor => {
+                let mut best = secret.0[0];
+                for color in secret.0 {
+                    if count(color) > count(best) || (count(color) == count(best) && color < best) {
+                        best = color;
+                    }
+                }
+                best as u32
+            }
+            Hint::PositionIs { position, color } => (secret.0[*position as usize] == *color) as u32,
+            Hint::ColorCount { color } => count(*color),
+        }
+    }
+
+    /// Whether some code of the rule set could give `answer`.
+    pub fn is_possible_answer(&self, rules: &Rules, answer: u32) -> bool {
+        match self {
+            Hint::MostFrequentColor => {
+                answer <= u8::MAX as u32 && rules.is_valid_color(answer as u8)
+            }
+            Hint::PositionIs { .. } => answer <= 1,
+            Hint::ColorCount { .. } => answer <= PEGS as u32,
+        }
+    }
+}
+
+/// A guess and the feedback it got.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Turn {
+    pub guess: Code,
+    pub feedback: Feedback,
+}
+
+/// Iterator over every code of a rule set, returned by `Rules::codes`.
+#[derive(Clone, Debug)]
+pub struct Codes {
+    rules: Rules,
+    next: u32,
+}
+
+impl Iterator for Codes {
+    type Item = Code;
+
+    fn next(&mut self) -> Option<Code> {
+        let code = Code::from_index(&self.rules, self.next)?;
+        self.next += 1;
+        Some(code)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = (self.rules.code_count() - self.next) as usize;
+        (left, Some(left))
+    }
+}
+
+impl ExactSizeIterator for Codes {}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,305 @@
+#![cfg(test)]
+
+use crate::{blake2s_256, Code, Error, Feedback, Hint, Rules, Turn, PEGS};
+
+fn code(pegs: [u8; PEGS]) -> Code {
+    Code::new(&Rules::CLASSIC, pegs).unwrap()
+}
+
+fn feedback(exact: u32, partial: u32) -> Feedback {
+    Feedback::new(exact, partial).unwrap()
+}
+
+fn hex32(hex: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+    }
+    out
+}
+
+// ============================================================================
+// Rules and Code Tests
+// ============================================================================
+
+#[test]
+fn test_rules_bounds() {
+    assert_eq!(Rules::new(1), Err(Error::InvalidRules));
+    assert_eq!(Rules::new(10), Err(Error::InvalidRules));
+    assert_eq!(Rules::new(8).unwrap().code_count(), 4096);
+    assert_eq!(Rules::default(), Rules::CLASSIC);
+    assert_eq!(Rules::CLASSIC.code_count(), 1296);
+}
+
+#[test]
+fn test_code_rejects_colors_outside_rules() {
+    assert_eq!(
+        Code::new(&Rules::CLASSIC, [0, 1, 2, 3]),
+        Err(Error::InvalidColor)
+    );
+    assert_eq!(
+        Code::new(&Rules::CLASSIC, [1, 2, 3, 7]),
+        Err(Error::InvalidColor)
+    );
+
+    let rules = Rules::new(8).unwrap();
+    assert!(Code::new(&rules, [8, 7, 1, 2]).is_ok());
+}
+
+#[test]
+fn test_pack_matches_circuit_layout() {
+    let guess = code([1, 2, 3, 4]);
+    assert_eq!(guess.pack(), 0x01020304);
+    assert_eq!(Code::from_packed(&Rules::CLASSIC, 0x01020304), Ok(guess));
+    assert_eq!(
+        Code::from_packed(&Rules::CLASSIC, 0x00020304),
+        Err(Error::InvalidColor)
+    );
+
+    let field = guess.to_field();
+    assert!(field[..28].iter().all(|byte| *byte == 0));
+    assert_eq!(field[28..], [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_codes_enumerate_whole_space_in_order() {
+    let rules = Rules::CLASSIC;
+    let codes = rules.codes();
+    assert_eq!(codes.len(), 1296);
+
+    let mut previous = None;
+    for (index, code) in codes.enumerate() {
+        assert_eq!(code.index(&rules), index as u32);
+        assert!(previous < Some(code));
+        previous = Some(code);
+    }
+    assert_eq!(Code::from_index(&rules, 0), Some(code([1, 1, 1, 1])));
+    assert_eq!(Code::from_index(&rules, 1295), Some(code([6, 6, 6, 6])));
+    assert_eq!(Code::from_index(&rules, 1296), None);
+}
+
+// ============================================================================
+// Scoring Tests
+// ============================================================================
+
+#[test]
+fn test_score_examples() {
+    let secret = code([1, 1, 2, 2]);
+    assert_eq!(secret.score(&code([1, 1, 2, 2])), Feedback::SOLVED);
+    assert_eq!(secret.score(&code([2, 2, 1, 1])), feedback(0, 4));
+    assert_eq!(secret.score(&code([1, 2, 3, 4])), feedback(1, 1));
+    assert_eq!(secret.score(&code([3, 4, 5, 6])), feedback(0, 0));
+
+    // A secret peg matches at most one guess peg.
+    assert_eq!(
+        code([1, 2, 3, 4]).score(&code([4, 4, 4, 4])),
+        feedback(1, 0)
+    );
+    assert_eq!(
+        code([1, 2, 3, 4]).score(&code([2, 2, 1, 1])),
+        feedback(1, 1)
+    );
+    assert_eq!(
+        code([6, 5, 5, 1]).score(&code([5, 1, 6, 5])),
+        feedback(0, 4)
+    );
+}
+
+#[test]
+fn test_score_is_symmetric() {
+    let rules = Rules::new(4).unwrap();
+    for secret in rules.codes() {
+        for guess in rules.codes() {
+            assert_eq!(secret.score(&guess), guess.score(&secret));
+        }
+    }
+}
+
+#[test]
+fn test_feedback_validation() {
+    assert_eq!(Feedback::new(3, 1), Err(Error::InvalidFeedback));
+    assert_eq!(Feedback::new(2, 3), Err(Error::InvalidFeedback));
+    assert_eq!(Feedback::new(5, 0), Err(Error::InvalidFeedback));
+    assert_eq!(Feedback::new(u32::MAX, 1), Err(Error::InvalidFeedback));
+    assert!(Feedback::new(4, 0).unwrap().is_solved());
+    assert!(!Feedback::new(0, 4).unwrap().is_solved());
+
+    // Every score the space produces is valid feedback.
+    let secret = code([1, 2, 3, 3]);
+    for guess in Rules::CLASSIC.codes() {
+        let score = secret.score(&guess);
+        assert_eq!(Feedback::new(score.exact(), score.partial()), Ok(score));
+    }
+}
+
+// ============================================================================
+// Consistency Tests
+// ============================================================================
+
+#[test]
+fn test_consistent_codes_narrow_to_secret() {
+    let rules = Rules::CLASSIC;
+    let secret = code([3, 6, 2, 6]);
+    assert_eq!(rules.consistent_codes(&[]).count(), 1296);
+
+    let guesses = [
+        code([1, 1, 2, 2]),
+        code([3, 3, 4, 4]),
+        code([5, 5, 6, 6]),
+        code([3, 6, 1, 5]),
+        code([2, 6, 3, 6]),
+    ];
+    let history = guesses.map(|guess| Turn {
+        guess,
+        feedback: secret.score(&guess),
+    });
+
+    let mut last = rules.code_count() as usize;
+    for turns in 1..=history.len() {
+        let left = rules.consistent_codes(&history[..turns]).count();
+        assert!(left <= last);
+        last = left;
+    }
+    assert!(secret.is_consistent(&history));
+    assert!(rules.consistent_codes(&history).eq([secret]));
+}
+
+#[test]
+fn test_contradictory_history_has_no_codes() {
+    let guess = code([1, 2, 3, 4]);
+    let history = [
+        Turn {
+            guess,
+            feedback: feedback(0, 0),
+        },
+        Turn {
+            guess,
+            feedback: feedback(1, 0),
+        },
+    ];
+    assert_eq!(Rules::CLASSIC.consistent_codes(&history).count(), 0);
+}
+
+// ============================================================================
+// Commitment Tests
+// ============================================================================
+
+#[test]
+fn test_blake2s_vectors() {
+    assert_eq!(
+        blake2s_256(b""),
+        hex32("69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9")
+    );
+    assert_eq!(
+        blake2s_256(b"abc"),
+        hex32("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")
+    );
+
+    // Exactly one block, and several blocks with a partial last one
+    let mut data = [0u8; 200];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    assert_eq!(
+        blake2s_256(&data[..64]),
+        hex32("56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e")
+    );
+    assert_eq!(
+        blake2s_256(&data),
+        hex32("6d244e1a06ce4ef578dd0f63aff0936706735119ca9c8d22d86c801414ab9741")
+    );
+}
+
+#[test]
+fn test_commitment_matches_circuit_packing() {
+    let mut salt = [0u8; 16];
+    for (i, byte) in salt.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    assert_eq!(
+        code([1, 2, 3, 4]).commitment(&salt),
+        hex32("003824d55e8deb70246a316bcc62f0c59f6c7888b75bec6d2a15c0f3e942d004")
+    );
+    assert_ne!(
+        code([1, 2, 3, 5]).commitment(&salt),
+        code([1, 2, 3, 4]).commitment(&salt)
+    );
+}
+
+// ============================================================================
+// Hint Tests
+// ============================================================================
+
+#[test]
+fn test_hint_answers() {
+    let secret = code([3, 5, 3, 1]);
+    assert_eq!(Hint::MostFrequentColor.answer(&secret), 3);
+    assert_eq!(Hint::MostFrequentColor.answer(&code([6, 2, 4, 2])), 2);
+    // Ties go to the lowest colour
+    assert_eq!(Hint::MostFrequentColor.answer(&code([6, 4, 4, 6])), 4);
+    assert_eq!(Hint::MostFrequentColor.answer(&code([5, 4, 3, 2])), 2);
+
+    assert_eq!(
+        Hint::PositionIs {
+            position: 1,
+            color: 5
+        }
+        .answer(&secret),
+        1
+    );
+    assert_eq!(
+        Hint::PositionIs {
+            position: 3,
+            color: 5
+        }
+        .answer(&secret),
+        0
+    );
+    assert_eq!(Hint::ColorCount { color: 3 }.answer(&secret), 2);
+    assert_eq!(Hint::ColorCount { color: 6 }.answer(&secret), 0);
+}
+
+#[test]
+fn test_hint_validation() {
+    let rules = Rules::CLASSIC;
+    assert!(Hint::MostFrequentColor.validate(&rules).is_ok());
+    assert!(Hint::PositionIs {
+        position: 3,
+        color: 6
+    }
+    .validate(&rules)
+    .is_ok());
+    assert_eq!(
+        Hint::PositionIs {
+            position: 4,
+            color: 1
+        }
+        .validate(&rules),
+        Err(Error::InvalidHint)
+    );
+    assert_eq!(
+        Hint::ColorCount { color: 7 }.validate(&rules),
+        Err(Error::InvalidHint)
+    );
+
+    assert!(Hint::MostFrequentColor.is_possible_answer(&rules, 6));
+    assert!(!Hint::MostFrequentColor.is_possible_answer(&rules, 0));
+    assert!(!Hint::PositionIs {
+        position: 0,
+        color: 1
+    }
+    .is_possible_answer(&rules, 2));
+    assert!(Hint::ColorCount { color: 1 }.is_possible_answer(&rules, 4));
+    assert!(!Hint::ColorCount { color: 1 }.is_possible_answer(&rules, 5));
+
+    let statement_types = [
+        Hint::MostFrequentColor.statement_type(),
+        Hint::PositionIs {
+            position: 0,
+            color: 1,
+        }
+        .statement_type(),
+        Hint::ColorCount { color: 1 }.statement_type(),
+    ];
+    assert_eq!(statement_types, [1, 2, 3]);
+}
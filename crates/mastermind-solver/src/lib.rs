@@ -0,0 +1,193 @@
+//! # Mastermind Solver
+//!
+//! Codebreaker strategies for the `my-game` rules, used by practice-mode bots and to check
+//! that an attempt limit is fair for a rule set.
+//!
+//! **Strategies:**
+//! - `Strategy::Knuth` - Knuth's minimax: pick the guess whose worst feedback leaves the
+//!   fewest candidate codes.
+//! - `Strategy::Entropy` - Pick the guess whose feedback is expected to tell the most about
+//!   the secret (highest Shannon entropy over the candidate partition).
+//!
+//! Both search the whole code space, not only the codes still consistent with the history,
+//! and break ties in favour of a consistent code, then the lowest one. They are therefore
+//! deterministic: the same history always gets the same guess.
+
+use mastermind_core::{Code, Feedback, Rules, Turn, PEGS};
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    Knuth,
+    Entropy,
+}
+
+/// Outcome of playing a solver against every code of a rule set.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    pub games: u32,
+    pub solved: u32,         // Games solved within the attempt limit
+    pub worst_attempts: u32, // Most attempts any solved game took
+    pub total_attempts: u32, // Attempts over every solved game
+}
+
+impl Report {
+    /// Average attempts over the solved games.
+    pub fn average_attempts(&self) -> f64 {
+        if self.solved == 0 {
+            return 0.0;
+        }
+        self.total_attempts as f64 / self.solved as f64
+    }
+
+    /// Record one game, `None` if it was not solved.
+    pub fn record(&mut self, attempts: Option<u32>) {
+        self.games += 1;
+        if let Some(attempts) = attempts {
+            self.solved += 1;
+            self.worst_attempts = self.worst_attempts.max(attempts);
+            self.total_attempts += attempts;
+        }
+    }
+}
+
+// Feedback pairs (exact, partial) with exact + partial <= PEGS, indexed as exact * (PEGS + 1) + partial
+const FEEDBACK_SLOTS: usize = (PEGS + 1) * (PEGS + 1);
+
+// ============================================================================
+// Solver
+// ============================================================================
+
+#[derive(Clone, Debug)]
+pub struct Solver {
+    rules: Rules,
+    strategy: Strategy,
+    opening: Code,
+}
+
+impl Solver {
+    /// Create a solver. The opening guess is worked out once here.
+    pub fn new(rules: Rules, strategy: Strategy) -> Solver {
+        let codes: Vec<Code> = rules.codes().collect();
+        let opening = best_guess(&codes, &codes, strategy);
+        Solver {
+            rules,
+            strategy,
+            opening,
+        }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Next guess for a game with the given history.
+    ///
+    /// # Returns
+    /// The guess, or `None` if no code is consistent with the history
+    pub fn next_guess(&self, history: &[Turn]) -> Option<Code> {
+        if history.is_empty() {
+            return Some(self.opening);
+        }
+
+        let candidates: Vec<Code> = self.rules.consistent_codes(history).collect();
+        match candidates.len() {
+            0 => None,
+            1 | 2 => Some(candidates[0]),
+            _ => {
+                let codes: Vec<Code> = self.rules.codes().collect();
+                Some(best_guess(&codes, &candidates, self.strategy))
+            }
+        }
+    }
+
+    /// Play against `secret` off-chain.
+    ///
+    /// # Returns
+    /// Attempts used to solve it, or `None` if it was not solved within `max_attempts`
+    pub fn solve(&self, secret: &Code, max_attempts: u32) -> Option<u32> {
+        let mut history = Vec::new();
+        while (history.len() as u32) < max_attempts {
+            let guess = self.next_guess(&history)?;
+            let feedback = secret.score(&guess);
+            history.push(Turn { guess, feedback });
+            if feedback.is_solved() {
+                return Some(history.len() as u32);
+            }
+        }
+        None
+    }
+
+    /// Play against every code of the rule set off-chain.
+    pub fn evaluate(&self, max_attempts: u32) -> Report {
+        let mut report = Report::default();
+        for secret in self.rules.codes() {
+            report.record(self.solve(&secret, max_attempts));
+        }
+        report
+    }
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn feedback_slot(feedback: &Feedback) -> usize {
+    feedback.exact() as usize * (PEGS + 1) + feedback.partial() as usize
+}
+
+/// How many candidates would get each feedback if `guess` were played.
+fn partition(guess: &Code, candidates: &[Code]) -> [u32; FEEDBACK_SLOTS] {
+    let mut counts = [0u32; FEEDBACK_SLOTS];
+    for candidate in candidates {
+        counts[feedback_slot(&candidate.score(guess))] += 1;
+    }
+    counts
+}
+
+fn best_guess(codes: &[Code], candidates: &[Code], strategy: Strategy) -> Code {
+    let mut best: Option<(f64, bool, Code)> = None;
+    for guess in codes {
+        let counts = partition(guess, candidates);
+        // Higher is better for both strategies
+        let value = match strategy {
+            Strategy::Knuth => -(counts.iter().copied().max().unwrap_or(0) as f64),
+            Strategy::Entropy => entropy(&counts, candidates.len()),
+        };
+        let consistent = candidates.binary_search(guess).is_ok();
+
+        let better = match best {
+            None => true,
+            Some((best_value, best_consistent, _)) => {
+                value > best_value || (value == best_value && consistent && !best_consistent)
+            }
+        };
+        if better {
+            best = Some((value, consistent, *guess));
+        }
+    }
+    best.map(|(_, _, guess)| guess)
+        .expect("code space is never empty")
+}
+
+fn entropy(counts: &[u32], total: usize) -> f64 {
+    let total = total as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,110 @@
+#![cfg(test)]
+
+use crate::{Report, Solver, Strategy};
+use mastermind_core::{Code, Feedback, Rules, Turn};
+
+fn code(pegs: [u8; 4]) -> Code {
+    Code::new(&Rules::CLASSIC, pegs).unwrap()
+}
+
+#[test]
+fn test_knuth_opens_with_1122() {
+    let solver = Solver::new(Rules::CLASSIC, Strategy::Knuth);
+    assert_eq!(solver.next_guess(&[]), Some(code([1, 1, 2, 2])));
+}
+
+#[test]
+fn test_next_guess_is_deterministic() {
+    let secret = code([4, 2, 6, 1]);
+    for strategy in [Strategy::Knuth, Strategy::Entropy] {
+        let solver = Solver::new(Rules::CLASSIC, strategy);
+        let opening = solver.next_guess(&[]).unwrap();
+        let history = [Turn {
+            guess: opening,
+            feedback: secret.score(&opening),
+        }];
+        assert_eq!(solver.next_guess(&history), solver.next_guess(&history));
+    }
+}
+
+#[test]
+fn test_solves_sample_secrets_within_knuth_bound() {
+    let secrets = [
+        code([1, 1, 1, 1]),
+        code([6, 6, 6, 6]),
+        code([3, 6, 2, 6]),
+        code([5, 4, 3, 2]),
+    ];
+    for strategy in [Strategy::Knuth, Strategy::Entropy] {
+        let solver = Solver::new(Rules::CLASSIC, strategy);
+        for secret in secrets {
+            let attempts = solver.solve(&secret, 12).unwrap();
+            assert!(attempts <= 6, "{strategy:?} took {attempts} attempts");
+        }
+    }
+}
+
+#[test]
+fn test_no_guess_for_contradictory_history() {
+    let solver = Solver::new(Rules::CLASSIC, Strategy::Knuth);
+    let guess = code([1, 2, 3, 4]);
+    let history = [
+        Turn {
+            guess,
+            feedback: Feedback::new(0, 0).unwrap(),
+        },
+        Turn {
+            guess,
+            feedback: Feedback::new(0, 1).unwrap(),
+        },
+    ];
+    assert_eq!(solver.next_guess(&history), None);
+    assert_eq!(solver.solve(&code([5, 5, 6, 6]), 0), None);
+}
+
+#[test]
+fn test_evaluate_small_rule_set() {
+    let rules = Rules::new(4).unwrap();
+    for strategy in [Strategy::Knuth, Strategy::Entropy] {
+        let report = Solver::new(rules, strategy).evaluate(12);
+        assert_eq!(report.games, 256);
+        assert_eq!(report.solved, 256);
+        assert!(report.worst_attempts <= 5);
+    }
+}
+
+#[test]
+fn test_report_average() {
+    let mut report = Report::default();
+    assert_eq!(report.average_attempts(), 0.0);
+    report.record(Some(4));
+    report.record(Some(5));
+    report.record(None);
+    assert_eq!(report.games, 3);
+    assert_eq!(report.solved, 2);
+    assert_eq!(report.worst_attempts, 5);
+    assert_eq!(report.average_attempts(), 4.5);
+}
+
+// Plays all 1296 codes per strategy; run with `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn test_classic_rules_fit_attempt_limit() {
+    let knuth = Solver::new(Rules::CLASSIC, Strategy::Knuth).evaluate(12);
+    std::println!(
+        "knuth: worst {} average {:.4}",
+        knuth.worst_attempts,
+        knuth.average_attempts()
+    );
+    assert_eq!(knuth.solved, 1296);
+    assert_eq!(knuth.worst_attempts, 5);
+
+    let entropy = Solver::new(Rules::CLASSIC, Strategy::Entropy).evaluate(12);
+    std::println!(
+        "entropy: worst {} average {:.4}",
+        entropy.worst_attempts,
+        entropy.average_attempts()
+    );
+    assert_eq!(entropy.solved, 1296);
+    assert!(entropy.worst_attempts <= 6);
+}
@@ -0,0 +1,377 @@
+#![no_std]
+//! # State Channel
+//!
+//! Off-chain play for the workspace's table games. Once a game is started on chain, its
+//! players can open a channel and exchange signed state updates instead of sending every
+//! move to the contract. Only the outcome goes on chain.
+//!
+//! **States:**
+//! A `ChannelState` carries a `nonce` that grows with every update, the hash of the game's
+//! off-chain state (opaque to the contract), and once the game is over, `finished` and the
+//! winners. Every seat signs `state_digest` of each update with the ed25519 key it
+//! registered when the channel was opened, so any seat holds the latest co-signed state.
+//!
+//! **Disputes:**
+//! Any seat can submit the latest co-signed state it holds. The first submission opens a
+//! challenge window of `CHALLENGE_LEDGERS`, during which a state with a higher nonce
+//! replaces the submitted one. A finished state can be settled as soon as it is submitted;
+//! anything else settles once the window has closed. A seat whose table stopped signing
+//! before any state was co-signed opens the window with `challenge` instead.
+//!
+//! **Settlement:**
+//! A finished state settles with its winners, or as a draw between every seat if it has
+//! none, and the game contract ends the Game Hub session the same way its on-chain play
+//! does. A window that closes on no state or an unfinished one settles as `Resume`: the
+//! channel closes and the game goes back to on-chain play where it stood when the channel
+//! was opened, so a seat that stopped signing has to play on under the game's own timeouts
+//! rather than walking away with a draw.
+//!
+//! **Storage:**
+//! Channels live in the game contract's temporary storage under the session ID, so a
+//! contract only maps seats in and results out.
+//!
+//! **Games:**
+//! A contract implements `ChannelGame` for its game and calls `open_game`, `submit_game`,
+//! `challenge_game` and `settle_game`, which load the running game, map its seats and its
+//! tie policy, and convert errors with `impl_from_channel_error!`. What a settlement does
+//! to the game and the Game Hub session stays with the contract.
+
+use soroban_sdk::{contracttype, vec, xdr::ToXdr, Address, BytesN, Env, IntoVal, Vec};
+
+/// Window for a higher-nonce state after the first submission (1 hour, ~5 seconds per ledger)
+pub const CHALLENGE_LEDGERS: u32 = 720;
+
+/// TTL for channel storage, the same 30 days the games keep their sessions for
+pub const CHANNEL_TTL_LEDGERS: u32 = 518_400;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChannelError {
+    InvalidKeys,       // Not exactly one key per seat
+    WrongSession,      // The state is for another session
+    StaleState,        // The nonce is not above the latest submitted state's
+    InvalidWinners,    // Winners are not distinct seats, or set on an unfinished state
+    InvalidSignatures, // Not exactly one signature per seat
+    ChallengeClosed,   // The challenge window has passed
+    ChallengeOpen,     // The challenge window is still running
+    NoState,           // Nothing has been submitted and no challenge opened yet
+    AlreadySettled,
+    NotFound, // The session has no channel
+    Exists,   // The session already has a channel
+    NotSeat,  // The challenger is not seated at the table
+}
+
+/// How a settled channel ends
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Settlement {
+    Winners(Vec<Address>), // The finished state's winners, every seat for a draw
+    Resume,                // The game goes back to on-chain play
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum ChannelKey {
+    Channel(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChannelState {
+    pub session_id: u32,
+    pub nonce: u64,
+    pub state_hash: BytesN<32>, // Hash of the game's off-chain state
+    pub finished: bool,         // Set on the state that ends the game
+    pub winners: Vec<Address>,  // Empty for a draw, and while the game is running
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Channel {
+    pub keys: Vec<BytesN<32>>, // Ed25519 channel key of each seat, in seat order
+    pub state: ChannelState,   // Latest submitted state, nonce 0 before the first
+    pub challenge_deadline: u32, // Last ledger a higher nonce is accepted, 0 before the first
+    pub settled: bool,
+}
+
+/// Open a channel for a session.
+/// Every seat authorizes `(session_id, key)` for the key it signs states with.
+pub fn open(
+    env: &Env,
+    session_id: u32,
+    players: &Vec<Address>,
+    keys: Vec<BytesN<32>>,
+) -> Result<(), ChannelError> {
+    if env
+        .storage()
+        .temporary()
+        .has(&ChannelKey::Channel(session_id))
+    {
+        return Err(ChannelError::Exists);
+    }
+    if keys.len() != players.len() {
+        return Err(ChannelError::InvalidKeys);
+    }
+    for (player, key) in players.iter().zip(keys.iter()) {
+        player.require_auth_for_args(vec![env, session_id.into_val(env), key.into_val(env)]);
+    }
+
+    let channel = Channel {
+        keys,
+        state: ChannelState {
+            session_id,
+            nonce: 0,
+            state_hash: BytesN::from_array(env, &[0; 32]),
+            finished: false,
+            winners: Vec::new(env),
+        },
+        challenge_deadline: 0,
+        settled: false,
+    };
+    save(env, session_id, &channel);
+    Ok(())
+}
+
+/// Get the channel of a session.
+pub fn get(env: &Env, session_id: u32) -> Result<Channel, ChannelError> {
+    env.storage()
+        .temporary()
+        .get(&ChannelKey::Channel(session_id))
+        .ok_or(ChannelError::NotFound)
+}
+
+/// Whether the session is played through a channel that has not been settled.
+pub fn is_open(env: &Env, session_id: u32) -> bool {
+    get(env, session_id).is_ok_and(|channel| !channel.settled)
+}
+
+/// The message every seat signs: sha256(contract address xdr || state xdr).
+/// Binding the contract keeps a state for one game from being replayed on another.
+pub fn state_digest(env: &Env, state: &ChannelState) -> BytesN<32> {
+    let mut preimage = env.current_contract_address().to_xdr(env);
+    preimage.append(&state.clone().to_xdr(env));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Submit a co-signed state, opening the challenge window on the first submission.
+/// Signatures are in seat order; an invalid signature fails the host's ed25519 check.
+pub fn submit(
+    env: &Env,
+    session_id: u32,
+    players: &Vec<Address>,
+    state: ChannelState,
+    signatures: Vec<BytesN<64>>,
+) -> Result<(), ChannelError> {
+    let mut channel = get(env, session_id)?;
+    if channel.settled {
+        return Err(ChannelError::AlreadySettled);
+    }
+    if state.session_id != channel.state.session_id {
+        return Err(ChannelError::WrongSession);
+    }
+    if channel.challenge_deadline != 0 && env.ledger().sequence() > channel.challenge_deadline {
+        return Err(ChannelError::ChallengeClosed);
+    }
+    if state.nonce <= channel.state.nonce {
+        return Err(ChannelError::StaleState);
+    }
+    check_winners(&state, players)?;
+    if signatures.len() != channel.keys.len() {
+        return Err(ChannelError::InvalidSignatures);
+    }
+
+    let digest = state_digest(env, &state).into();
+    for (key, signature) in channel.keys.iter().zip(signatures.iter()) {
+        env.crypto().ed25519_verify(&key, &digest, &signature);
+    }
+
+    if channel.challenge_deadline == 0 {
+        channel.challenge_deadline = env.ledger().sequence().saturating_add(CHALLENGE_LEDGERS);
+    }
+    channel.state = state;
+    save(env, session_id, &channel);
+    Ok(())
+}
+
+/// Open the challenge window without a state, for a table that stopped signing before
+/// any state was co-signed. Any seat can challenge; the others answer with a state.
+pub fn challenge(
+    env: &Env,
+    session_id: u32,
+    players: &Vec<Address>,
+    player: &Address,
+) -> Result<(), ChannelError> {
+    player.require_auth();
+
+    let mut channel = get(env, session_id)?;
+    if channel.settled {
+        return Err(ChannelError::AlreadySettled);
+    }
+    if !players.contains(player) {
+        return Err(ChannelError::NotSeat);
+    }
+    if channel.challenge_deadline != 0 {
+        if env.ledger().sequence() > channel.challenge_deadline {
+            return Err(ChannelError::ChallengeClosed);
+        }
+        return Err(ChannelError::ChallengeOpen);
+    }
+
+    channel.challenge_deadline = env.ledger().sequence().saturating_add(CHALLENGE_LEDGERS);
+    save(env, session_id, &channel);
+    Ok(())
+}
+
+/// Settle the channel: with the winners to end the session with once a finished state
+/// is in, or back to on-chain play once the window has closed without one.
+pub fn settle(
+    env: &Env,
+    session_id: u32,
+    players: &Vec<Address>,
+) -> Result<Settlement, ChannelError> {
+    let mut channel = get(env, session_id)?;
+    if channel.settled {
+        return Err(ChannelError::AlreadySettled);
+    }
+    if channel.challenge_deadline == 0 {
+        return Err(ChannelError::NoState);
+    }
+    if !channel.state.finished && env.ledger().sequence() <= channel.challenge_deadline {
+        return Err(ChannelError::ChallengeOpen);
+    }
+
+    channel.settled = true;
+    save(env, session_id, &channel);
+    if !channel.state.finished {
+        Ok(Settlement::Resume)
+    } else if channel.state.winners.is_empty() {
+        Ok(Settlement::Winners(players.clone()))
+    } else {
+        Ok(Settlement::Winners(channel.state.winners))
+    }
+}
+
+/// A game contract's sessions, as its channel entry points see them. The `*_game`
+/// functions below load the running game, map its seats in and its errors out, so a
+/// contract only settles the outcome itself.
+pub trait ChannelGame: Sized {
+    /// The contract's error; `impl_from_channel_error!` maps every `ChannelError` into it
+    type Error: From<ChannelError>;
+
+    /// The session's game, or the contract's error if it is missing or has ended
+    fn load_running(env: &Env, session_id: u32) -> Result<Self, Self::Error>;
+
+    /// Every seat's player, in seat order
+    fn seat_players(&self, env: &Env) -> Vec<Address>;
+
+    /// Whether a shared win goes to the earliest of its seats instead of ending as a tie
+    fn player1_wins_ties(&self) -> bool {
+        false
+    }
+}
+
+/// `open` for a running game's seats.
+pub fn open_game<G: ChannelGame>(
+    env: &Env,
+    session_id: u32,
+    keys: Vec<BytesN<32>>,
+) -> Result<(), G::Error> {
+    let game = G::load_running(env, session_id)?;
+    open(env, session_id, &game.seat_players(env), keys)?;
+    Ok(())
+}
+
+/// `submit` for a running game's seats.
+pub fn submit_game<G: ChannelGame>(
+    env: &Env,
+    session_id: u32,
+    state: ChannelState,
+    signatures: Vec<BytesN<64>>,
+) -> Result<(), G::Error> {
+    let game = G::load_running(env, session_id)?;
+    submit(env, session_id, &game.seat_players(env), state, signatures)?;
+    Ok(())
+}
+
+/// `challenge` for a running game's seats.
+pub fn challenge_game<G: ChannelGame>(
+    env: &Env,
+    session_id: u32,
+    player: &Address,
+) -> Result<(), G::Error> {
+    let game = G::load_running(env, session_id)?;
+    challenge(env, session_id, &game.seat_players(env), player)?;
+    Ok(())
+}
+
+/// `settle` for a running game, returned with the game to settle it on. When the game
+/// gives ties to player 1, a shared win goes to the earliest of its seats.
+pub fn settle_game<G: ChannelGame>(
+    env: &Env,
+    session_id: u32,
+) -> Result<(G, Settlement), G::Error> {
+    let game = G::load_running(env, session_id)?;
+    let players = game.seat_players(env);
+    let settlement = match settle(env, session_id, &players)? {
+        Settlement::Winners(winners) if winners.len() > 1 && game.player1_wins_ties() => {
+            let first = players.iter().find(|player| winners.contains(player));
+            Settlement::Winners(Vec::from_array(env, [first.unwrap()]))
+        }
+        settlement => settlement,
+    };
+    Ok((game, settlement))
+}
+
+/// `From<ChannelError>` for a game contract's error, which has the channel variants
+/// every game declares: `StaleChannelState`, `ChallengeClosed`, `ChallengeOpen`,
+/// `NoChannelState`, `GameAlreadyEnded`, `ChannelNotFound`, `ChannelExists`, `NotPlayer`
+/// and `InvalidChannelState`.
+#[macro_export]
+macro_rules! impl_from_channel_error {
+    ($error:ident) => {
+        impl From<$crate::ChannelError> for $error {
+            fn from(error: $crate::ChannelError) -> Self {
+                match error {
+                    $crate::ChannelError::StaleState => $error::StaleChannelState,
+                    $crate::ChannelError::ChallengeClosed => $error::ChallengeClosed,
+                    $crate::ChannelError::ChallengeOpen => $error::ChallengeOpen,
+                    $crate::ChannelError::NoState => $error::NoChannelState,
+                    $crate::ChannelError::AlreadySettled => $error::GameAlreadyEnded,
+                    $crate::ChannelError::NotFound => $error::ChannelNotFound,
+                    $crate::ChannelError::Exists => $error::ChannelExists,
+                    $crate::ChannelError::NotSeat => $error::NotPlayer,
+                    $crate::ChannelError::InvalidKeys
+                    | $crate::ChannelError::WrongSession
+                    | $crate::ChannelError::InvalidWinners
+                    | $crate::ChannelError::InvalidSignatures => $error::InvalidChannelState,
+                }
+            }
+        }
+    };
+}
+
+fn save(env: &Env, session_id: u32, channel: &Channel) {
+    let channel_key = ChannelKey::Channel(session_id);
+    env.storage().temporary().set(&channel_key, channel);
+    env.storage()
+        .temporary()
+        .extend_ttl(&channel_key, CHANNEL_TTL_LEDGERS, CHANNEL_TTL_LEDGERS);
+}
+
+fn check_winners(state: &ChannelState, players: &Vec<Address>) -> Result<(), ChannelError> {
+    if !state.finished && !state.winners.is_empty() {
+        return Err(ChannelError::InvalidWinners);
+    }
+    for (i, winner) in state.winners.iter().enumerate() {
+        let repeated = state
+            .winners
+            .iter()
+            .skip(i + 1)
+            .any(|other| other == winner);
+        if repeated || !players.contains(&winner) {
+            return Err(ChannelError::InvalidWinners);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,308 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::{
+    challenge, get, is_open, open, settle, settle_game, state_digest, submit, Channel,
+    ChannelError, ChannelGame, ChannelState, Settlement, CHALLENGE_LEDGERS,
+};
+use ed25519_dalek::{Signer as _, SigningKey};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{contract, vec, Address, BytesN, Env, Vec};
+
+// Channel functions run inside a contract, so tests run them as this one
+#[contract]
+struct Host;
+
+// A game whose seats and tie policy live in the host's instance storage
+struct Game {
+    players: Vec<Address>,
+    player1_wins_ties: bool,
+}
+
+impl ChannelGame for Game {
+    type Error = ChannelError;
+
+    fn load_running(env: &Env, _session_id: u32) -> Result<Self, ChannelError> {
+        let storage = env.storage().instance();
+        Ok(Game {
+            players: storage.get(&0u32).ok_or(ChannelError::NotFound)?,
+            player1_wins_ties: storage.get(&1u32).unwrap_or(false),
+        })
+    }
+
+    fn seat_players(&self, _env: &Env) -> Vec<Address> {
+        self.players.clone()
+    }
+
+    fn player1_wins_ties(&self) -> bool {
+        self.player1_wins_ties
+    }
+}
+
+struct Table {
+    env: Env,
+    host: Address,
+    players: Vec<Address>,
+    keys: [SigningKey; 2],
+}
+
+fn table() -> Table {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    let host = env.register(Host, ());
+    let players = vec![&env, Address::generate(&env), Address::generate(&env)];
+    let keys = [
+        SigningKey::from_bytes(&[1; 32]),
+        SigningKey::from_bytes(&[2; 32]),
+    ];
+    Table {
+        env,
+        host,
+        players,
+        keys,
+    }
+}
+
+impl Table {
+    fn open(&self) {
+        let keys = vec![
+            &self.env,
+            BytesN::from_array(&self.env, &self.keys[0].verifying_key().to_bytes()),
+            BytesN::from_array(&self.env, &self.keys[1].verifying_key().to_bytes()),
+        ];
+        self.env
+            .as_contract(&self.host, || open(&self.env, 7, &self.players, keys))
+            .unwrap()
+    }
+
+    fn channel(&self) -> Channel {
+        self.env
+            .as_contract(&self.host, || get(&self.env, 7))
+            .unwrap()
+    }
+
+    fn state(&self, nonce: u64, finished: bool, winners: Vec<Address>) -> ChannelState {
+        ChannelState {
+            session_id: 7,
+            nonce,
+            state_hash: BytesN::from_array(&self.env, &[nonce as u8; 32]),
+            finished,
+            winners,
+        }
+    }
+
+    fn sign(&self, state: &ChannelState, keys: &[SigningKey]) -> Vec<BytesN<64>> {
+        let digest = self
+            .env
+            .as_contract(&self.host, || state_digest(&self.env, state));
+        let mut signatures = Vec::new(&self.env);
+        for key in keys {
+            let signature = key.sign(&digest.to_array()).to_bytes();
+            signatures.push_back(BytesN::from_array(&self.env, &signature));
+        }
+        signatures
+    }
+
+    fn submit(&self, state: ChannelState) -> Result<(), ChannelError> {
+        let signatures = self.sign(&state, &self.keys);
+        self.env.as_contract(&self.host, || {
+            submit(&self.env, 7, &self.players, state, signatures)
+        })
+    }
+
+    fn challenge(&self, player: &Address) -> Result<(), ChannelError> {
+        self.env.as_contract(&self.host, || {
+            challenge(&self.env, 7, &self.players, player)
+        })
+    }
+
+    fn settle(&self) -> Result<Settlement, ChannelError> {
+        self.env
+            .as_contract(&self.host, || settle(&self.env, 7, &self.players))
+    }
+
+    fn advance(&self, ledgers: u32) {
+        self.env
+            .ledger()
+            .with_mut(|li| li.sequence_number += ledgers);
+    }
+}
+
+#[test]
+fn test_open_needs_a_key_per_seat() {
+    let t = table();
+    let keys = vec![&t.env, BytesN::from_array(&t.env, &[9; 32])];
+    let result = t
+        .env
+        .as_contract(&t.host, || open(&t.env, 7, &t.players, keys));
+    assert_eq!(result, Err(ChannelError::InvalidKeys));
+
+    t.open();
+    let channel = t.channel();
+    assert_eq!(channel.state.nonce, 0);
+    assert_eq!(channel.challenge_deadline, 0);
+    assert_eq!(t.settle(), Err(ChannelError::NoState));
+
+    let result = t.env.as_contract(&t.host, || {
+        open(&t.env, 7, &t.players, channel.keys.clone())
+    });
+    assert_eq!(result, Err(ChannelError::Exists));
+    let missing = t.env.as_contract(&t.host, || get(&t.env, 8));
+    assert_eq!(missing, Err(ChannelError::NotFound));
+}
+
+#[test]
+fn test_higher_nonce_overrides_during_challenge() {
+    let t = table();
+    t.open();
+
+    t.submit(t.state(3, false, Vec::new(&t.env))).unwrap();
+    assert_eq!(t.channel().challenge_deadline, 100 + CHALLENGE_LEDGERS);
+    assert_eq!(
+        t.submit(t.state(3, false, Vec::new(&t.env))),
+        Err(ChannelError::StaleState)
+    );
+
+    // The override keeps the original deadline
+    t.advance(10);
+    t.submit(t.state(5, false, Vec::new(&t.env))).unwrap();
+    let channel = t.channel();
+    assert_eq!(channel.state.nonce, 5);
+    assert_eq!(channel.challenge_deadline, 100 + CHALLENGE_LEDGERS);
+    assert_eq!(t.settle(), Err(ChannelError::ChallengeOpen));
+
+    // An unfinished state goes back to on-chain play once the window closes
+    t.advance(CHALLENGE_LEDGERS);
+    assert_eq!(
+        t.submit(t.state(6, false, Vec::new(&t.env))),
+        Err(ChannelError::ChallengeClosed)
+    );
+    assert_eq!(t.settle(), Ok(Settlement::Resume));
+    assert!(!t.env.as_contract(&t.host, || is_open(&t.env, 7)));
+    assert_eq!(t.settle(), Err(ChannelError::AlreadySettled));
+}
+
+#[test]
+fn test_challenge_without_state_resumes_play() {
+    let t = table();
+    t.open();
+    let player1 = t.players.get_unchecked(0);
+
+    assert_eq!(
+        t.challenge(&Address::generate(&t.env)),
+        Err(ChannelError::NotSeat)
+    );
+    t.challenge(&player1).unwrap();
+    assert_eq!(t.channel().challenge_deadline, 100 + CHALLENGE_LEDGERS);
+    assert_eq!(t.challenge(&player1), Err(ChannelError::ChallengeOpen));
+    assert_eq!(t.settle(), Err(ChannelError::ChallengeOpen));
+
+    // Nothing was ever co-signed, so the table plays on chain
+    t.advance(CHALLENGE_LEDGERS + 1);
+    assert_eq!(t.challenge(&player1), Err(ChannelError::ChallengeClosed));
+    assert_eq!(t.settle(), Ok(Settlement::Resume));
+    assert_eq!(t.challenge(&player1), Err(ChannelError::AlreadySettled));
+}
+
+#[test]
+fn test_challenge_is_answered_with_a_finished_state() {
+    let t = table();
+    t.open();
+    t.challenge(&t.players.get_unchecked(1)).unwrap();
+
+    // A finished draw settles with every seat
+    t.advance(10);
+    t.submit(t.state(4, true, Vec::new(&t.env))).unwrap();
+    assert_eq!(t.channel().challenge_deadline, 100 + CHALLENGE_LEDGERS);
+    assert_eq!(t.settle(), Ok(Settlement::Winners(t.players.clone())));
+}
+
+#[test]
+fn test_finished_state_settles_immediately() {
+    let t = table();
+    t.open();
+    let winner = t.players.get_unchecked(1);
+
+    t.submit(t.state(9, true, vec![&t.env, winner.clone()]))
+        .unwrap();
+    assert!(t.env.as_contract(&t.host, || is_open(&t.env, 7)));
+    assert_eq!(t.settle(), Ok(Settlement::Winners(vec![&t.env, winner])));
+    assert!(!t.env.as_contract(&t.host, || is_open(&t.env, 7)));
+    assert_eq!(
+        t.submit(t.state(10, false, Vec::new(&t.env))),
+        Err(ChannelError::AlreadySettled)
+    );
+}
+
+#[test]
+fn test_states_are_validated() {
+    let t = table();
+    t.open();
+    let player1 = t.players.get_unchecked(0);
+
+    let unfinished_with_winner = t.state(1, false, vec![&t.env, player1.clone()]);
+    assert_eq!(
+        t.submit(unfinished_with_winner),
+        Err(ChannelError::InvalidWinners)
+    );
+    let stranger = t.state(1, true, vec![&t.env, Address::generate(&t.env)]);
+    assert_eq!(t.submit(stranger), Err(ChannelError::InvalidWinners));
+    let repeated = t.state(1, true, vec![&t.env, player1.clone(), player1]);
+    assert_eq!(t.submit(repeated), Err(ChannelError::InvalidWinners));
+
+    let mut other_session = t.state(1, false, Vec::new(&t.env));
+    other_session.session_id = 8;
+    assert_eq!(t.submit(other_session), Err(ChannelError::WrongSession));
+
+    let state = t.state(1, false, Vec::new(&t.env));
+    let one_signature = t.sign(&state, &t.keys[..1]);
+    let result = t.env.as_contract(&t.host, || {
+        submit(&t.env, 7, &t.players, state, one_signature)
+    });
+    assert_eq!(result, Err(ChannelError::InvalidSignatures));
+    assert_eq!(t.channel().state.nonce, 0);
+}
+
+#[test]
+#[should_panic]
+fn test_signature_from_another_key_is_rejected() {
+    let t = table();
+    t.open();
+    let state = t.state(1, false, Vec::new(&t.env));
+    let forged = t.sign(
+        &state,
+        &[t.keys[0].clone(), SigningKey::from_bytes(&[3; 32])],
+    );
+    let _ = t
+        .env
+        .as_contract(&t.host, || submit(&t.env, 7, &t.players, state, forged));
+}
+
+#[test]
+fn test_settle_game_gives_ties_to_the_earliest_seat() {
+    for player1_wins_ties in [false, true] {
+        let t = table();
+        t.open();
+        let winners = vec![
+            &t.env,
+            t.players.get_unchecked(1),
+            t.players.get_unchecked(0),
+        ];
+        t.submit(t.state(3, true, winners.clone())).unwrap();
+
+        let settlement = t.env.as_contract(&t.host, || {
+            let storage = t.env.storage().instance();
+            storage.set(&0u32, &t.players);
+            storage.set(&1u32, &player1_wins_ties);
+            settle_game::<Game>(&t.env, 7).map(|(_, settlement)| settlement)
+        });
+        let expected = if player1_wins_ties {
+            vec![&t.env, t.players.get_unchecked(0)]
+        } else {
+            winners
+        };
+        assert_eq!(settlement, Ok(Settlement::Winners(expected)));
+    }
+}
@@ -650,9 +650,28 @@ for (const contract of contracts) {
     const wasmHash = installResult.trim();
     console.log(`  WASM hash: ${wasmHash}`);
 
+    if (contract.packageName === "game-account") {
+      // Each player deploys their own account with a signer and policy, from this hash
+      console.log(`✅ ${contract.packageName} installed; deploy one per player from ${wasmHash}\n`);
+      continue;
+    }
+
     console.log("  Deploying and initializing...");
-    const deployResult =
-      await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- --admin ${adminAddress} --game-hub ${mockGameHubId}`.text();
+    let deployResult: string;
+    if (contract.packageName === "randomness-beacon") {
+      // The beacon takes bonds in the native asset instead of talking to the Game Hub
+      const nativeTokenId =
+        (await $`stellar contract id asset --asset native --network ${NETWORK}`.text()).trim();
+      if (isLocal) {
+        // The native asset contract may not be deployed yet on a fresh local network
+        await $`stellar contract asset deploy --asset native --source-account ${adminSecret} --network ${NETWORK}`.quiet().nothrow();
+      }
+      deployResult =
+        await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- --admin ${adminAddress} --token ${nativeTokenId}`.text();
+    } else {
+      deployResult =
+        await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- --admin ${adminAddress} --game-hub ${mockGameHubId}`.text();
+    }
     const contractId = deployResult.trim();
     deployed[contract.packageName] = contractId;
     if (contract.packageName === "my-game") {